@@ -0,0 +1,15 @@
+//! Feed arbitrary bytes to the turn parser
+//!
+//! Run with `cargo fuzz run parse_game_input` from the repository root.
+//! Any input must produce a `GameInput` or a `FillerError`; a panic is a
+//! crash that libFuzzer reports and minimizes.
+
+#![no_main]
+
+use filler::parser::parse_game_input_from;
+use libfuzzer_sys::fuzz_target;
+use std::io::Cursor;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_game_input_from(&mut Cursor::new(data));
+});
@@ -0,0 +1,232 @@
+/// Epsilon-greedy strategy selection
+///
+/// Which strategy works best can shift over a game. The selector keeps the
+/// average territory gain each `AIStrategy` achieved on the turns it was
+/// used and mostly plays the best one so far, occasionally trying another
+/// at random. A turn's gain is measured when the next position comes in:
+/// the growth of our territory since the previous `select` call.
+///
+/// When the opponent's last piece claimed at least `REACTIVE_MIN_CELLS`
+/// cells, the selector answers it with `strategies::reactive` instead.
+
+use crate::game_state::GameState;
+use crate::placement::Placement;
+use super::{select_move, strategies, AIStrategy};
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+/// Probability of trying a random strategy instead of the best one
+pub const ADAPTIVE_EPSILON: f64 = 0.1;
+
+/// Cells an opponent move must claim for the selector to react to it
+pub const REACTIVE_MIN_CELLS: usize = 4;
+
+/// Territory gain record of one strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyRecord {
+    pub strategy: AIStrategy,
+    /// Sum of the territory gained on the turns it was used
+    pub total_gain: f64,
+    pub plays: u32,
+}
+
+impl StrategyRecord {
+    /// Mean gain per play, 0 if never played
+    pub fn average_gain(&self) -> f64 {
+        if self.plays == 0 {
+            0.0
+        } else {
+            self.total_gain / self.plays as f64
+        }
+    }
+}
+
+/// Picks a strategy per turn by epsilon-greedy over past territory gains
+#[derive(Debug)]
+pub struct AdaptiveStrategySelector {
+    epsilon: f64,
+    rng: StdRng,
+    /// One record per `AIStrategy::all()` variant, in the same order
+    records: Vec<StrategyRecord>,
+    /// Strategy used on the last turn and our territory size before it
+    pending: Option<(usize, usize)>,
+    /// Position seen by the last `select` call, to find the opponent's reply
+    previous: Option<GameState>,
+}
+
+impl AdaptiveStrategySelector {
+    /// Create a selector exploring with probability `epsilon`
+    ///
+    /// `seed` drives the exploration draws, so a game replays identically.
+    pub fn new(epsilon: f64, seed: u64) -> Self {
+        AdaptiveStrategySelector {
+            epsilon,
+            rng: StdRng::seed_from_u64(seed),
+            records: AIStrategy::all()
+                .into_iter()
+                .map(|strategy| StrategyRecord { strategy, total_gain: 0.0, plays: 0 })
+                .collect(),
+            pending: None,
+            previous: None,
+        }
+    }
+
+    /// Credit the previous turn's strategy, then pick a move
+    ///
+    /// If the opponent claimed `REACTIVE_MIN_CELLS` or more cells since the
+    /// last call, the move comes from `strategies::reactive` and is not
+    /// credited to any strategy. Otherwise, with probability `1 - epsilon`
+    /// the strategy with the highest average gain is used (ties go to the
+    /// first in `AIStrategy::all()` order), else a uniformly random one.
+    /// Returns `None` if there are no placements; the previous turn is
+    /// still credited.
+    pub fn select(&mut self, placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+        let size = game_state.get_my_territory_size();
+        if let Some((index, before)) = self.pending.take() {
+            let strategy = self.records[index].strategy;
+            self.record(strategy, size.saturating_sub(before) as f64);
+        }
+        let opponent_move = self
+            .previous
+            .replace(game_state.clone())
+            .map(|previous| GameState::diff(&previous, game_state).placed_by(game_state.opponent_number()))
+            .unwrap_or_default();
+        if placements.is_empty() {
+            return None;
+        }
+        if opponent_move.len() >= REACTIVE_MIN_CELLS {
+            return strategies::reactive(placements, game_state, Some(&opponent_move));
+        }
+
+        let index = if self.rng.random::<f64>() < self.epsilon {
+            self.rng.random_range(0..self.records.len())
+        } else {
+            self.best_index()
+        };
+        self.pending = Some((index, size));
+        select_move(placements, game_state, self.records[index].strategy)
+    }
+
+    /// Add one play of `strategy` that gained `gain` cells
+    ///
+    /// Strategies outside `AIStrategy::all()` are ignored.
+    pub fn record(&mut self, strategy: AIStrategy, gain: f64) {
+        if let Some(record) = self.records.iter_mut().find(|r| r.strategy == strategy) {
+            record.total_gain += gain;
+            record.plays += 1;
+        }
+    }
+
+    /// Record of `strategy`, if the selector tracks it
+    pub fn record_for(&self, strategy: AIStrategy) -> Option<&StrategyRecord> {
+        self.records.iter().find(|r| r.strategy == strategy)
+    }
+
+    /// Strategy with the highest average gain so far
+    pub fn best_strategy(&self) -> AIStrategy {
+        self.records[self.best_index()].strategy
+    }
+
+    fn best_index(&self) -> usize {
+        let mut best: Option<(usize, f64)> = None;
+        for (index, record) in self.records.iter().enumerate() {
+            let gain = record.average_gain();
+            if best.is_none_or(|(_, best_gain)| gain > best_gain) {
+                best = Some((index, gain));
+            }
+        }
+        best.map_or(0, |(index, _)| index)
+    }
+}
+
+impl Default for AdaptiveStrategySelector {
+    fn default() -> Self {
+        Self::new(ADAPTIVE_EPSILON, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{Position, Shape};
+    use crate::placement::find_all_valid_placements;
+
+    fn game_state(territory: &[Position]) -> GameState {
+        GameState::builder_from_size(8, 8)
+            .with_territory(1, territory)
+            .with_territory(2, &[Position::new(7, 7)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build()
+    }
+
+    #[test]
+    fn test_greedy_follows_average_gain() {
+        let mut selector = AdaptiveStrategySelector::new(0.0, 1);
+        assert_eq!(selector.best_strategy(), AIStrategy::all()[0]);
+
+        selector.record(AIStrategy::Defensive, 4.0);
+        selector.record(AIStrategy::Defensive, 0.0);
+        selector.record(AIStrategy::Balanced, 3.0);
+        selector.record(AIStrategy::Mcts(10), 100.0);
+
+        assert_eq!(selector.best_strategy(), AIStrategy::Balanced);
+        assert_eq!(selector.record_for(AIStrategy::Defensive).unwrap().average_gain(), 2.0);
+        assert!(selector.record_for(AIStrategy::Mcts(10)).is_none());
+    }
+
+    #[test]
+    fn test_select_credits_previous_turn() {
+        let mut selector = AdaptiveStrategySelector::new(0.0, 1);
+        let before = game_state(&[Position::new(2, 2)]);
+        let placements = find_all_valid_placements(&before);
+
+        let placement = selector.select(&placements, &before).unwrap();
+        assert!(placements.contains(&placement));
+        let after = game_state(&[Position::new(2, 2), Position::new(3, 2)]);
+        assert!(selector.select(&[], &after).is_none());
+
+        let record = selector.record_for(AIStrategy::all()[0]).unwrap();
+        assert_eq!((record.plays, record.total_gain), (1, 1.0));
+    }
+
+    #[test]
+    fn test_reacts_to_large_opponent_move() {
+        let mut selector = AdaptiveStrategySelector::new(0.0, 1);
+        let before = game_state(&[Position::new(2, 2)]);
+        selector.select(&find_all_valid_placements(&before), &before);
+
+        let mut after = game_state(&[Position::new(2, 2), Position::new(3, 2)]);
+        for x in 3..7 {
+            after.set_cell(Position::new(x, 6), crate::game_state::CellState::Player2);
+        }
+        let placements = find_all_valid_placements(&after);
+        let opponent_move: Vec<Position> = (3..7).map(|x| Position::new(x, 6)).collect();
+
+        assert_eq!(
+            selector.select(&placements, &after),
+            strategies::reactive(&placements, &after, Some(&opponent_move))
+        );
+        // The reactive turn is not credited to any strategy
+        selector.select(&[], &after);
+        let plays: u32 = AIStrategy::all().iter().map(|&s| selector.record_for(s).unwrap().plays).sum();
+        assert_eq!(plays, 1);
+    }
+
+    #[test]
+    fn test_exploration_tries_other_strategies() {
+        let mut selector = AdaptiveStrategySelector::new(1.0, 3);
+        let state = game_state(&[Position::new(2, 2)]);
+        let placements = find_all_valid_placements(&state);
+
+        for _ in 0..30 {
+            selector.select(&placements, &state);
+        }
+        selector.select(&[], &state);
+
+        let tried = AIStrategy::all()
+            .into_iter()
+            .filter(|&s| selector.record_for(s).unwrap().plays > 0)
+            .count();
+        assert!(tried > 1);
+    }
+}
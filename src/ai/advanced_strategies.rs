@@ -4,135 +4,430 @@
 /// sophisticated analysis including predictive blocking, territory control,
 /// and opponent modeling.
 
-use crate::game_state::GameState;
-use crate::placement::Placement;
+use crate::game_state::{CellState, GameState, Grid, Position};
+use crate::placement::{filter_near_opponent, Placement, PlacementConstraint};
+use crate::utils::convex_hull;
 use super::heuristics::{
-    analyze_flood_fill, detect_weak_positions, analyze_density, 
-    analyze_edge_control, advanced_score
+    analyze_flood_fill, weak_positions_with_map, analyze_density, 
+    analyze_edge_control, analyze_growth_projection,
+    analyze_opponent_growth_projection, influence_gain_with_map,
+    connectivity_gain_with_features, territory_convexity_ratio, HeuristicWeights, ScoreMaps, WeaknessMap,
+    bottleneck_score_with_map, chokepoint_score_with_set, DEFAULT_WEAKNESS_THRESHOLD
 };
+use super::optimization::TurnCache;
+use super::score::Score;
+use super::territory::compute_voronoi_partition;
 
-/// Aggressive expansion strategy that prioritizes growth potential
-pub fn aggressive_expansion(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
-    if placements.is_empty() {
-        return None;
+/// Scoring weights for every strategy in this module
+/// 
+/// Each field is the multiplier applied to one heuristic term inside one
+/// strategy. `EvalWeights::default()` reproduces the original hand-tuned
+/// constants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalWeights {
+    // aggressive_expansion
+    pub aggressive_cells: f32,
+    pub aggressive_flood_fill: f32,
+    pub aggressive_spread: f32,
+    // opportunistic
+    pub opportunistic_weak: f32,
+    pub opportunistic_cells: f32,
+    // defensive
+    pub defensive_density: f32,
+    pub defensive_touches: f32,
+    pub defensive_edge: f32,
+    pub defensive_core: f32,
+    pub defensive_convexity: f32,
+    // strategic_blocking
+    pub blocking_weak: f32,
+    pub blocking_touches: f32,
+    pub blocking_cells: f32,
+    pub blocking_chokepoints: f32,
+    // advanced_balanced
+    pub balanced_cells: f32,
+    pub balanced_flood_fill: f32,
+    pub balanced_weak: f32,
+    pub balanced_density: f32,
+    pub balanced_edge: f32,
+    pub balanced_influence: f32,
+    pub balanced_connectivity: f32,
+    pub balanced_bottleneck: f32,
+    // territorial_control
+    pub territorial_cells: f32,
+    pub territorial_flood_fill: f32,
+    pub territorial_touches: f32,
+    pub territorial_edge: f32,
+    pub territorial_region: f32,
+    pub territorial_fragmentation: f32,
+    // threat_aware
+    pub threat_cells: f32,
+    pub threat_penalty: f32,
+}
+
+impl Default for EvalWeights {
+    fn default() -> Self {
+        EvalWeights {
+            aggressive_cells: 10.0,
+            aggressive_flood_fill: 2.0,
+            aggressive_spread: 1.0,
+            opportunistic_weak: 2.5,
+            opportunistic_cells: 5.0,
+            defensive_density: 2.0,
+            defensive_touches: 2.0,
+            defensive_edge: 1.5,
+            defensive_core: 1.0,
+            defensive_convexity: 20.0,
+            blocking_weak: 1.8,
+            blocking_touches: 3.0,
+            blocking_cells: 3.0,
+            blocking_chokepoints: 2.0,
+            balanced_cells: 10.0,
+            balanced_flood_fill: 1.5,
+            balanced_weak: 2.0,
+            balanced_density: 1.2,
+            balanced_edge: 0.5,
+            balanced_influence: 1.0,
+            balanced_connectivity: 1.0,
+            balanced_bottleneck: 0.5,
+            territorial_cells: 8.0,
+            territorial_flood_fill: 1.5,
+            territorial_touches: 1.5,
+            territorial_edge: 0.8,
+            territorial_region: 1.0,
+            territorial_fragmentation: 5.0,
+            threat_cells: 10.0,
+            threat_penalty: 1.0,
+        }
     }
-    
-    placements
-        .iter()
-        .max_by(|a, b| {
-            let score_a = (a.cells_added as f32) * 10.0 
-                + analyze_flood_fill(a, game_state) * 2.0;
-            let score_b = (b.cells_added as f32) * 10.0 
-                + analyze_flood_fill(b, game_state) * 2.0;
-            
-            score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .cloned()
 }
 
-/// Opportunistic strategy that attacks weak opponent positions
-pub fn opportunistic(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
-    if placements.is_empty() {
-        return None;
+impl EvalWeights {
+    /// Default weights with the `advanced_balanced` terms taken from `weights`
+    ///
+    /// `advanced_balanced` scores the same terms as `advanced_score`, so
+    /// this carries `HeuristicWeights` (e.g. from `HeuristicWeights::from_env`)
+    /// into strategy selection.
+    pub fn from_heuristics(weights: &HeuristicWeights) -> Self {
+        EvalWeights {
+            balanced_cells: weights.expansion,
+            balanced_flood_fill: weights.flood_fill,
+            balanced_weak: weights.weak_positions,
+            balanced_density: weights.density,
+            balanced_edge: weights.edge_control,
+            balanced_influence: weights.influence,
+            balanced_connectivity: weights.connectivity,
+            balanced_bottleneck: weights.bottleneck,
+            ..EvalWeights::default()
+        }
     }
-    
-    placements
-        .iter()
-        .max_by(|a, b| {
-            let score_a = detect_weak_positions(a, game_state) * 2.5
-                + (a.cells_added as f32) * 5.0;
-            let score_b = detect_weak_positions(b, game_state) * 2.5
-                + (b.cells_added as f32) * 5.0;
-            
-            score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .cloned()
 }
 
+/// Pick the placement with the highest score
+///
+/// Each placement is scored once; on ties the last placement wins.
+fn max_by_score<F>(placements: &[Placement], score: F) -> Option<Placement>
+where
+    F: Fn(&Placement) -> Score,
+{
+    placements.iter().max_by_key(|p| score(p)).cloned()
+}
+
+/// Territory spread below which `aggressive_expansion` rewards breaking out
+///
+/// A mean squared distance of 4 is a solid 5x5 block.
+pub const AGGRESSIVE_SPREAD_THRESHOLD: f32 = 4.0;
+
+/// Aggressive expansion strategy that prioritizes growth potential
+///
+/// While our territory is still a tight cluster (spread below
+/// `AGGRESSIVE_SPREAD_THRESHOLD`, see `Grid::territory_spread`), placements
+/// are also rewarded for how much they increase the spread, so the
+/// territory breaks out early instead of growing in one spot.
+pub fn aggressive_expansion(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    let player = game_state.player_number();
+    let spread = game_state.grid().territory_spread(player);
+    let spread_gain = |p: &Placement| {
+        if spread >= AGGRESSIVE_SPREAD_THRESHOLD {
+            return 0.0;
+        }
+        let mut after = game_state.grid().grid().clone();
+        after.apply_placement(p, player);
+        after.territory_spread(player) - spread
+    };
+
+    let opponent = game_state.opponent_number();
+    max_by_score(placements, |p| {
+        Score(
+            (p.cells_added as f32) * weights.aggressive_cells
+                + analyze_flood_fill(p, &p.simulate(game_state.grid(), player), opponent) * weights.aggressive_flood_fill
+                + spread_gain(p) * weights.aggressive_spread
+        )
+    })
+}
+
+/// Opportunistic strategy that attacks weak opponent positions
+pub fn opportunistic(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    let weakness = WeaknessMap::new(game_state);
+    max_by_score(placements, |p| {
+        Score(
+            weak_positions_with_map(p, &weakness, DEFAULT_WEAKNESS_THRESHOLD) * weights.opportunistic_weak
+                + (p.cells_added as f32) * weights.opportunistic_cells
+        )
+    })
+}
+
+/// Own neighbors a cell needs to count as solid core in `defensive`
+const DEFENSIVE_CORE_RADIUS: usize = 2;
+
+/// Manhattan distance from the opponent `defensive` keeps when it can
+/// (2: never touching an opponent cell)
+const DEFENSIVE_OPPONENT_DISTANCE: usize = 2;
+
 /// Defensive strategy that consolidates territory and maximizes density
-pub fn defensive(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
-    if placements.is_empty() {
-        return None;
-    }
-    
-    placements
-        .iter()
-        .max_by(|a, b| {
-            let score_a = analyze_density(a, game_state) * 2.0
-                + (a.territory_touches as f32) * 2.0
-                + analyze_edge_control(a, &game_state.grid) * 1.5;
-            let score_b = analyze_density(b, game_state) * 2.0
-                + (b.territory_touches as f32) * 2.0
-                + analyze_edge_control(b, &game_state.grid) * 1.5;
-            
-            score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .cloned()
+///
+/// Also rewards growth of the solid core of our territory (cells left by
+/// `Grid::erode_territory` with `DEFENSIVE_CORE_RADIUS`), so placements
+/// that thicken the territory beat ones that add thin extensions.
+/// Placements that raise `territory_convexity_ratio` fill the concave gaps
+/// of the territory, and count for more the more concave it is.
+/// Placements that keep `DEFENSIVE_OPPONENT_DISTANCE` from the opponent
+/// cannot be answered by an adjacent counter-attack and are preferred:
+/// the rest are only considered when no such placement exists.
+pub fn defensive(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    let player = game_state.player_number();
+    let core = |grid: &Grid| grid.erode_territory(player, DEFENSIVE_CORE_RADIUS).count_territory(player);
+    let core_before = core(game_state.grid());
+    let territory = game_state.grid().get_player_positions(player);
+    let hull = convex_hull(&territory);
+    let convexity_before = territory_convexity_ratio(&territory, &hull);
+
+    let keep_off = PlacementConstraint { require_opponent_distance: Some(DEFENSIVE_OPPONENT_DISTANCE) };
+    let safe: Vec<Placement> = placements.iter().filter(|p| keep_off.allows(p, game_state)).cloned().collect();
+    let candidates = if safe.is_empty() { placements } else { &safe };
+
+    max_by_score(candidates, |p| {
+        let mut after = game_state.grid().grid().clone();
+        after.apply_placement(p, player);
+        let core_gain = core(&after) as f32 - core_before as f32;
+        // Only the old hull's vertices and the new cells can be vertices of
+        // the new hull
+        let new_cells: Vec<Position> = p
+            .get_absolute_positions()
+            .into_iter()
+            .filter(|&pos| game_state.grid().get(pos) == Some(CellState::Empty))
+            .collect();
+        let hull_after = convex_hull(&[hull.as_slice(), &new_cells].concat());
+        let territory_after = [territory.as_slice(), &new_cells].concat();
+        let convexity_after = territory_convexity_ratio(&territory_after, &hull_after);
+        let convexity_gain = (convexity_after - convexity_before) * (1.0 - convexity_before);
+
+        Score(
+            analyze_density(p, game_state) * weights.defensive_density
+                + (p.territory_touches as f32) * weights.defensive_touches
+                + analyze_edge_control(p, game_state.grid()) * weights.defensive_edge
+                + core_gain * weights.defensive_core
+                + convexity_gain * weights.defensive_convexity
+        )
+    })
 }
 
+/// Number of unopposed moves simulated when projecting growth for blocking
+const BLOCKING_PROJECTION_TURNS: usize = 3;
+
+/// Manhattan reach from the opponent within which blocking moves are sought
+const BLOCKING_MAX_DISTANCE: usize = 3;
+
+/// Centroid distance, as a fraction of the board diagonal, under which the
+/// battle fronts count as close enough to block
+const BLOCKING_FRONT_RATIO: f32 = 0.25;
+
 /// Strategic blocking strategy that tries to deny opponent territory
-pub fn strategic_blocking(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+///
+/// Projections are computed afresh; `select_move_with_cache` passes a
+/// per-turn cache to `strategic_blocking_cached` instead.
+pub fn strategic_blocking(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    strategic_blocking_cached(placements, game_state, weights, &mut TurnCache::new())
+}
+
+/// Strategic blocking using a shared per-turn projection cache
+///
+/// If the opponent is projected to out-grow us, or the territory centroids
+/// are within `BLOCKING_FRONT_RATIO` of the board diagonal (see
+/// `GameState::territory_centroid_distance`), placements close to the
+/// opponent's frontier are preferred (candidates are first narrowed to
+/// those within `BLOCKING_MAX_DISTANCE` of the opponent, when any are);
+/// otherwise weak (distant) positions are.
+pub fn strategic_blocking_cached(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+    cache: &mut TurnCache,
+) -> Option<Placement> {
     if placements.is_empty() {
         return None;
     }
     
-    placements
-        .iter()
-        .max_by(|a, b| {
-            // Prioritize positions that block opponent from expanding
-            // by maximizing weak position detection (offensive blocking)
-            // combined with territory touch count (defensive blocking)
-            let score_a = detect_weak_positions(a, game_state) * 1.8
-                + (a.territory_touches as f32) * 3.0
-                + (a.cells_added as f32) * 3.0;
-            let score_b = detect_weak_positions(b, game_state) * 1.8
-                + (b.territory_touches as f32) * 3.0
-                + (b.cells_added as f32) * 3.0;
-            
-            score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .cloned()
+    let opponent = game_state.opponent_number();
+    let opponent_gain = cache.get_or_compute_projection((opponent, BLOCKING_PROJECTION_TURNS), || {
+        analyze_opponent_growth_projection(game_state, BLOCKING_PROJECTION_TURNS)
+    });
+    let own_gain = cache.get_or_compute_projection(
+        (game_state.player_number(), BLOCKING_PROJECTION_TURNS),
+        || analyze_growth_projection(game_state, game_state.player_number(), BLOCKING_PROJECTION_TURNS),
+    );
+    // Negative projected advantage: opponent out-grows us, so move towards them.
+    // Close fronts are contested regardless of the projection.
+    let diagonal = (game_state.grid().width as f32).hypot(game_state.grid().height as f32);
+    let fronts_close = game_state.territory_centroid_distance() <= diagonal * BLOCKING_FRONT_RATIO;
+    let blocking = own_gain - opponent_gain < 0.0 || fronts_close;
+    let weak_weight = if blocking {
+        -weights.blocking_weak
+    } else {
+        weights.blocking_weak
+    };
+    let nearby = if blocking {
+        filter_near_opponent(placements, game_state, BLOCKING_MAX_DISTANCE)
+    } else {
+        Vec::new()
+    };
+    let candidates = if nearby.is_empty() { placements } else { &nearby };
+    
+    // Prioritize positions that block opponent from expanding
+    // by weighting weak position detection (offensive blocking)
+    // combined with territory touch count (defensive blocking)
+    // and chokepoints that split the empty space
+    let weakness = WeaknessMap::new(game_state);
+    let chokepoints = game_state.grid().articulation_points().into_iter().collect();
+    max_by_score(candidates, |p| {
+        Score(
+            weak_positions_with_map(p, &weakness, DEFAULT_WEAKNESS_THRESHOLD) * weak_weight
+                + (p.territory_touches as f32) * weights.blocking_touches
+                + (p.cells_added as f32) * weights.blocking_cells
+                + chokepoint_score_with_set(p, &chokepoints) * weights.blocking_chokepoints
+        )
+    })
 }
 
 /// Advanced balanced strategy using all heuristics
 /// This is the new default strategy for Phase 5
-pub fn advanced_balanced(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
-    if placements.is_empty() {
-        return None;
-    }
-    
-    placements
-        .iter()
-        .max_by(|a, b| {
-            let score_a = advanced_score(a, game_state);
-            let score_b = advanced_score(b, game_state);
-            
-            score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .cloned()
+pub fn advanced_balanced(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    // Same terms as `advanced_score`, with configurable weights
+    let maps = ScoreMaps::new(game_state);
+    let player = game_state.player_number();
+    max_by_score(placements, |p| {
+        let simulated = p.simulate(game_state.grid(), game_state.player_number());
+        Score(
+            (p.cells_added as f32) * weights.balanced_cells
+                + analyze_flood_fill(p, &simulated, game_state.opponent_number()) * weights.balanced_flood_fill
+                + weak_positions_with_map(p, &maps.weakness, DEFAULT_WEAKNESS_THRESHOLD) * weights.balanced_weak
+                + analyze_density(p, game_state) * weights.balanced_density
+                + analyze_edge_control(p, game_state.grid()) * weights.balanced_edge
+                + influence_gain_with_map(p, game_state, &maps.influence) * weights.balanced_influence
+                + connectivity_gain_with_features(&maps.components, &simulated, player) * weights.balanced_connectivity
+                + bottleneck_score_with_map(p, &maps.bottlenecks) * weights.balanced_bottleneck
+        )
+    })
 }
 
 /// Territorial control strategy that balances multiple objectives
-pub fn territorial_control(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
-    if placements.is_empty() {
-        return None;
-    }
-    
-    placements
-        .iter()
-        .max_by(|a, b| {
-            let score_a = (a.cells_added as f32) * 8.0
-                + analyze_flood_fill(a, game_state) * 1.5
-                + (a.territory_touches as f32) * 1.5
-                + analyze_edge_control(a, &game_state.grid) * 0.8;
-            let score_b = (b.cells_added as f32) * 8.0
-                + analyze_flood_fill(b, game_state) * 1.5
-                + (b.territory_touches as f32) * 1.5
-                + analyze_edge_control(b, &game_state.grid) * 0.8;
-            
-            score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .cloned()
+///
+/// Used for the late game until fewer than `ENDGAME_EMPTY_PERCENT` of the
+/// cells are empty and `EndgameMaximizer` takes over (see
+/// `strategy_for_state`), so it also rewards the empty-region advantage
+/// after the placement (see `GameState::empty_region_advantage`): once the
+/// board splits into pockets, keeping access to the biggest one decides
+/// the game. Fragmentation (see `GameState::board_fragmentation`) is
+/// scaled by our share of the territory lead: a split board locks in the
+/// current standings, which suits the leader and hurts the trailer.
+pub fn territorial_control(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    max_by_score(placements, |p| {
+        let after = game_state.apply_placement(p);
+        // `after` has the opponent to move, so its advantage is theirs
+        let region_advantage = -after.empty_region_advantage();
+        let mine = after.grid().count_territory(game_state.player_number()) as f32;
+        let theirs = after.grid().count_territory(game_state.opponent_number()) as f32;
+        let lead = if mine + theirs > 0.0 { (mine - theirs) / (mine + theirs) } else { 0.0 };
+
+        Score(
+            (p.cells_added as f32) * weights.territorial_cells
+                + analyze_flood_fill(p, after.grid(), game_state.opponent_number()) * weights.territorial_flood_fill
+                + (p.territory_touches as f32) * weights.territorial_touches
+                + analyze_edge_control(p, game_state.grid()) * weights.territorial_edge
+                + (region_advantage as f32) * weights.territorial_region
+                + after.board_fragmentation() * lead * weights.territorial_fragmentation
+        )
+    })
+}
+
+/// Threat-aware strategy that steers clear of cells the opponent contests
+///
+/// Builds the opponent's threat map (see `Grid::compute_threat_map`) for
+/// the current piece, since their next piece is unknown, and penalizes
+/// each placement by the threat on the cells it claims.
+pub fn threat_aware(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    let threat = game_state
+        .grid()
+        .compute_threat_map(game_state.opponent_number(), &game_state.current_piece);
+
+    max_by_score(placements, |p| {
+        let contested: u32 = p
+            .get_absolute_positions()
+            .iter()
+            .map(|pos| threat[pos.y][pos.x] as u32)
+            .sum();
+
+        Score(
+            (p.cells_added as f32) * weights.threat_cells
+                - (contested as f32) * weights.threat_penalty
+        )
+    })
+}
+
+/// Voronoi strategy that maximizes post-placement reachable territory
+///
+/// Scores each placement by (own territory + empty cells we reach first)
+/// minus the same for the opponent, after applying the placement.
+pub fn territorial_voronoi(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+    let player = game_state.player_number();
+    let opponent = game_state.opponent_number();
+
+    max_by_score(placements, |p| {
+        let mut grid = game_state.grid().grid().clone();
+        grid.apply_placement(p, player);
+        let voronoi = compute_voronoi_partition(&grid);
+
+        let ours = grid.count_territory(player) + voronoi.cells_for(player);
+        let theirs = grid.count_territory(opponent) + voronoi.cells_for(opponent);
+        Score(ours as f32 - theirs as f32)
+    })
 }
 
 #[cfg(test)]
@@ -150,16 +445,12 @@ mod tests {
     }
 
     fn create_test_game_state() -> GameState {
-        let raw = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '@', '@', '.', '.'],
-            vec!['.', '@', '.', '.', '.'],
-            vec!['.', '.', '.', '$', '$'],
-            vec!['.', '.', '.', '$', '.'],
-        ];
-        let grid = crate::game_state::Grid::from_chars(5, 5, raw);
-        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
-        GameState::new(1, grid, shape)
+        use crate::game_state::Position;
+        GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(1, 2)])
+            .with_territory(2, &[Position::new(3, 3), Position::new(4, 3), Position::new(3, 4)])
+            .with_piece(Shape::from_chars(1, 1, vec![vec!['#']]))
+            .build()
     }
 
     #[test]
@@ -171,19 +462,45 @@ mod tests {
             create_test_placement(2, 2, 2, 2),
         ];
         
-        let best = aggressive_expansion(&placements, &game_state);
+        let best = aggressive_expansion(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_some());
         
         let selected = best.unwrap();
         assert_eq!(selected.cells_added, 3);
     }
 
+    #[test]
+    fn test_aggressive_expansion_breaks_out_of_cluster() {
+        use crate::game_state::Position;
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(8, 8)
+            .with_territory(1, &[Position::new(3, 3), Position::new(4, 3), Position::new(3, 4), Position::new(4, 4)])
+            .with_piece(Shape::from_chars(3, 1, vec![vec!['#', '#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+        let weights = EvalWeights {
+            aggressive_cells: 0.0,
+            aggressive_flood_fill: 0.0,
+            ..EvalWeights::default()
+        };
+        let spread = |p: &Placement| {
+            let mut after = game_state.grid().grid().clone();
+            after.apply_placement(p, 1);
+            after.territory_spread(1)
+        };
+
+        let best = aggressive_expansion(&placements, &game_state, &weights).unwrap();
+        assert!(placements.iter().all(|p| spread(p) <= spread(&best)));
+        assert!(spread(&best) > game_state.grid().territory_spread(1));
+    }
+
     #[test]
     fn test_aggressive_expansion_empty() {
         let game_state = create_test_game_state();
         let placements = vec![];
         
-        let best = aggressive_expansion(&placements, &game_state);
+        let best = aggressive_expansion(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_none());
     }
 
@@ -196,7 +513,7 @@ mod tests {
             create_test_placement(2, 2, 1, 1),
         ];
         
-        let best = opportunistic(&placements, &game_state);
+        let best = opportunistic(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_some());
     }
 
@@ -209,10 +526,52 @@ mod tests {
             create_test_placement(2, 2, 2, 1),
         ];
         
-        let best = defensive(&placements, &game_state);
+        let best = defensive(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_some());
     }
 
+    #[test]
+    fn test_defensive_fills_concave_gaps() {
+        // An L of 5 cells; the domino at (0, 1) fills its inner corner, the
+        // one at (2, 2) extends its foot
+        let territory = [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)].map(|(x, y)| Position::new(x, y));
+        let domino = Shape::from_chars(2, 1, vec![vec!['#', '#']]);
+        let game_state = GameState::builder_from_size(6, 6)
+            .with_territory(1, &territory)
+            .with_territory(2, &[Position::new(5, 5)])
+            .with_piece(domino.clone())
+            .build();
+        let placement = |x, y| Placement { position: Position::new(x, y), shape: domino.clone(), cells_added: 1, territory_touches: 1 };
+        let weights = EvalWeights {
+            defensive_density: 0.0,
+            defensive_touches: 0.0,
+            defensive_edge: 0.0,
+            defensive_core: 0.0,
+            ..EvalWeights::default()
+        };
+
+        let best = defensive(&[placement(0, 1), placement(2, 2)], &game_state, &weights).unwrap();
+        assert_eq!(best.position, Position::new(0, 1));
+    }
+
+    #[test]
+    fn test_defensive_keeps_off_the_opponent() {
+        // Both dominoes add one cell; the one toward (4, 1) would touch it
+        let domino = Shape::from_chars(2, 1, vec![vec!['#', '#']]);
+        let game_state = GameState::builder_from_size(6, 3)
+            .with_territory(1, &[Position::new(2, 1)])
+            .with_territory(2, &[Position::new(4, 1)])
+            .with_piece(domino.clone())
+            .build();
+        let placement = |x| Placement { position: Position::new(x, 1), shape: domino.clone(), cells_added: 1, territory_touches: 1 };
+
+        let best = defensive(&[placement(1), placement(2)], &game_state, &EvalWeights::default()).unwrap();
+        assert_eq!(best.position, Position::new(1, 1));
+        // With no safe placement the others are still played
+        let forced = defensive(&[placement(2)], &game_state, &EvalWeights::default()).unwrap();
+        assert_eq!(forced.position, Position::new(2, 1));
+    }
+
     #[test]
     fn test_strategic_blocking() {
         let game_state = create_test_game_state();
@@ -222,10 +581,30 @@ mod tests {
             create_test_placement(2, 2, 1, 1),
         ];
         
-        let best = strategic_blocking(&placements, &game_state);
+        let best = strategic_blocking(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_some());
     }
 
+    #[test]
+    fn test_strategic_blocking_contests_close_fronts() {
+        use crate::game_state::Position;
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(20, 20)
+            .with_territory(1, &[Position::new(5, 5)])
+            .with_territory(2, &[Position::new(9, 5)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        assert!(game_state.territory_centroid_distance() <= 20f32.hypot(20.0) * BLOCKING_FRONT_RATIO);
+
+        let placements = find_all_valid_placements(&game_state);
+        let nearby = filter_near_opponent(&placements, &game_state, BLOCKING_MAX_DISTANCE);
+        let best = strategic_blocking(&placements, &game_state, &EvalWeights::default()).unwrap();
+
+        assert!(!nearby.is_empty());
+        assert!(nearby.contains(&best));
+    }
+
     #[test]
     fn test_advanced_balanced() {
         let game_state = create_test_game_state();
@@ -235,7 +614,7 @@ mod tests {
             create_test_placement(2, 2, 2, 2),
         ];
         
-        let best = advanced_balanced(&placements, &game_state);
+        let best = advanced_balanced(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_some());
     }
 
@@ -248,20 +627,191 @@ mod tests {
             create_test_placement(2, 2, 2, 2),
         ];
         
-        let best = territorial_control(&placements, &game_state);
+        let best = territorial_control(&placements, &game_state, &EvalWeights::default());
         assert!(best.is_some());
     }
 
+    #[test]
+    fn test_territorial_control_region_weight() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = create_test_game_state();
+        let placements = find_all_valid_placements(&game_state);
+        let weights = EvalWeights {
+            territorial_region: 1000.0,
+            ..EvalWeights::default()
+        };
+        let advantage = |p: &Placement| -game_state.apply_placement(p).empty_region_advantage();
+
+        let best = territorial_control(&placements, &game_state, &weights).unwrap();
+
+        assert!(placements.iter().all(|p| advantage(p) <= advantage(&best)));
+    }
+
     #[test]
     fn test_all_strategies_handle_single_placement() {
         let game_state = create_test_game_state();
         let placements = vec![create_test_placement(1, 1, 2, 1)];
         
-        assert!(aggressive_expansion(&placements, &game_state).is_some());
-        assert!(opportunistic(&placements, &game_state).is_some());
-        assert!(defensive(&placements, &game_state).is_some());
-        assert!(strategic_blocking(&placements, &game_state).is_some());
-        assert!(advanced_balanced(&placements, &game_state).is_some());
-        assert!(territorial_control(&placements, &game_state).is_some());
+        assert!(aggressive_expansion(&placements, &game_state, &EvalWeights::default()).is_some());
+        assert!(opportunistic(&placements, &game_state, &EvalWeights::default()).is_some());
+        assert!(defensive(&placements, &game_state, &EvalWeights::default()).is_some());
+        assert!(strategic_blocking(&placements, &game_state, &EvalWeights::default()).is_some());
+        assert!(advanced_balanced(&placements, &game_state, &EvalWeights::default()).is_some());
+        assert!(territorial_control(&placements, &game_state, &EvalWeights::default()).is_some());
+    }
+
+    #[test]
+    fn test_strategic_blocking_prefers_chokepoints() {
+        // Corridor along row 0; (2, 0) and (3, 0) split the empty cells
+        let game_state = GameState::builder_from_size(5, 2)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &(0..5).map(|x| Position::new(x, 1)).collect::<Vec<_>>())
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let bar = |x| Placement {
+            position: Position::new(x, 0),
+            shape: game_state.current_piece.clone(),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        let placements = vec![bar(0), bar(2), bar(3)];
+        let weights = EvalWeights { blocking_weak: 0.0, ..EvalWeights::default() };
+
+        let best = strategic_blocking(&placements, &game_state, &weights).unwrap();
+        assert_eq!(best.position, Position::new(2, 0));
+    }
+
+    #[test]
+    fn test_strategic_blocking_caches_projection() {
+        let game_state = create_test_game_state();
+        let placements = vec![
+            create_test_placement(1, 1, 2, 1),
+            create_test_placement(2, 2, 1, 1),
+        ];
+        let mut cache = TurnCache::new();
+        
+        let first = strategic_blocking_cached(&placements, &game_state, &EvalWeights::default(), &mut cache);
+        assert_eq!(cache.stats().entries, 2);
+        let second = strategic_blocking_cached(&placements, &game_state, &EvalWeights::default(), &mut cache);
+        
+        assert_eq!(first, second);
+        assert_eq!(first, strategic_blocking(&placements, &game_state, &EvalWeights::default()));
+    }
+
+    #[test]
+    fn test_advanced_balanced_default_weights_match_advanced_score() {
+        use super::super::heuristics::{advanced_score, HeuristicWeights};
+        let game_state = create_test_game_state();
+        let placements = vec![
+            create_test_placement(0, 0, 1, 1),
+            create_test_placement(2, 2, 2, 1),
+            create_test_placement(4, 0, 1, 1),
+        ];
+        
+        let expected = placements
+            .iter()
+            .max_by(|a, b| {
+                let weights = HeuristicWeights::default();
+                advanced_score(a, &game_state, &weights).cmp(&advanced_score(b, &game_state, &weights))
+            })
+            .cloned();
+        
+        assert_eq!(advanced_balanced(&placements, &game_state, &EvalWeights::default()), expected);
+    }
+
+    #[test]
+    fn test_eval_weights_from_heuristics() {
+        assert_eq!(EvalWeights::from_heuristics(&HeuristicWeights::default()), EvalWeights::default());
+
+        let heavier = HeuristicWeights { flood_fill: 4.0, ..HeuristicWeights::default() };
+        let weights = EvalWeights::from_heuristics(&heavier);
+        assert_eq!(weights.balanced_flood_fill, 4.0);
+        assert_eq!(weights.aggressive_flood_fill, EvalWeights::default().aggressive_flood_fill);
+    }
+
+    #[test]
+    fn test_weights_change_selection() {
+        let game_state = create_test_game_state();
+        let placements = vec![
+            create_test_placement(0, 0, 1, 3),
+            create_test_placement(2, 2, 3, 1),
+        ];
+        let default_pick = territorial_control(&placements, &game_state, &EvalWeights::default());
+        assert_eq!(default_pick.unwrap().cells_added, 3);
+        
+        // Make territory touches dominate
+        let weights = EvalWeights {
+            territorial_cells: 0.0,
+            territorial_touches: 100.0,
+            ..EvalWeights::default()
+        };
+        let weighted_pick = territorial_control(&placements, &game_state, &weights);
+        assert_eq!(weighted_pick.unwrap().territory_touches, 3);
+    }
+
+    #[test]
+    fn test_territorial_voronoi_selects_valid_placement() {
+        use crate::game_state::{Position, Shape};
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(9, 3)
+            .with_territory(1, &[Position::new(3, 1)])
+            .with_territory(2, &[Position::new(8, 1)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+        assert_eq!(placements.len(), 2);
+
+        // Extending towards the opponent claims more of the contested middle
+        let result = territorial_voronoi(&placements, &game_state).unwrap();
+        assert_eq!(result.position, Position::new(3, 1));
+        assert!(territorial_voronoi(&[], &game_state).is_none());
+    }
+
+    #[test]
+    fn test_threat_aware_avoids_contested_cells() {
+        use crate::game_state::{Position, Shape};
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(9, 3)
+            .with_territory(1, &[Position::new(6, 1)])
+            .with_territory(2, &[Position::new(8, 1)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+        assert_eq!(placements.len(), 2);
+
+        // (7, 1) is the one cell the opponent's domino can reach
+        let result = threat_aware(&placements, &game_state, &EvalWeights::default()).unwrap();
+        assert_eq!(result.position, Position::new(5, 1));
+        assert!(threat_aware(&[], &game_state, &EvalWeights::default()).is_none());
+    }
+
+    #[test]
+    fn test_defensive_prefers_thickening_territory() {
+        use crate::game_state::Position;
+        use crate::placement::find_all_valid_placements;
+
+        let weights = EvalWeights {
+            defensive_density: 0.0,
+            defensive_touches: 0.0,
+            defensive_edge: 0.0,
+            ..EvalWeights::default()
+        };
+
+        for player in [1, 2] {
+            let game_state = GameState::builder_from_size(6, 6)
+                .with_player(player)
+                .with_territory(player, &[Position::new(2, 2), Position::new(3, 2)])
+                .with_territory(GameState::opponent_of(player), &[Position::new(5, 5)])
+                .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]))
+                .build();
+            let placements = find_all_valid_placements(&game_state);
+
+            // Closing the bar into a 2x2 block makes all four cells core
+            let best = defensive(&placements, &game_state, &weights).unwrap();
+            assert_eq!(best.position, Position::new(2, 1), "player {}", player);
+        }
     }
 }
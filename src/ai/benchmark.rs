@@ -3,10 +3,16 @@
 /// This module provides tools to measure and track performance
 /// improvements from optimization efforts.
 
+use crate::game_state::GameState;
+use crate::placement::{find_all_valid_placements, find_all_valid_placements_bitboard, Placement};
+use crate::sim::Simulation;
+use super::evaluator::{evaluate_placement, evaluate_placement_with, DistanceMetric, EvalConfig};
+use super::AIStrategy;
+use super::optimization::{BatchScorer, ScoringContext};
 use std::time::{Instant, Duration};
 
 /// Performance metrics for evaluation operations
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PerformanceMetrics {
     /// Total time spent in operation
     pub total_duration: Duration,
@@ -18,6 +24,8 @@ pub struct PerformanceMetrics {
     pub min_time: Duration,
     /// Maximum time for any single operation
     pub max_time: Duration,
+    /// Every recorded duration, in ascending order
+    pub samples: Vec<Duration>,
 }
 
 impl PerformanceMetrics {
@@ -29,6 +37,7 @@ impl PerformanceMetrics {
             avg_per_op: Duration::ZERO,
             min_time: Duration::MAX,
             max_time: Duration::ZERO,
+            samples: Vec::new(),
         }
     }
 
@@ -39,6 +48,22 @@ impl PerformanceMetrics {
         self.min_time = self.min_time.min(duration);
         self.max_time = self.max_time.max(duration);
         self.avg_per_op = self.total_duration / self.operations as u32;
+        // Insert in place: `record` runs once per operation, not in a hot loop
+        let index = self.samples.partition_point(|&sample| sample <= duration);
+        self.samples.insert(index, duration);
+    }
+
+    /// The `p`th percentile of the recorded durations (nearest rank)
+    ///
+    /// `p` is in percent and clamped to 0..=100, so `percentile(99.0)` is the
+    /// p99 latency and `percentile(100.0)` the slowest sample. Zero if
+    /// nothing was recorded.
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * self.samples.len() as f64).ceil() as usize;
+        self.samples[rank.saturating_sub(1)]
     }
 
     /// Get average time in microseconds
@@ -114,6 +139,19 @@ impl BenchmarkResult {
         }
     }
 
+    /// Speedup factor of the p99 latencies
+    ///
+    /// Compares the slow tail rather than the average; 0 if the optimized
+    /// side recorded nothing.
+    pub fn p99_speedup(&self) -> f64 {
+        let optimized = self.optimized_metrics.percentile(99.0);
+        if optimized.is_zero() {
+            0.0
+        } else {
+            self.baseline_metrics.percentile(99.0).as_secs_f64() / optimized.as_secs_f64()
+        }
+    }
+
     /// Calculate time saved per operation
     pub fn time_saved_per_op(&self) -> Duration {
         self.baseline_metrics
@@ -132,6 +170,136 @@ impl BenchmarkResult {
     }
 }
 
+/// Measure `ScoringContext::evaluate_cached` against plain `evaluate_placement`
+///
+/// Both sides score every placement `rounds` times; one operation is one
+/// full pass. The cache persists across rounds, as it would across
+/// repeated placement searches within a turn. Returns the timings and the
+/// cache hit rate.
+pub fn benchmark_eval_cache(
+    placements: &[Placement],
+    game_state: &GameState,
+    rounds: usize,
+) -> (BenchmarkResult, f32) {
+    let mut baseline = PerformanceMetrics::new();
+    for _ in 0..rounds {
+        let timer = Timer::start();
+        for placement in placements {
+            evaluate_placement(placement, game_state);
+        }
+        baseline.record(timer.elapsed());
+    }
+
+    let mut context = ScoringContext::new();
+    let mut optimized = PerformanceMetrics::new();
+    for _ in 0..rounds {
+        let timer = Timer::start();
+        for placement in placements {
+            context.evaluate_cached(placement, game_state);
+        }
+        optimized.record(timer.elapsed());
+    }
+
+    let result = BenchmarkResult {
+        baseline_metrics: baseline,
+        optimized_metrics: optimized,
+    };
+    (result, context.eval_cache().hit_rate())
+}
+
+/// Measure `BatchScorer::for_grid` against `BatchScorer::new`
+///
+/// Each round scores every placement with a fresh scorer, so the caches
+/// start empty every time: the `new` scorer's maps grow as they fill, the
+/// presized one's never do. One operation is one round.
+pub fn benchmark_cache_presizing(
+    placements: &[Placement],
+    game_state: &GameState,
+    rounds: usize,
+) -> BenchmarkResult {
+    let mut baseline = PerformanceMetrics::new();
+    for _ in 0..rounds {
+        let timer = Timer::start();
+        BatchScorer::new().score_all(placements, game_state);
+        baseline.record(timer.elapsed());
+    }
+
+    let mut optimized = PerformanceMetrics::new();
+    for _ in 0..rounds {
+        let timer = Timer::start();
+        BatchScorer::for_grid(game_state.grid()).score_all(placements, game_state);
+        optimized.record(timer.elapsed());
+    }
+
+    BenchmarkResult {
+        baseline_metrics: baseline,
+        optimized_metrics: optimized,
+    }
+}
+
+/// Measure `find_all_valid_placements_bitboard` against `find_all_valid_placements`
+///
+/// Each round runs both searches once on `game_state`; one operation is
+/// one full search.
+pub fn benchmark_bitboard_placements(game_state: &GameState, rounds: usize) -> BenchmarkResult {
+    let mut baseline = PerformanceMetrics::new();
+    let mut optimized = PerformanceMetrics::new();
+    for _ in 0..rounds {
+        let timer = Timer::start();
+        find_all_valid_placements(game_state);
+        baseline.record(timer.elapsed());
+
+        let timer = Timer::start();
+        find_all_valid_placements_bitboard(game_state);
+        optimized.record(timer.elapsed());
+    }
+
+    BenchmarkResult {
+        baseline_metrics: baseline,
+        optimized_metrics: optimized,
+    }
+}
+
+/// Self-play win rate of the Euclidean centrality bonus against Manhattan
+///
+/// Both players pick the placement with the best `evaluate_placement_with`
+/// score, differing only in `EvalConfig::distance`. Seats alternate and
+/// game `i` uses seed `i`; ties count half. Used to choose the default
+/// `EvalConfig`.
+pub fn benchmark_distance_metrics(games: usize, width: usize, height: usize) -> f32 {
+    if games == 0 {
+        return 0.0;
+    }
+
+    let config = |metric| EvalConfig { distance: metric };
+    let (euclidean, manhattan) = (config(DistanceMetric::Euclidean), config(DistanceMetric::Manhattan));
+    let mut points = 0.0;
+    for game in 0..games {
+        let euclidean_seat = if game % 2 == 0 { 1 } else { 2 };
+        // The strategies are unused: every move comes from the selector below
+        let mut sim = Simulation::with_board(AIStrategy::Default, AIStrategy::Default, width, height, game as u64);
+        let result = sim.run_with(&mut |player, placements, game_state| {
+            let config = if player == euclidean_seat { &euclidean } else { &manhattan };
+            let mut best: Option<(&Placement, _)> = None;
+            for placement in placements {
+                let score = evaluate_placement_with(placement, game_state, config);
+                if best.is_none_or(|(_, best_score)| score > best_score) {
+                    best = Some((placement, score));
+                }
+            }
+            best.map(|(placement, _)| placement.clone())
+        });
+
+        if result.winner == euclidean_seat {
+            points += 1.0;
+        } else if result.winner == 0 {
+            points += 0.5;
+        }
+    }
+
+    points / games as f32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +333,23 @@ mod tests {
         assert_eq!(metrics.max_time, Duration::from_millis(30));
     }
 
+    #[test]
+    fn test_performance_metrics_percentile() {
+        let mut metrics = PerformanceMetrics::new();
+        assert_eq!(metrics.percentile(99.0), Duration::ZERO);
+
+        for millis in [5, 1, 4, 2, 3, 100, 6, 7, 8, 9] {
+            metrics.record(Duration::from_millis(millis));
+        }
+
+        assert!(metrics.samples.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(metrics.percentile(0.0), Duration::from_millis(1));
+        assert_eq!(metrics.percentile(50.0), Duration::from_millis(5));
+        assert_eq!(metrics.percentile(90.0), Duration::from_millis(9));
+        assert_eq!(metrics.percentile(99.0), Duration::from_millis(100));
+        assert_eq!(metrics.percentile(150.0), Duration::from_millis(100));
+    }
+
     #[test]
     fn test_performance_metrics_throughput() {
         let mut metrics = PerformanceMetrics::new();
@@ -212,6 +397,27 @@ mod tests {
         assert!(speedup >= 1.9 && speedup <= 2.1); // Approximately 2x
     }
 
+    #[test]
+    fn test_benchmark_result_p99_speedup() {
+        let mut baseline = PerformanceMetrics::new();
+        let mut optimized = PerformanceMetrics::new();
+        for _ in 0..9 {
+            baseline.record(Duration::from_millis(10));
+            optimized.record(Duration::from_millis(10));
+        }
+        // Equal typical samples; only the optimized tail is shorter
+        baseline.record(Duration::from_millis(100));
+        optimized.record(Duration::from_millis(20));
+
+        let result = BenchmarkResult {
+            baseline_metrics: baseline,
+            optimized_metrics: optimized,
+        };
+
+        assert!((result.p99_speedup() - 5.0).abs() < 1e-9);
+        assert!(result.p99_speedup() > result.speedup());
+    }
+
     #[test]
     fn test_benchmark_result_improvement_percent() {
         let mut baseline = PerformanceMetrics::new();
@@ -245,4 +451,75 @@ mod tests {
         let saved = result.time_saved_per_op();
         assert!(saved >= Duration::from_millis(69) && saved <= Duration::from_millis(71));
     }
+
+    #[test]
+    fn test_benchmark_eval_cache_hit_rate() {
+        use crate::game_state::{Position, Shape};
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_territory(2, &[Position::new(7, 7)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+
+        let (result, hit_rate) = benchmark_eval_cache(&placements, &game_state, 4);
+
+        assert_eq!(result.baseline_metrics.operations, 4);
+        assert_eq!(result.optimized_metrics.operations, 4);
+        // Only the first round misses
+        assert_eq!(hit_rate, 0.75);
+    }
+
+    #[test]
+    fn test_benchmark_cache_presizing() {
+        use crate::game_state::{Position, Shape};
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_territory(2, &[Position::new(7, 7)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+
+        let result = benchmark_cache_presizing(&placements, &game_state, 3);
+        assert_eq!(result.baseline_metrics.operations, 3);
+        assert_eq!(result.optimized_metrics.operations, 3);
+
+        // A presized scorer fills its caches without growing them
+        let mut scorer = BatchScorer::for_grid(game_state.grid());
+        let before = scorer.cache_stats();
+        scorer.score_all(&placements, &game_state);
+        let after = scorer.cache_stats();
+        assert!(after.flood_fill.entries > 0);
+        assert_eq!(
+            (after.flood_fill.capacity, after.density.capacity),
+            (before.flood_fill.capacity, before.density.capacity)
+        );
+    }
+
+    #[test]
+    fn test_benchmark_bitboard_placements() {
+        use crate::game_state::Shape;
+
+        let game_state = GameState::builder_from_size(8, 8)
+            .symmetric_start()
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+
+        let result = benchmark_bitboard_placements(&game_state, 3);
+        assert_eq!(result.baseline_metrics.operations, 3);
+        assert_eq!(result.optimized_metrics.operations, 3);
+        assert_eq!(result.optimized_metrics.samples.len(), 3);
+    }
+
+    #[test]
+    fn test_benchmark_distance_metrics() {
+        let rate = benchmark_distance_metrics(2, 12, 10);
+        assert!((0.0..=1.0).contains(&rate));
+        assert_eq!(rate, benchmark_distance_metrics(2, 12, 10));
+        assert_eq!(benchmark_distance_metrics(0, 12, 10), 0.0);
+    }
 }
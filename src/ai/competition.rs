@@ -0,0 +1,67 @@
+/// Strategy standings from a round-robin tournament
+///
+/// Ranks every `AIStrategy` by the games it wins against all the others,
+/// to pick a strategy without running the game engine. The games are
+/// played by `tournament::Tournament`; this module orders its matrix into
+/// a table of standings.
+
+use crate::tournament::{Tournament, TournamentResults};
+use super::AIStrategy;
+
+/// Strategies ranked by total wins, with the full head-to-head matrix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyStandings {
+    /// Each strategy and its total wins, most wins first; ties keep
+    /// `AIStrategy::all()` order
+    pub standings: Vec<(AIStrategy, usize)>,
+    /// Win/tie/loss matrix the standings are computed from
+    pub results: TournamentResults,
+}
+
+impl StrategyStandings {
+    /// Strategy with the most wins, `None` if no strategy played
+    pub fn winner(&self) -> Option<AIStrategy> {
+        self.standings.first().map(|&(strategy, _)| strategy)
+    }
+}
+
+/// Play every `AIStrategy` against every other `games_per_pair` times
+///
+/// Game seeds count up from `seed`, so a tournament replays identically.
+pub fn run_strategy_tournament(
+    board_width: usize,
+    board_height: usize,
+    games_per_pair: usize,
+    seed: u64,
+) -> StrategyStandings {
+    let tournament = Tournament::new(board_width, board_height, games_per_pair, seed);
+    let results = tournament.run();
+
+    let mut standings: Vec<(AIStrategy, usize)> = tournament
+        .strategies
+        .iter()
+        .enumerate()
+        .map(|(i, &strategy)| (strategy, results.total_wins(i)))
+        .collect();
+    standings.sort_by_key(|&(_, wins)| std::cmp::Reverse(wins));
+
+    StrategyStandings { standings, results }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standings_rank_every_strategy_by_wins() {
+        let standings = run_strategy_tournament(10, 8, 1, 3);
+
+        assert_eq!(standings.standings.len(), AIStrategy::all().len());
+        assert!(standings.standings.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        for (i, &strategy) in AIStrategy::all().iter().enumerate() {
+            let wins = standings.standings.iter().find(|&&(s, _)| s == strategy).unwrap().1;
+            assert_eq!(wins, standings.results.total_wins(i));
+        }
+        assert_eq!(standings.winner().map(|s| format!("{:?}", s)), Some(standings.results.best_strategy()));
+    }
+}
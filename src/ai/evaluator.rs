@@ -4,44 +4,147 @@
 /// - Territory expansion (primary score)
 /// - Center of mass bias (secondary)
 /// - Adjacency count (secondary)
+/// - Territory compactness (penalty for spindly territory)
+/// - Interior holes (penalty for enclosing empty cells)
 
-use crate::game_state::{GameState, Position, Grid, Shape, CellState};
+use crate::game_state::{GameState, Position, CellState};
 use crate::placement::Placement;
-use crate::utils::manhattan_distance;
+use crate::utils::{euclidean_distance, manhattan_distance};
+use super::heuristics::HeuristicWeights;
+use super::score::Score;
+use std::cmp::Reverse;
+
+/// Score lost per empty board cell enclosed by a placed piece
+const HOLE_PENALTY: f32 = 1.5;
+
+/// Score lost per unit of compactness our territory falls short of 1.0
+const COMPACTNESS_PENALTY: f32 = 10.0;
+
+/// Distance from the board center beyond which there is no centrality bonus
+const CENTRALITY_RADIUS: f32 = 15.0;
+
+/// Distance measure for the centrality bonus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    Manhattan,
+    Euclidean,
+}
+
+impl DistanceMetric {
+    /// Distance between `a` and `b` under this metric
+    pub fn distance(self, a: Position, b: Position) -> f32 {
+        match self {
+            DistanceMetric::Manhattan => manhattan_distance(a, b) as f32,
+            DistanceMetric::Euclidean => euclidean_distance(a, b),
+        }
+    }
+}
+
+/// Options for `evaluate_placement_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvalConfig {
+    pub distance: DistanceMetric,
+}
+
+impl Default for EvalConfig {
+    /// Euclidean centrality, which won 52% of 300 self-play games against
+    /// Manhattan on the default 20x15 board (74% of 150 on 40x30; see
+    /// `benchmark::benchmark_distance_metrics`)
+    fn default() -> Self {
+        EvalConfig { distance: DistanceMetric::Euclidean }
+    }
+}
 
 /// Score a single placement
 /// 
 /// Scoring factors:
 /// 1. Territory expansion (cells_added) - PRIMARY
-/// 2. Position centrality (distance from board center) - SECONDARY
+/// 2. Position centrality (piece centroid's distance from board center) - SECONDARY
 /// 3. Adjacency count (touching own territory) - TERTIARY
-pub fn evaluate_placement(placement: &Placement, game_state: &GameState) -> f32 {
+/// 4. Territory compactness - spindly territory is thin and easy to cut off
+/// 5. Interior holes - empty board cells the piece would enclose
+pub fn evaluate_placement(placement: &Placement, game_state: &GameState) -> Score {
+    evaluate_placement_with(placement, game_state, &EvalConfig::default())
+}
+
+/// Score a single placement with explicit evaluation options
+///
+/// Same factors as `evaluate_placement`; `config.distance` picks how the
+/// distance from the piece centroid to the board center is measured.
+pub fn evaluate_placement_with(placement: &Placement, game_state: &GameState, config: &EvalConfig) -> Score {
+    let weights = HeuristicWeights::default();
+
     // Primary score: Territory expansion
     // Each cell added is worth base points
-    let expansion_score = placement.cells_added as f32 * 10.0;
+    let expansion_score = placement.cells_added as f32 * weights.expansion;
     
     // Secondary score: Centrality bonus
-    // Placements near board center get slight bonus
-    let center_x = game_state.grid.width as i32 / 2;
-    let center_y = game_state.grid.height as i32 / 2;
-    let distance_to_center = manhattan_distance(
-        placement.position,
-        Position { x: center_x as usize, y: center_y as usize },
-    );
-    let centrality_bonus = if distance_to_center < 15 {
-        (15 - distance_to_center) as f32 * 0.5
-    } else {
-        0.0
+    // Placements whose cells sit near board center get slight bonus
+    let center_x = (game_state.grid().width / 2) as f32;
+    let center_y = (game_state.grid().height / 2) as f32;
+    let distance_to_center = match config.distance {
+        DistanceMetric::Euclidean => placement.distance_to_point(center_x, center_y),
+        DistanceMetric::Manhattan => {
+            let (x, y) = placement.centroid();
+            (x - center_x).abs() + (y - center_y).abs()
+        }
     };
+    let centrality_bonus = (CENTRALITY_RADIUS - distance_to_center).max(0.0) * weights.centrality;
     
     // Tertiary score: Adjacency bonus
     // Placements touching more of own territory get bonus
-    let adjacency_bonus = (placement.territory_touches as f32) * 1.0;
+    let adjacency_bonus = (placement.territory_touches as f32) * weights.adjacency;
     
+    // Compactness penalty: long thin territory is easy to cut off
+    let compactness_penalty = (1.0 - territory_compactness_after(placement, game_state)) * COMPACTNESS_PENALTY;
+
+    // Hole penalty: enclosed empty cells are territory nobody can claim
+    let enclosed_empty = placement
+        .shape
+        .interior_hole_positions()
+        .into_iter()
+        .map(|p| Position::new(placement.position.x + p.x, placement.position.y + p.y))
+        .filter(|&pos| game_state.grid().get(pos) == Some(CellState::Empty))
+        .count();
+    let hole_penalty = enclosed_empty as f32 * HOLE_PENALTY;
+
     // Total score combines all factors
-    let total_score = expansion_score + centrality_bonus + adjacency_bonus;
+    let total_score =
+        expansion_score + centrality_bonus + adjacency_bonus - compactness_penalty - hole_penalty;
     
-    total_score
+    Score(total_score)
+}
+
+/// Isoperimetric ratio `4π·area / perimeter²` of our territory after `placement`
+///
+/// Same measure as `Shape::compactness`. Starts from the cached territory
+/// perimeter and adds only the placement's new cells: each adds 4 edges
+/// less 2 per neighbor we already own.
+fn territory_compactness_after(placement: &Placement, game_state: &GameState) -> f32 {
+    let grid = &game_state.grid();
+    let player = game_state.player_number();
+    let territory = game_state.my_territory();
+    let new_cells: Vec<Position> = placement
+        .get_absolute_positions()
+        .into_iter()
+        .filter(|&pos| grid.get(pos) == Some(CellState::Empty))
+        .collect();
+
+    let mut perimeter = territory.perimeter as isize;
+    for (i, &cell) in new_cells.iter().enumerate() {
+        let owned = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| cell.checked_add(dx, dy))
+            .filter(|n| grid.get(*n).is_some_and(|c| c.is_player(player)) || new_cells[..i].contains(n))
+            .count();
+        perimeter += 4 - 2 * owned as isize;
+    }
+
+    if perimeter <= 0 {
+        return 0.0;
+    }
+    let area = territory.size() + new_cells.len();
+    4.0 * std::f32::consts::PI * area as f32 / (perimeter * perimeter) as f32
 }
 
 /// Rank placements by their evaluation score
@@ -50,14 +153,14 @@ pub fn evaluate_placement(placement: &Placement, game_state: &GameState) -> f32
 pub fn rank_placements(
     placements: &[Placement],
     game_state: &GameState,
-) -> Vec<(Placement, f32)> {
-    let mut scored: Vec<(Placement, f32)> = placements
+) -> Vec<(Placement, Score)> {
+    let mut scored: Vec<(Placement, Score)> = placements
         .iter()
         .map(|p| (p.clone(), evaluate_placement(p, game_state)))
         .collect();
     
     // Sort by score descending
-    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored.sort_by_key(|(_, score)| Reverse(*score));
     
     scored
 }
@@ -78,22 +181,52 @@ pub fn select_best_placement(
     ranked.first().map(|(placement, _)| placement.clone())
 }
 
+/// Select the top-k placements by evaluation score
+/// 
+/// Returns at most `k` placements sorted from highest to lowest score.
+/// Intended as the root expansion set for search-based strategies.
+pub fn select_top_k_placements(
+    placements: &[Placement],
+    game_state: &GameState,
+    k: usize,
+) -> Vec<Placement> {
+    rank_placements(placements, game_state)
+        .into_iter()
+        .take(k)
+        .map(|(placement, _)| placement)
+        .collect()
+}
+
+/// Select the best placement only if its score exceeds `min_score`
+/// 
+/// Returns None when no placement is good enough, so the caller can
+/// fall back to another strategy.
+pub fn select_best_placement_with_min_score(
+    placements: &[Placement],
+    game_state: &GameState,
+    min_score: f32,
+) -> Option<Placement> {
+    rank_placements(placements, game_state)
+        .into_iter()
+        .next()
+        .filter(|(_, score)| *score > Score(min_score))
+        .map(|(placement, _)| placement)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game_state::Shape;
 
     fn create_test_game_state() -> GameState {
-        let grid = Grid::from_chars(
-            10, 10,
-            (0..10).map(|_| (0..10).map(|_| '.').collect()).collect(),
-        );
-        
         let shape = Shape::from_chars(
             2, 2,
             vec![vec!['.', '#'], vec!['#', '.']],
         );
         
-        GameState::new(1, grid, shape)
+        GameState::builder_from_size(10, 10)
+            .with_piece(shape)
+            .build()
     }
 
     #[test]
@@ -108,7 +241,41 @@ mod tests {
         
         let score = evaluate_placement(&placement, &game_state);
         // Should be significant due to cells_added
-        assert!(score > 15.0);
+        assert!(score > Score(15.0));
+    }
+
+    #[test]
+    fn test_evaluate_placement_distance_metric() {
+        let game_state = create_test_game_state();
+        // The centroid (2.5, 1.5) is 6 steps but only sqrt(18.5) in a
+        // straight line from (5, 5)
+        let placement = Placement {
+            position: Position { x: 2, y: 1 },
+            shape: game_state.current_piece.clone(),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        let score = |distance| evaluate_placement_with(&placement, &game_state, &EvalConfig { distance });
+
+        let centrality = HeuristicWeights::default().centrality;
+        let gap = score(DistanceMetric::Euclidean).value() - score(DistanceMetric::Manhattan).value();
+        assert!((gap - (6.0 - 18.5f32.sqrt()) * centrality).abs() < 1e-4);
+        assert_eq!(evaluate_placement(&placement, &game_state), score(EvalConfig::default().distance));
+    }
+
+    #[test]
+    fn test_centrality_uses_piece_centroid() {
+        // A bar centered on (5, 5) has its corner 4 cells off center, the
+        // same bar 4 cells to the right is centered there
+        let game_state = create_test_game_state();
+        let bar = |x| Placement {
+            position: Position::new(x, 5),
+            shape: Shape::from_chars(9, 1, vec![vec!['#'; 9]]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        assert!(evaluate_placement(&bar(1), &game_state) > evaluate_placement(&bar(5), &game_state));
     }
 
     #[test]
@@ -204,4 +371,116 @@ mod tests {
         let best = select_best_placement(&placements, &game_state);
         assert!(best.is_none());
     }
+
+    fn create_ranked_placements(game_state: &GameState) -> Vec<Placement> {
+        (1..=5)
+            .map(|cells| Placement {
+                position: Position { x: cells, y: 5 },
+                shape: game_state.current_piece.clone(),
+                cells_added: cells,
+                territory_touches: 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_top_k_placements() {
+        let game_state = create_test_game_state();
+        let placements = create_ranked_placements(&game_state);
+        
+        let top = select_top_k_placements(&placements, &game_state, 3);
+        
+        assert_eq!(top.len(), 3);
+        let scores: Vec<Score> = top.iter().map(|p| evaluate_placement(p, &game_state)).collect();
+        assert!(scores[0] >= scores[1] && scores[1] >= scores[2]);
+        assert_eq!(top[0].cells_added, 5);
+    }
+
+    #[test]
+    fn test_select_top_k_placements_fewer_than_k() {
+        let game_state = create_test_game_state();
+        let placements = create_ranked_placements(&game_state);
+        
+        assert_eq!(select_top_k_placements(&placements, &game_state, 10).len(), 5);
+        assert!(select_top_k_placements(&placements, &game_state, 0).is_empty());
+    }
+
+    #[test]
+    fn test_select_best_placement_with_min_score() {
+        let game_state = create_test_game_state();
+        let placements = create_ranked_placements(&game_state);
+        let best_score = evaluate_placement(&placements[4], &game_state).value();
+        
+        let best = select_best_placement_with_min_score(&placements, &game_state, best_score - 1.0);
+        assert_eq!(best.map(|p| p.cells_added), Some(5));
+        
+        // All scores below threshold
+        let none = select_best_placement_with_min_score(&placements, &game_state, best_score + 1.0);
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn test_evaluate_placement_penalizes_spindly_piece() {
+        let game_state = create_test_game_state();
+        let placement_with = |shape: Shape| Placement {
+            position: Position { x: 4, y: 4 },
+            shape,
+            cells_added: 3,
+            territory_touches: 1,
+        };
+
+        let square = placement_with(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '#']]));
+        let bar = placement_with(Shape::from_chars(4, 1, vec![vec!['#'; 4]]));
+
+        assert!(evaluate_placement(&square, &game_state) > evaluate_placement(&bar, &game_state));
+    }
+
+    #[test]
+    fn test_territory_compactness_after_fills_corner() {
+        // Filling the corner of our L squares it off; the cell beside it
+        // sticks out
+        let game_state = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(4, 4), Position::new(5, 4), Position::new(4, 5)])
+            .build();
+        let dot = |x, y| Placement {
+            position: Position::new(x, y),
+            shape: Shape::from_chars(1, 1, vec![vec!['#']]),
+            cells_added: 1,
+            territory_touches: 2,
+        };
+
+        assert_eq!(game_state.my_territory().perimeter, 8);
+        let corner = territory_compactness_after(&dot(5, 5), &game_state);
+        assert!((corner - std::f32::consts::PI / 4.0).abs() < 1e-4);
+        assert!(corner > territory_compactness_after(&dot(6, 4), &game_state));
+    }
+
+    #[test]
+    fn test_evaluate_placement_penalizes_enclosed_empty_cells() {
+        let ring = Shape::from_chars(3, 3, vec![
+            vec!['#', '#', '#'],
+            vec!['#', '.', '#'],
+            vec!['#', '#', '#'],
+        ]);
+        let mut game_state = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(5, 5)])
+            .with_piece(ring.clone())
+            .build();
+        let placement = Placement {
+            position: Position { x: 4, y: 4 },
+            shape: ring,
+            cells_added: 7,
+            territory_touches: 1,
+        };
+
+        // The hole lands on our own cell at (5, 5): no penalty, and the
+        // result is a solid square rather than a ring
+        let over_territory = evaluate_placement(&placement, &game_state);
+        game_state.set_cell(Position::new(5, 5), CellState::Empty);
+        let over_empty = evaluate_placement(&placement, &game_state);
+
+        let ring_compactness_loss = std::f32::consts::PI / 4.0 - std::f32::consts::PI / 8.0;
+        let expected = HOLE_PENALTY + ring_compactness_loss * COMPACTNESS_PENALTY;
+        assert!((over_territory.value() - over_empty.value() - expected).abs() < 1e-4);
+    }
 }
@@ -0,0 +1,210 @@
+/// Max-flow territory partition
+///
+/// Where the Voronoi partition hands each empty cell to whoever reaches it
+/// first, a minimum cut finds the fewest empty cells that wall the two
+/// territories off from each other. Each empty cell is a node of capacity
+/// 1 (split into an in and an out node); player 1's territory feeds the
+/// source and player 2's drains into the sink. Ford-Fulkerson with BFS
+/// augmenting paths (Edmonds-Karp) finds the cut in O(V·E), which is only
+/// worth paying late in the game when few empty cells remain.
+
+use crate::game_state::{CellState, Grid};
+use std::collections::VecDeque;
+
+/// Capacity of the edges between cells, large enough never to be cut
+const UNBOUNDED: u32 = u32::MAX;
+
+/// Directed edge of the residual graph
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    to: usize,
+    capacity: u32,
+    /// Index of the reverse edge in `FlowNetwork::edges[to]`
+    reverse: usize,
+}
+
+/// Flow network over the empty cells of a grid
+///
+/// Cell `i` has in node `2 * i` and out node `2 * i + 1`; the source and
+/// sink come after all cell nodes.
+#[derive(Debug)]
+struct FlowNetwork {
+    edges: Vec<Vec<Edge>>,
+    source: usize,
+    sink: usize,
+}
+
+impl FlowNetwork {
+    fn new(cells: usize) -> Self {
+        FlowNetwork {
+            edges: vec![Vec::new(); 2 * cells + 2],
+            source: 2 * cells,
+            sink: 2 * cells + 1,
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, capacity: u32) {
+        let (forward, backward) = (self.edges[to].len(), self.edges[from].len());
+        self.edges[from].push(Edge { to, capacity, reverse: forward });
+        self.edges[to].push(Edge { to: from, capacity: 0, reverse: backward });
+    }
+
+    /// Push one unit along shortest augmenting paths until none is left
+    fn max_flow(&mut self) -> usize {
+        let mut flow = 0;
+        while let Some(parents) = self.augmenting_path() {
+            // Every path crosses at least one unit-capacity cell edge
+            let mut node = self.sink;
+            while node != self.source {
+                let (from, index) = parents[node].expect("path nodes have parents");
+                let edge = &mut self.edges[from][index];
+                edge.capacity -= 1;
+                let (to, reverse) = (edge.to, edge.reverse);
+                self.edges[to][reverse].capacity += 1;
+                node = from;
+            }
+            flow += 1;
+        }
+        flow
+    }
+
+    /// BFS from the source over edges with capacity left
+    ///
+    /// Each node reached stores the edge it was reached by, as
+    /// `(from node, edge index)`. `None` if the sink is not reached.
+    fn augmenting_path(&self) -> Option<Vec<Option<(usize, usize)>>> {
+        let reached = self.residual_reach();
+        reached[self.sink].is_some().then_some(reached)
+    }
+
+    fn residual_reach(&self) -> Vec<Option<(usize, usize)>> {
+        let mut parents = vec![None; self.edges.len()];
+        parents[self.source] = Some((self.source, 0));
+        let mut queue = VecDeque::from([self.source]);
+        while let Some(node) = queue.pop_front() {
+            for (index, edge) in self.edges[node].iter().enumerate() {
+                if edge.capacity > 0 && parents[edge.to].is_none() {
+                    parents[edge.to] = Some((node, index));
+                    if edge.to == self.sink {
+                        return parents;
+                    }
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        parents
+    }
+}
+
+impl Grid {
+    /// Empty cells `(source side, sink side)` keep once the territories are walled off
+    ///
+    /// Finds a minimum set of empty cells separating `player1_source`'s
+    /// territory from `player2_source`'s (the minimum cut nearest
+    /// `player1_source`), then counts the empty cells each player still
+    /// reaches without crossing it. Cells on the cut are contested and,
+    /// like cells neither player can reach, count for neither side.
+    /// Territories that already touch are not separated by any cell.
+    pub fn max_flow_territory_partition(&self, player1_source: u8, player2_source: u8) -> (usize, usize) {
+        let mut network = FlowNetwork::new(self.cells.len());
+        let neighbors = |index: usize| {
+            let (x, y) = ((index % self.width) as i32, (index / self.width) as i32);
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .filter_map(move |(dx, dy)| self.get_signed(x + dx, y + dy).map(|cell| {
+                    ((y + dy) as usize * self.width + (x + dx) as usize, cell)
+                }))
+        };
+
+        for index in 0..self.cells.len() {
+            if self.cells[index] != CellState::Empty {
+                continue;
+            }
+            network.add_edge(2 * index, 2 * index + 1, 1);
+            for (neighbor, cell) in neighbors(index) {
+                if cell == CellState::Empty {
+                    network.add_edge(2 * index + 1, 2 * neighbor, UNBOUNDED);
+                } else if cell.is_player(player1_source) {
+                    network.add_edge(network.source, 2 * index, UNBOUNDED);
+                } else if cell.is_player(player2_source) {
+                    network.add_edge(2 * index + 1, network.sink, UNBOUNDED);
+                }
+            }
+        }
+
+        network.max_flow();
+        // The cut cells are those whose in node the source still reaches
+        // but whose out node it does not
+        let reach = network.residual_reach();
+        let cut: Vec<bool> = (0..self.cells.len())
+            .map(|index| reach[2 * index].is_some() && reach[2 * index + 1].is_none())
+            .collect();
+
+        let side = |player: u8| {
+            let mut seen = vec![false; self.cells.len()];
+            let mut queue: VecDeque<usize> = (0..self.cells.len())
+                .filter(|&index| self.cells[index].is_player(player))
+                .collect();
+            let mut count = 0;
+            while let Some(index) = queue.pop_front() {
+                for (neighbor, cell) in neighbors(index) {
+                    if cell == CellState::Empty && !cut[neighbor] && !seen[neighbor] {
+                        seen[neighbor] = true;
+                        count += 1;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            count
+        };
+
+        (side(player1_source), side(player2_source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_walls_off_the_doorway() {
+        // Two rooms walled apart by player 3, joined by the doorway at
+        // (2, 2); the cut nearest player 1 is the room cell in front of it
+        let grid: Grid = "\
+@.&..
+..&..
+.....
+..&..
+..&.$"
+            .parse()
+            .unwrap();
+
+        assert_eq!(grid.max_flow_territory_partition(1, 2), (8, 10));
+        // As the source, player 2 loses the cell in front of the doorway
+        assert_eq!(grid.max_flow_territory_partition(2, 1), (8, 10));
+    }
+
+    #[test]
+    fn test_partition_cuts_wide_fronts() {
+        // Three rows between the players need a three-cell wall
+        let grid: Grid = "\
+@....$
+@....$
+@....$"
+            .parse()
+            .unwrap();
+
+        let (mine, theirs) = grid.max_flow_territory_partition(1, 2);
+        assert_eq!(mine + theirs, 12 - 3);
+    }
+
+    #[test]
+    fn test_partition_without_contact() {
+        // Separate regions: nothing to cut, each keeps its own
+        let grid: Grid = "@.&.$\n..&.$".parse().unwrap();
+        assert_eq!(grid.max_flow_territory_partition(1, 2), (3, 2));
+
+        let touching: Grid = "@$..".parse().unwrap();
+        assert_eq!(touching.max_flow_territory_partition(1, 2), (0, 2));
+    }
+}
@@ -3,120 +3,149 @@
 /// This module provides sophisticated heuristics for evaluating placements
 /// including flood-fill territory analysis, edge detection, and density mapping.
 
-use crate::game_state::{Grid, Position, CellState, GameState};
-use crate::placement::Placement;
-use std::collections::{VecDeque, HashSet};
+use crate::game_state::{Grid, Position, GameState};
+use crate::placement::{valid_placements_iter, Placement};
+use crate::sim::PieceGenerator;
+use crate::utils::bfs_distance_map;
+use super::score::Score;
+use super::strategies::greedy_expansion_iter;
+use std::collections::{HashMap, HashSet};
+
+/// Weight of the opponent path term in `analyze_flood_fill`
+///
+/// A placement right next to the opponent earns half of this; the bonus
+/// falls off as `1 / (length + 1)` with the walkable path length.
+pub const FLOOD_FILL_PATH_WEIGHT: f32 = 10.0;
 
 /// Analyzes territory growth potential using flood-fill algorithm
 /// Returns the approximate maximum territory that could be claimed from this placement
-pub fn analyze_flood_fill(placement: &Placement, game_state: &GameState) -> f32 {
-    // Create a hypothetical grid state after this placement
-    let mut test_grid = game_state.grid.clone();
-    
-    // Simulate placing the piece
-    let absolute_positions = placement.get_absolute_positions();
-    for pos in absolute_positions {
-        if test_grid.is_valid(pos) {
-            test_grid.set(pos, CellState::Player1Last);
-        }
-    }
-    
-    // Perform flood-fill from the placement positions to estimate territory growth
-    let reachable = flood_fill_reachable(&test_grid, &placement.get_absolute_positions());
-    
-    // Score based on reachable empty cells
-    (reachable as f32) * 2.5
+///
+/// `simulated_grid` is the board after the placement (see `Placement::simulate`).
+/// Placements with a short walkable path to `opponent`'s territory
+/// (`opponent_path_length`) score up to `FLOOD_FILL_PATH_WEIGHT` more,
+/// since the reachable count alone cannot tell a wall-off from open space.
+pub fn analyze_flood_fill(placement: &Placement, simulated_grid: &Grid, opponent: u8) -> f32 {
+    analyze_flood_fill_with_distances(placement, simulated_grid, opponent, None)
 }
 
-/// Performs flood-fill to find all reachable empty cells from given positions
-fn flood_fill_reachable(grid: &Grid, start_positions: &[Position]) -> usize {
-    let mut visited = HashSet::new();
-    let mut queue = VecDeque::new();
-    
-    // Initialize queue with starting positions
-    for &pos in start_positions {
-        if grid.is_valid(pos) {
-            queue.push_back(pos);
-            visited.insert(pos);
-        }
-    }
-    
-    let mut reachable_count = 0;
+/// Flood-fill analysis, optionally weighted by a BFS distance map
+///
+/// Without a map every reachable empty cell scores 2.5. With a map (such as
+/// `Grid::distance_map` for our territory) each cell's score decays as
+/// `2.5 / (distance + 1)`, and cells the map cannot reach score nothing.
+/// The opponent path term of `analyze_flood_fill` is added either way.
+pub fn analyze_flood_fill_with_distances(
+    placement: &Placement,
+    simulated_grid: &Grid,
+    opponent: u8,
+    distance_map: Option<&[Vec<Option<usize>>]>,
+) -> f32 {
+    // Perform flood-fill from the placement positions to estimate territory growth
+    let positions = placement.get_absolute_positions();
+    let reachable = simulated_grid.reachable_empty_cells(&positions, None);
     
-    while let Some(pos) = queue.pop_front() {
-        // Check all 4 adjacent cells
-        let neighbors = [
-            Position::new(pos.x.wrapping_add(1), pos.y),
-            Position::new(pos.x.wrapping_sub(1), pos.y),
-            Position::new(pos.x, pos.y.wrapping_add(1)),
-            Position::new(pos.x, pos.y.wrapping_sub(1)),
-        ];
-        
-        for neighbor in neighbors {
-            if !visited.contains(&neighbor) && grid.is_valid(neighbor) {
-                if let Some(state) = grid.get(neighbor) {
-                    // Count empty cells and our territory
-                    if matches!(state, CellState::Empty | CellState::Player1 | CellState::Player1Last) {
-                        visited.insert(neighbor);
-                        
-                        if state == CellState::Empty {
-                            reachable_count += 1;
-                        }
-                        
-                        // Only continue flood-fill through empty cells
-                        if state == CellState::Empty {
-                            queue.push_back(neighbor);
-                        }
-                    }
-                }
-            }
+    let reach_score = match distance_map {
+        // Score based on reachable empty cells
+        None => (reachable.len() as f32) * 2.5,
+        Some(distances) => {
+            // Sum in a fixed order so the float result does not depend on
+            // hash-set iteration order
+            let mut cell_distances: Vec<usize> = reachable
+                .iter()
+                .filter_map(|pos| distances.get(pos.y)?.get(pos.x).copied().flatten())
+                .collect();
+            cell_distances.sort_unstable();
+            cell_distances.iter().map(|&d| 2.5 / (d as f32 + 1.0)).sum()
         }
+    };
+
+    let path_score = opponent_path_length(placement, simulated_grid, opponent)
+        .map_or(0.0, |length| FLOOD_FILL_PATH_WEIGHT / (length as f32 + 1.0));
+
+    reach_score + path_score
+}
+
+/// Length of the shortest empty-cell path from the placement to `opponent`
+///
+/// Manhattan distance ignores walls, so this runs one multi-source BFS
+/// from the whole opponent territory through empty cells and takes the
+/// smallest distance it assigns to a placement cell. `None` if the
+/// opponent has no cells or every route is walled off.
+pub fn opponent_path_length(placement: &Placement, simulated_grid: &Grid, opponent: u8) -> Option<usize> {
+    let distances = bfs_distance_map(simulated_grid, &simulated_grid.get_player_positions(opponent));
+
+    placement
+        .get_absolute_positions()
+        .into_iter()
+        .filter_map(|pos| distances.get(pos.y)?.get(pos.x).copied().flatten())
+        .min()
+}
+
+/// Default BFS distance at which a cell counts as fully out of the opponent's reach
+pub const DEFAULT_WEAKNESS_THRESHOLD: usize = 3;
+
+/// BFS distances from the opponent's frontier, as `detect_weak_positions`
+/// reads them
+/// 
+/// Depends only on the board, so build it once per turn and score every
+/// placement with `weak_positions_with_map`.
+#[derive(Debug, Clone)]
+pub struct WeaknessMap {
+    distances: Vec<Vec<Option<usize>>>,
+    /// Largest reachable distance, used for cells the opponent cannot reach
+    max_distance: usize,
+}
+
+impl WeaknessMap {
+    /// Compute the distance map for the opponent of `game_state`'s player
+    pub fn new(game_state: &GameState) -> Self {
+        // The opponent's frontier is its territory border, cached on the state
+        let frontier = &game_state.opponent_territory().border;
+        let distances = bfs_distance_map(game_state.grid(), frontier);
+        let max_distance = distances
+            .iter()
+            .flatten()
+            .filter_map(|&d| d)
+            .max()
+            .unwrap_or(0);
+        WeaknessMap { distances, max_distance }
     }
-    
-    reachable_count
 }
 
-/// Detects weak positions - areas where opponent has sparse territory
+/// Detects weak positions - areas far from the opponent's frontier
 /// Returns score based on attacking weak positions (higher = more opportunity)
-pub fn detect_weak_positions(placement: &Placement, game_state: &GameState) -> f32 {
-    let abs_positions = placement.get_absolute_positions();
-    let mut weak_score = 0.0;
+///
+/// Each placement cell is scored by its BFS distance from the opponent's
+/// frontier cells. Cells at least `weakness_threshold` steps away score the
+/// full 3.0; closer cells score proportionally less. Cells the opponent
+/// cannot reach at all use the largest distance seen on the board.
+pub fn detect_weak_positions(
+    placement: &Placement,
+    game_state: &GameState,
+    weakness_threshold: usize,
+) -> f32 {
+    weak_positions_with_map(placement, &WeaknessMap::new(game_state), weakness_threshold)
+}
+
+/// `detect_weak_positions` using a precomputed `WeaknessMap`
+pub fn weak_positions_with_map(
+    placement: &Placement,
+    weakness: &WeaknessMap,
+    weakness_threshold: usize,
+) -> f32 {
+    let threshold = weakness_threshold.max(1);
     
-    for pos in abs_positions {
-        if game_state.grid.is_valid(pos) {
-            // Check density of opponent territory around this position
-            let opponent_density = count_opponent_neighbors(&game_state.grid, pos);
-            
-            // Low opponent density = weak position (good for us)
-            if opponent_density < 2 {
-                weak_score += 3.0;
-            } else if opponent_density < 4 {
-                weak_score += 1.5;
-            }
+    let mut weak_score = 0.0;
+    for pos in placement.get_absolute_positions() {
+        if let Some(&distance) = weakness.distances.get(pos.y).and_then(|row| row.get(pos.x)) {
+            let distance = distance.unwrap_or(weakness.max_distance).min(threshold);
+            weak_score += 3.0 * (distance as f32) / (threshold as f32);
         }
     }
     
     weak_score
 }
 
-/// Count opponent (player 2) cells adjacent to a position
-fn count_opponent_neighbors(grid: &Grid, pos: Position) -> usize {
-    let neighbors = [
-        Position::new(pos.x.wrapping_add(1), pos.y),
-        Position::new(pos.x.wrapping_sub(1), pos.y),
-        Position::new(pos.x, pos.y.wrapping_add(1)),
-        Position::new(pos.x, pos.y.wrapping_sub(1)),
-    ];
-    
-    neighbors.iter().filter(|&&neighbor| {
-        if grid.is_valid(neighbor) {
-            matches!(grid.get(neighbor), Some(CellState::Player2 | CellState::Player2Last))
-        } else {
-            false
-        }
-    }).count()
-}
-
 /// Analyzes territory density around a placement position
 /// Higher density means more consolidated territory (better defense)
 pub fn analyze_density(placement: &Placement, game_state: &GameState) -> f32 {
@@ -125,9 +154,9 @@ pub fn analyze_density(placement: &Placement, game_state: &GameState) -> f32 {
     let mut count = 0;
     
     for pos in &abs_positions {
-        if game_state.grid.is_valid(*pos) {
+        if game_state.grid().is_valid(*pos) {
             // Count our territory cells within distance 2
-            let nearby_our_territory = count_nearby_our_territory(&game_state.grid, *pos);
+            let nearby_our_territory = count_nearby_our_territory(game_state.grid(), *pos, game_state.player_number());
             
             // More nearby territory = higher density bonus
             density_score += (nearby_our_territory as f32) * 0.8;
@@ -142,8 +171,8 @@ pub fn analyze_density(placement: &Placement, game_state: &GameState) -> f32 {
     }
 }
 
-/// Count our (player 1) territory cells within manhattan distance 2
-fn count_nearby_our_territory(grid: &Grid, center: Position) -> usize {
+/// Count `player`'s territory cells within manhattan distance 2
+fn count_nearby_our_territory(grid: &Grid, center: Position, player: u8) -> usize {
     let mut count = 0;
     
     // Check positions at manhattan distance <= 2
@@ -153,16 +182,9 @@ fn count_nearby_our_territory(grid: &Grid, center: Position) -> usize {
                 continue;
             }
             
-            let x = (center.x as i32 + dx) as usize;
-            let y = (center.y as i32 + dy) as usize;
-            let pos = Position::new(x, y);
-            
-            if grid.is_valid(pos) {
-                if let Some(state) = grid.get(pos) {
-                    if matches!(state, CellState::Player1 | CellState::Player1Last) {
-                        count += 1;
-                    }
-                }
+            let cell = center.checked_add(dx, dy).and_then(|pos| grid.get(pos));
+            if cell.is_some_and(|c| c.is_player(player)) {
+                count += 1;
             }
         }
     }
@@ -170,6 +192,77 @@ fn count_nearby_our_territory(grid: &Grid, center: Position) -> usize {
     count
 }
 
+/// Connectivity features of a player's territory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentFeatures {
+    /// Number of disconnected islands
+    pub count: usize,
+    /// Size of the largest island
+    pub largest: usize,
+}
+
+/// Compute component count and largest-component size for `player`
+pub fn territory_component_features(grid: &Grid, player: u8) -> ComponentFeatures {
+    let components = grid.connected_components(player);
+    ComponentFeatures {
+        count: components.len(),
+        largest: components.iter().map(|c| c.len()).max().unwrap_or(0),
+    }
+}
+
+/// Territory size over the number of cells its convex hull covers
+///
+/// 1.0 for a convex territory, falling toward 0 as it gets concave or
+/// stringy: a low ratio means gaps worth consolidating. Hull cells are
+/// the lattice points on or inside `hull` (Pick's theorem), so a hull of
+/// one or two vertices covers its point or segment. 1.0 for no territory.
+pub fn territory_convexity_ratio(territory: &[Position], hull: &[Position]) -> f32 {
+    let cells = hull_cell_count(hull);
+    if territory.is_empty() || cells == 0 {
+        return 1.0;
+    }
+    (territory.len() as f32 / cells as f32).min(1.0)
+}
+
+/// Lattice points on or inside the polygon `hull`
+fn hull_cell_count(hull: &[Position]) -> usize {
+    if hull.is_empty() {
+        return 0;
+    }
+    let mut twice_area = 0i64;
+    let mut boundary = 0i64;
+    for (i, a) in hull.iter().enumerate() {
+        let b = hull[(i + 1) % hull.len()];
+        let (ax, ay, bx, by) = (a.x as i64, a.y as i64, b.x as i64, b.y as i64);
+        twice_area += ax * by - bx * ay;
+        boundary += gcd((bx - ax).unsigned_abs(), (by - ay).unsigned_abs()) as i64;
+    }
+    // Pick: area = interior + boundary / 2 - 1
+    ((twice_area.abs() + boundary) / 2 + 1) as usize
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Analyzes how a placement changes territory connectivity
+/// Rewards merging islands and growing the largest connected blob
+pub fn analyze_connectivity(placement: &Placement, game_state: &GameState) -> f32 {
+    let player = game_state.player_number();
+    let before = territory_component_features(game_state.grid(), player);
+    connectivity_gain_with_features(&before, &placement.simulate(game_state.grid(), player), player)
+}
+
+/// `analyze_connectivity` using the precomputed features of the board
+/// before the placement and the simulated board after it
+pub fn connectivity_gain_with_features(before: &ComponentFeatures, simulated_grid: &Grid, player: u8) -> f32 {
+    let after = territory_component_features(simulated_grid, player);
+    
+    let merged = before.count.saturating_sub(after.count);
+    let growth = after.largest.saturating_sub(before.largest);
+    (merged as f32) * 5.0 + (growth as f32) * 0.5
+}
+
 /// Analyzes strategic value of controlling edges and corners
 /// Corners and edges provide natural defense
 pub fn analyze_edge_control(placement: &Placement, grid: &Grid) -> f32 {
@@ -194,45 +287,361 @@ pub fn analyze_edge_control(placement: &Placement, grid: &Grid) -> f32 {
     edge_score
 }
 
+/// Counts the chokepoints a placement occupies
+/// Returns the number of placement cells that are articulation points of
+/// the empty-cell graph (see `Grid::articulation_points`); filling one cuts
+/// an empty region in two and can deny the opponent the far side
+pub fn chokepoint_score(placement: &Placement, game_state: &GameState) -> f32 {
+    let chokepoints: HashSet<Position> = game_state.grid().articulation_points().into_iter().collect();
+    chokepoint_score_with_set(placement, &chokepoints)
+}
+
+/// `chokepoint_score` against precomputed `Grid::articulation_points`
+pub fn chokepoint_score_with_set(placement: &Placement, chokepoints: &HashSet<Position>) -> f32 {
+    placement
+        .get_absolute_positions()
+        .iter()
+        .filter(|pos| chokepoints.contains(pos))
+        .count() as f32
+}
+
+/// Sums the empty cells cut off by the bottlenecks a placement covers
+/// Each cell of the placement that is a bottleneck of the empty-cell graph
+/// (see `Grid::find_bottlenecks`) adds the size of the region filling it
+/// disconnects, so covering the largest bottleneck scores the most
+pub fn bottleneck_score(placement: &Placement, game_state: &GameState) -> f32 {
+    let bottlenecks: HashMap<Position, usize> = game_state.grid().find_bottlenecks().into_iter().collect();
+    bottleneck_score_with_map(placement, &bottlenecks)
+}
+
+/// `bottleneck_score` against precomputed `Grid::find_bottlenecks` cuts
+pub fn bottleneck_score_with_map(placement: &Placement, bottlenecks: &HashMap<Position, usize>) -> f32 {
+    placement
+        .get_absolute_positions()
+        .iter()
+        .filter_map(|pos| bottlenecks.get(pos))
+        .sum::<usize>() as f32
+}
+
+/// Seed for the piece sequence used by growth projections (kept fixed for determinism)
+const PROJECTION_SEED: u64 = 0x5EED;
+
+/// Projects how many cells the opponent would gain over `turns` unopposed moves
+///
+/// Plays `GreedyExpansion` for the opponent on a cloned board with randomly
+/// generated pieces. Turns where the piece can't be placed gain nothing.
+pub fn analyze_opponent_growth_projection(game_state: &GameState, turns: usize) -> f32 {
+    let opponent = game_state.opponent_number();
+    analyze_growth_projection(game_state, opponent, turns)
+}
+
+/// Our projected growth minus the opponent's over `turns` unopposed moves
+///
+/// Negative values mean the opponent is expanding faster than we are.
+pub fn analyze_projected_advantage(game_state: &GameState, turns: usize) -> f32 {
+    analyze_growth_projection(game_state, game_state.player_number(), turns)
+        - analyze_opponent_growth_projection(game_state, turns)
+}
+
+/// Simulate `turns` greedy moves for `player` and return the cells gained
+pub fn analyze_growth_projection(game_state: &GameState, player: u8, turns: usize) -> f32 {
+    let mut grid = game_state.grid().grid().clone();
+    let mut pieces = PieceGenerator::new(PROJECTION_SEED);
+    let start = grid.count_territory(player);
+    
+    for _ in 0..turns {
+        let state = GameState::new(player, grid.clone(), pieces.next_piece());
+        if let Some(placement) = greedy_expansion_iter(valid_placements_iter(&state), &state, None) {
+            grid.apply_placement(&placement, player);
+        }
+    }
+    
+    (grid.count_territory(player) - start) as f32
+}
+
+/// Compute a signed influence map over the board
+/// 
+/// Each empty cell scores `1 / (d1 + 1) - 1 / (d2 + 1)`, where `d1` and `d2`
+/// are BFS distances from player 1's and player 2's territory (a player that
+/// cannot reach the cell contributes 0). Positive values favor player 1,
+/// negative values player 2; occupied cells are 0. Indexed as `map[y][x]`.
+pub fn compute_influence_map(grid: &Grid) -> Vec<Vec<f32>> {
+    let (player1, player2) = grid.voronoi_distance_pair();
+    let decay = |d: Option<usize>| d.map_or(0.0, |d| 1.0 / (d as f32 + 1.0));
+    
+    let mut map = vec![vec![0.0; grid.width]; grid.height];
+    for pos in grid.get_empty_positions() {
+        map[pos.y][pos.x] = decay(player1[pos.y][pos.x]) - decay(player2[pos.y][pos.x]);
+    }
+    map
+}
+
+/// Analyzes how much contested influence a placement claims
+/// Sums influence-map values over the placement cells and their neighbors,
+/// signed so that positive values favor the current player
+pub fn analyze_influence_gain(placement: &Placement, game_state: &GameState) -> f32 {
+    let influence = compute_influence_map(game_state.grid());
+    influence_gain_with_map(placement, game_state, &influence)
+}
+
+/// Influence gain using a precomputed influence map
+pub fn influence_gain_with_map(
+    placement: &Placement,
+    game_state: &GameState,
+    influence: &[Vec<f32>],
+) -> f32 {
+    let grid = game_state.grid();
+    let mut cells = HashSet::new();
+    for pos in placement.get_absolute_positions() {
+        cells.insert(pos);
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            if let Some(neighbor) = pos.checked_add(dx, dy).filter(|&n| grid.is_valid(n)) {
+                cells.insert(neighbor);
+            }
+        }
+    }
+    
+    let total: f32 = cells
+        .iter()
+        .filter_map(|pos| influence.get(pos.y)?.get(pos.x))
+        .sum();
+    if game_state.player_number() == 1 { total } else { -total }
+}
+
+/// Multipliers for each heuristic term in placement scoring
+/// 
+/// `HeuristicWeights::default()` reproduces the original hand-tuned
+/// constants; `from_env` lets them be tuned without recompiling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeuristicWeights {
+    /// Per empty cell claimed
+    pub expansion: f32,
+    pub flood_fill: f32,
+    pub weak_positions: f32,
+    pub density: f32,
+    pub edge_control: f32,
+    pub influence: f32,
+    pub connectivity: f32,
+    /// Per empty cell cut off by a covered bottleneck
+    pub bottleneck: f32,
+    /// Per step closer to the board center (evaluator only)
+    pub centrality: f32,
+    /// Per cell touching own territory (evaluator only)
+    pub adjacency: f32,
+}
+
+impl Default for HeuristicWeights {
+    fn default() -> Self {
+        HeuristicWeights {
+            expansion: 10.0,
+            flood_fill: 1.5,
+            weak_positions: 2.0,
+            density: 1.2,
+            edge_control: 0.5,
+            influence: 1.0,
+            connectivity: 1.0,
+            bottleneck: 0.5,
+            centrality: 0.5,
+            adjacency: 1.0,
+        }
+    }
+}
+
+impl HeuristicWeights {
+    /// Defaults overridden by `FILLER_WEIGHT_<FIELD>` environment variables
+    /// 
+    /// For example `FILLER_WEIGHT_FLOOD_FILL=2.0`. Unset or unparsable
+    /// variables keep the default value.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Defaults overridden by values from `lookup`, keyed by variable name
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let read = |name: &str, default: f32| {
+            lookup(&format!("FILLER_WEIGHT_{}", name))
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        let defaults = HeuristicWeights::default();
+
+        HeuristicWeights {
+            expansion: read("EXPANSION", defaults.expansion),
+            flood_fill: read("FLOOD_FILL", defaults.flood_fill),
+            weak_positions: read("WEAK_POSITIONS", defaults.weak_positions),
+            density: read("DENSITY", defaults.density),
+            edge_control: read("EDGE_CONTROL", defaults.edge_control),
+            influence: read("INFLUENCE", defaults.influence),
+            connectivity: read("CONNECTIVITY", defaults.connectivity),
+            bottleneck: read("BOTTLENECK", defaults.bottleneck),
+            centrality: read("CENTRALITY", defaults.centrality),
+            adjacency: read("ADJACENCY", defaults.adjacency),
+        }
+    }
+}
+
+/// Board-wide maps `advanced_score_pure` reads, computed once per turn
+/// 
+/// They depend only on the board before the placement, so callers scoring
+/// every candidate of a turn build one `ScoreMaps` and share it.
+#[derive(Debug, Clone)]
+pub struct ScoreMaps {
+    /// See `compute_influence_map`
+    pub influence: Vec<Vec<f32>>,
+    pub weakness: WeaknessMap,
+    /// The current player's territory islands
+    pub components: ComponentFeatures,
+    /// Empty cells cut off by each bottleneck, see `Grid::find_bottlenecks`
+    pub bottlenecks: HashMap<Position, usize>,
+}
+
+impl ScoreMaps {
+    /// Compute the maps for the board in `game_state`
+    pub fn new(game_state: &GameState) -> Self {
+        ScoreMaps {
+            influence: compute_influence_map(game_state.grid()),
+            weakness: WeaknessMap::new(game_state),
+            components: territory_component_features(game_state.grid(), game_state.player_number()),
+            bottlenecks: game_state.grid().find_bottlenecks().into_iter().collect(),
+        }
+    }
+}
+
 /// Comprehensive advanced scoring combining all heuristics
-pub fn advanced_score(placement: &Placement, game_state: &GameState) -> f32 {
+/// 
+/// Compatibility wrapper that simulates the placement and delegates to
+/// `advanced_score_pure`. It rebuilds the `ScoreMaps` on every call, so
+/// loops over a turn's placements should call `advanced_score_pure` instead.
+pub fn advanced_score(placement: &Placement, game_state: &GameState, weights: &HeuristicWeights) -> Score {
+    let simulated_grid = placement.simulate(game_state.grid(), game_state.player_number());
+    advanced_score_pure(placement, &simulated_grid, game_state, &ScoreMaps::new(game_state), weights)
+}
+
+/// Advanced scoring from a pre-simulated post-placement grid
+/// 
+/// Every component is a pure function of its inputs: `simulated_grid` is
+/// the board after the placement, `original_state` the board before it and
+/// `maps` the `ScoreMaps` of `original_state`.
+pub fn advanced_score_pure(
+    placement: &Placement,
+    simulated_grid: &Grid,
+    original_state: &GameState,
+    maps: &ScoreMaps,
+    weights: &HeuristicWeights,
+) -> Score {
     // Base expansion score (most important)
-    let base_expansion = (placement.cells_added as f32) * 10.0;
+    let base_expansion = (placement.cells_added as f32) * weights.expansion;
     
     // Advanced heuristics (new in Phase 5)
-    let flood_fill = analyze_flood_fill(placement, game_state);
-    let weak_positions = detect_weak_positions(placement, game_state);
-    let density = analyze_density(placement, game_state);
-    let edge_control = analyze_edge_control(placement, &game_state.grid);
+    let flood_fill = analyze_flood_fill(placement, simulated_grid, original_state.opponent_number());
+    let weak_positions = weak_positions_with_map(placement, &maps.weakness, DEFAULT_WEAKNESS_THRESHOLD);
+    let density = analyze_density(placement, original_state);
+    let edge_control = analyze_edge_control(placement, original_state.grid());
+    let influence = influence_gain_with_map(placement, original_state, &maps.influence);
+    let connectivity =
+        connectivity_gain_with_features(&maps.components, simulated_grid, original_state.player_number());
+    let bottleneck = bottleneck_score_with_map(placement, &maps.bottlenecks);
     
     // Combine scores with strategic weights
-    base_expansion 
-        + (flood_fill * 1.5)           // Territory growth potential (medium importance)
-        + (weak_positions * 2.0)       // Attacking weak positions (high importance)
-        + (density * 1.2)              // Territory consolidation (medium importance)
-        + (edge_control * 0.5)         // Edge control (lower importance)
+    Score(
+        base_expansion
+            + flood_fill * weights.flood_fill             // Territory growth potential
+            + weak_positions * weights.weak_positions     // Attacking weak positions
+            + density * weights.density                   // Territory consolidation
+            + edge_control * weights.edge_control         // Edge control
+            + influence * weights.influence               // Contested-space influence
+            + connectivity * weights.connectivity         // Merging territory islands
+            + bottleneck * weights.bottleneck,            // Cutting off empty regions
+    )
+}
+
+/// Weighted contribution of each heuristic to `advanced_score`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBreakdown {
+    pub expansion: f32,
+    pub flood_fill: f32,
+    pub weak_positions: f32,
+    pub density: f32,
+    pub edge_control: f32,
+    pub influence: f32,
+    pub connectivity: f32,
+    pub bottleneck: f32,
+    /// Sum of all terms, equal to `advanced_score`
+    pub total: f32,
+}
+
+impl std::fmt::Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "total {:.2} = expansion {:.2} + flood_fill {:.2} + weak {:.2} + density {:.2} + edge {:.2} + influence {:.2} + connectivity {:.2} + bottleneck {:.2}",
+            self.total,
+            self.expansion,
+            self.flood_fill,
+            self.weak_positions,
+            self.density,
+            self.edge_control,
+            self.influence,
+            self.connectivity,
+            self.bottleneck,
+        )
+    }
+}
+
+/// `advanced_score` split into its weighted terms, for debugging
+///
+/// Recomputes every per-placement heuristic; keep it off the hot path.
+/// `maps` are the `ScoreMaps` of `game_state`.
+pub fn advanced_score_with_breakdown(
+    placement: &Placement,
+    game_state: &GameState,
+    maps: &ScoreMaps,
+    weights: &HeuristicWeights,
+) -> ScoreBreakdown {
+    let simulated_grid = placement.simulate(game_state.grid(), game_state.player_number());
+
+    let expansion = (placement.cells_added as f32) * weights.expansion;
+    let flood_fill = analyze_flood_fill(placement, &simulated_grid, game_state.opponent_number()) * weights.flood_fill;
+    let weak_positions =
+        weak_positions_with_map(placement, &maps.weakness, DEFAULT_WEAKNESS_THRESHOLD) * weights.weak_positions;
+    let density = analyze_density(placement, game_state) * weights.density;
+    let edge_control = analyze_edge_control(placement, game_state.grid()) * weights.edge_control;
+    let influence = influence_gain_with_map(placement, game_state, &maps.influence) * weights.influence;
+    let connectivity =
+        connectivity_gain_with_features(&maps.components, &simulated_grid, game_state.player_number())
+            * weights.connectivity;
+    let bottleneck = bottleneck_score_with_map(placement, &maps.bottlenecks) * weights.bottleneck;
+
+    ScoreBreakdown {
+        expansion,
+        flood_fill,
+        weak_positions,
+        density,
+        edge_control,
+        influence,
+        connectivity,
+        bottleneck,
+        // Same summation order as `advanced_score_pure`
+        total: expansion + flood_fill + weak_positions + density + edge_control + influence + connectivity + bottleneck,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::game_state::Shape;
+    use crate::utils::convex_hull;
 
     fn create_test_grid() -> Grid {
-        let raw = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '@', '@', '.', '.'],
-            vec!['.', '@', '.', '.', '.'],
-            vec!['.', '.', '.', '$', '$'],
-            vec!['.', '.', '.', '$', '.'],
-        ];
-        Grid::from_chars(5, 5, raw)
+        create_test_game_state().into_grid()
     }
 
     fn create_test_game_state() -> GameState {
-        let grid = create_test_grid();
-        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
-        GameState::new(1, grid, shape)
+        GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(1, 2)])
+            .with_territory(2, &[Position::new(3, 3), Position::new(4, 3), Position::new(3, 4)])
+            .with_piece(Shape::from_chars(1, 1, vec![vec!['#']]))
+            .build()
     }
 
     fn create_test_placement(x: usize, y: usize) -> Placement {
@@ -246,24 +655,159 @@ mod tests {
     }
 
     #[test]
-    fn test_flood_fill_reachable() {
-        let grid = create_test_grid();
-        let start = vec![Position::new(1, 1)];
-        
-        let reachable = flood_fill_reachable(&grid, &start);
+    fn test_analyze_flood_fill() {
+        let game_state = create_test_game_state();
+        let placement = create_test_placement(1, 0);
+        let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+        let score = analyze_flood_fill(&placement, &simulated, game_state.opponent_number());
         
-        // Should find some empty cells reachable from position (1,1)
-        assert!(reachable > 0);
+        // Should return a positive score
+        assert!(score > 0.0);
     }
 
     #[test]
-    fn test_analyze_flood_fill() {
+    fn test_analyze_flood_fill_with_distances() {
         let game_state = create_test_game_state();
         let placement = create_test_placement(1, 0);
-        let score = analyze_flood_fill(&placement, &game_state);
+        let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+        let distances = game_state.grid().distance_map(1);
         
-        // Should return a positive score
-        assert!(score > 0.0);
+        let unweighted = analyze_flood_fill_with_distances(&placement, &simulated, 2, None);
+        let weighted = analyze_flood_fill_with_distances(&placement, &simulated, 2, Some(&distances));
+        
+        assert_eq!(unweighted, analyze_flood_fill(&placement, &simulated, 2));
+        assert!(weighted > 0.0);
+        assert!(weighted < unweighted);
+    }
+
+    #[test]
+    fn test_territory_component_features() {
+        let grid = GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(0, 0), Position::new(1, 0), Position::new(4, 4)])
+            .build()
+            .into_grid();
+        
+        let features = territory_component_features(&grid, 1);
+        
+        assert_eq!(features, ComponentFeatures { count: 2, largest: 2 });
+        assert_eq!(territory_component_features(&grid, 2), ComponentFeatures { count: 0, largest: 0 });
+    }
+
+    #[test]
+    fn test_territory_convexity_ratio() {
+        let p = Position::new;
+        let square: Vec<Position> = (0..3).flat_map(|y| (0..3).map(move |x| p(x, y))).collect();
+        let l_shape = [p(0, 0), p(0, 1), p(0, 2), p(1, 2), p(2, 2)];
+
+        assert_eq!(territory_convexity_ratio(&square, &convex_hull(&square)), 1.0);
+        // The L's hull triangle covers 6 cells
+        assert_eq!(territory_convexity_ratio(&l_shape, &convex_hull(&l_shape)), 5.0 / 6.0);
+        // Two far apart cells: the hull is the 5-cell segment between them
+        assert_eq!(territory_convexity_ratio(&[p(0, 0), p(4, 0)], &[p(0, 0), p(4, 0)]), 0.4);
+        assert_eq!(territory_convexity_ratio(&[], &[]), 1.0);
+    }
+
+    #[test]
+    fn test_analyze_connectivity_rewards_merging() {
+        // Islands at (0, 0) and (2, 1); a bar along row 0 touches both
+        let game_state = GameState::builder_from_size(5, 3)
+            .with_territory(1, &[Position::new(0, 0), Position::new(2, 1)])
+            .build();
+        let bridge = Placement {
+            position: Position::new(0, 0),
+            shape: Shape::from_chars(3, 1, vec![vec!['#', '#', '#']]),
+            cells_added: 2,
+            territory_touches: 1,
+        };
+        let away = Placement {
+            position: Position::new(2, 1),
+            shape: Shape::from_chars(3, 1, vec![vec!['#', '#', '#']]),
+            cells_added: 2,
+            territory_touches: 1,
+        };
+        
+        // Bridge: 2 islands -> 1 of size 4; away: still 2 islands, largest 3
+        assert_eq!(analyze_connectivity(&bridge, &game_state), 5.0 + 3.0 * 0.5);
+        assert_eq!(analyze_connectivity(&away, &game_state), 2.0 * 0.5);
+
+        // The connectivity weight scales exactly this term of advanced_score
+        let without = HeuristicWeights { connectivity: 0.0, ..HeuristicWeights::default() };
+        let gain = advanced_score(&bridge, &game_state, &HeuristicWeights::default()).value()
+            - advanced_score(&bridge, &game_state, &without).value();
+        assert!((gain - analyze_connectivity(&bridge, &game_state)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_analyze_connectivity_as_player_two() {
+        // Same islands as above, owned by player 2 with a player 1 cell nearby
+        let game_state = GameState::builder_from_size(5, 3)
+            .with_player(2)
+            .with_territory(2, &[Position::new(0, 0), Position::new(2, 1)])
+            .with_territory(1, &[Position::new(4, 2)])
+            .build();
+        let bar = |x, y| Placement {
+            position: Position::new(x, y),
+            shape: Shape::from_chars(3, 1, vec![vec!['#', '#', '#']]),
+            cells_added: 2,
+            territory_touches: 1,
+        };
+
+        assert_eq!(analyze_connectivity(&bar(0, 0), &game_state), 5.0 + 3.0 * 0.5);
+        assert_eq!(analyze_connectivity(&bar(2, 1), &game_state), 2.0 * 0.5);
+    }
+
+    #[test]
+    fn test_compute_influence_map() {
+        let grid = GameState::builder_from_size(5, 1)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &[Position::new(4, 0)])
+            .build()
+            .into_grid();
+        
+        let map = compute_influence_map(&grid);
+        
+        assert_eq!(map[0][0], 0.0);
+        assert!(map[0][1] > 0.0);
+        assert_eq!(map[0][2], 0.0);
+        assert!(map[0][3] < 0.0);
+        assert_eq!(map[0][1], -map[0][3]);
+    }
+
+    #[test]
+    fn test_analyze_influence_gain_sign_follows_player() {
+        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let placement = Placement {
+            position: Position::new(1, 0),
+            shape,
+            cells_added: 1,
+            territory_touches: 0,
+        };
+        let state = |player| {
+            GameState::builder_from_size(6, 1)
+                .with_territory(1, &[Position::new(0, 0)])
+                .with_territory(2, &[Position::new(5, 0)])
+                .with_player(player)
+                .build()
+        };
+        
+        let gain = analyze_influence_gain(&placement, &state(1));
+        
+        assert!(gain > 0.0);
+        assert_eq!(analyze_influence_gain(&placement, &state(2)), -gain);
+    }
+
+    #[test]
+    fn test_analyze_flood_fill_does_not_modify_input() {
+        let game_state = create_test_game_state();
+        let placement = create_test_placement(2, 2);
+        let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+        let before = simulated.clone();
+        
+        let first = analyze_flood_fill(&placement, &simulated, 2);
+        let second = analyze_flood_fill(&placement, &simulated, 2);
+        
+        assert_eq!(first, second);
+        assert_eq!(simulated, before);
     }
 
     #[test]
@@ -271,12 +815,35 @@ mod tests {
         let game_state = create_test_game_state();
         // Position near opponent should have weak position score
         let placement = create_test_placement(2, 3);
-        let score = detect_weak_positions(&placement, &game_state);
+        let score = detect_weak_positions(&placement, &game_state, DEFAULT_WEAKNESS_THRESHOLD);
         
-        // Score depends on opponent density
+        // Score depends on distance from opponent frontier
         assert!(score >= 0.0);
     }
 
+    #[test]
+    fn test_detect_weak_positions_prefers_far_cells() {
+        let game_state = create_test_game_state();
+        let far = create_test_placement(0, 0);
+        let adjacent = create_test_placement(2, 3);
+        
+        let far_score = detect_weak_positions(&far, &game_state, DEFAULT_WEAKNESS_THRESHOLD);
+        let adjacent_score = detect_weak_positions(&adjacent, &game_state, DEFAULT_WEAKNESS_THRESHOLD);
+        
+        assert!(far_score > adjacent_score);
+    }
+
+    #[test]
+    fn test_detect_weak_positions_threshold() {
+        let game_state = create_test_game_state();
+        // (0, 0) is 6 steps from the opponent frontier
+        let far = create_test_placement(0, 0);
+        
+        // Fully weak under a low threshold, only partially weak under a high one
+        assert_eq!(detect_weak_positions(&far, &game_state, 3), 3.0);
+        assert!(detect_weak_positions(&far, &game_state, 10) < 3.0);
+    }
+
     #[test]
     fn test_analyze_density() {
         let game_state = create_test_game_state();
@@ -309,25 +876,29 @@ mod tests {
     }
 
     #[test]
-    fn test_advanced_score_combines_heuristics() {
+    fn test_advanced_score_pure_matches_shim() {
         let game_state = create_test_game_state();
-        let placement = create_test_placement(1, 1);
-        let score = advanced_score(&placement, &game_state);
+        let maps = ScoreMaps::new(&game_state);
         
-        // Should return a positive score combining all heuristics
-        assert!(score > 0.0);
+        for (x, y) in [(1, 0), (0, 1), (2, 2), (0, 4)] {
+            let placement = create_test_placement(x, y);
+            let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+            
+            assert_eq!(
+                advanced_score(&placement, &game_state, &HeuristicWeights::default()),
+                advanced_score_pure(&placement, &simulated, &game_state, &maps, &HeuristicWeights::default())
+            );
+        }
     }
 
     #[test]
-    fn test_count_opponent_neighbors() {
-        let grid = create_test_grid();
-        
-        // Position adjacent to opponent
-        let pos = Position::new(3, 3);
-        let count = count_opponent_neighbors(&grid, pos);
+    fn test_advanced_score_combines_heuristics() {
+        let game_state = create_test_game_state();
+        let placement = create_test_placement(1, 1);
+        let score = advanced_score(&placement, &game_state, &HeuristicWeights::default());
         
-        // Should count neighboring opponent cells
-        assert!(count > 0);
+        // Should return a positive score combining all heuristics
+        assert!(score > Score(0.0));
     }
 
     #[test]
@@ -336,9 +907,207 @@ mod tests {
         
         // Position near our territory
         let pos = Position::new(1, 1);
-        let count = count_nearby_our_territory(&grid, pos);
+        let count = count_nearby_our_territory(&grid, pos, 1);
         
         // Should count nearby our territory cells
         assert!(count >= 1); // At least the cell itself if it's ours
+
+        // Player 2 counts its own cells, not player 1's
+        assert_eq!(count_nearby_our_territory(&grid, Position::new(4, 4), 2), 3);
+        assert_eq!(count_nearby_our_territory(&grid, Position::new(4, 4), 1), 0);
+
+        let (near_player1, near_player2) = (create_test_placement(0, 0), create_test_placement(4, 4));
+        let as_player1 = create_test_game_state();
+        let as_player2 = as_player1.for_opponent();
+        assert!(analyze_density(&near_player1, &as_player1) > analyze_density(&near_player2, &as_player1));
+        assert!(analyze_density(&near_player2, &as_player2) > analyze_density(&near_player1, &as_player2));
+    }
+
+    fn create_open_game_state() -> GameState {
+        GameState::builder_from_size(12, 10)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_territory(2, &[Position::new(9, 7)])
+            .build()
+    }
+
+    #[test]
+    fn test_opponent_growth_projection_positive() {
+        let game_state = create_open_game_state();
+        let projection = analyze_opponent_growth_projection(&game_state, 3);
+        
+        assert!(projection > 0.0);
+        // Also non-zero on the smaller fixture board
+        assert!(analyze_opponent_growth_projection(&create_test_game_state(), 3) > 0.0);
+    }
+
+    #[test]
+    fn test_opponent_growth_projection_scales_with_turns() {
+        let game_state = create_open_game_state();
+        let short = analyze_opponent_growth_projection(&game_state, 2);
+        let long = analyze_opponent_growth_projection(&game_state, 6);
+        
+        assert!(long > short);
+        assert_eq!(analyze_opponent_growth_projection(&game_state, 0), 0.0);
+    }
+
+    #[test]
+    fn test_projected_advantage() {
+        let game_state = create_open_game_state();
+        let own = analyze_growth_projection(&game_state, 1, 3);
+        let opponent = analyze_opponent_growth_projection(&game_state, 3);
+        
+        assert!(own > 0.0);
+        assert_eq!(analyze_projected_advantage(&game_state, 3), own - opponent);
+    }
+
+    #[test]
+    fn test_heuristic_weights_from_lookup() {
+        let weights = HeuristicWeights::from_lookup(|name| match name {
+            "FILLER_WEIGHT_FLOOD_FILL" => Some("2.5".to_string()),
+            "FILLER_WEIGHT_DENSITY" => Some("not a number".to_string()),
+            "FILLER_WEIGHT_BOTTLENECK" => Some("1.5".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(weights.flood_fill, 2.5);
+        assert_eq!(weights.density, HeuristicWeights::default().density);
+        assert_eq!(weights.bottleneck, 1.5);
+        assert_eq!(
+            HeuristicWeights::from_lookup(|_| None),
+            HeuristicWeights::default()
+        );
+    }
+
+    #[test]
+    fn test_advanced_score_uses_weights() {
+        let game_state = create_test_game_state();
+        let placement = create_test_placement(1, 0);
+        let default = advanced_score(&placement, &game_state, &HeuristicWeights::default());
+        let heavier = HeuristicWeights {
+            expansion: 20.0,
+            ..HeuristicWeights::default()
+        };
+
+        // One cell added, so doubling the expansion weight adds 10
+        let boosted = advanced_score(&placement, &game_state, &heavier);
+        assert!((boosted.value() - default.value() - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_score_breakdown_matches_advanced_score() {
+        let game_state = GameState::builder_from_size(8, 8)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_territory(2, &[Position::new(5, 5)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let weights = HeuristicWeights::default();
+        let maps = ScoreMaps::new(&game_state);
+
+        for placement in crate::placement::find_all_valid_placements(&game_state) {
+            let breakdown = advanced_score_with_breakdown(&placement, &game_state, &maps, &weights);
+
+            assert_eq!(breakdown.total, advanced_score(&placement, &game_state, &weights).value());
+            assert_eq!(breakdown.expansion, placement.cells_added as f32 * weights.expansion);
+        }
+    }
+
+    #[test]
+    fn test_bottleneck_score() {
+        // Same corridor as `test_chokepoint_score`: (2, 0) and (3, 0) each
+        // cut off one end cell
+        let game_state = GameState::builder_from_size(5, 2)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &(0..5).map(|x| Position::new(x, 1)).collect::<Vec<_>>())
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let bar = |x| Placement {
+            position: Position::new(x, 0),
+            shape: game_state.current_piece.clone(),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        assert_eq!(bottleneck_score(&bar(0), &game_state), 0.0);
+        assert_eq!(bottleneck_score(&bar(1), &game_state), 1.0);
+        assert_eq!(bottleneck_score(&bar(2), &game_state), 2.0);
+        assert_eq!(bottleneck_score(&bar(3), &game_state), 1.0);
+
+        // The bottleneck weight scales exactly this term of advanced_score
+        let without = HeuristicWeights { bottleneck: 0.0, ..HeuristicWeights::default() };
+        let gain = advanced_score(&bar(2), &game_state, &HeuristicWeights::default()).value()
+            - advanced_score(&bar(2), &game_state, &without).value();
+        assert!((gain - 2.0 * HeuristicWeights::default().bottleneck).abs() < 1e-4);
+        let maps = ScoreMaps::new(&game_state);
+        let breakdown = advanced_score_with_breakdown(&bar(2), &game_state, &maps, &HeuristicWeights::default());
+        assert_eq!(breakdown.bottleneck, 2.0 * HeuristicWeights::default().bottleneck);
+    }
+
+    #[test]
+    fn test_chokepoint_score() {
+        // Corridor along row 0 with our cell at the left end
+        let game_state = GameState::builder_from_size(5, 2)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &(0..5).map(|x| Position::new(x, 1)).collect::<Vec<_>>())
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let bar = |x| Placement {
+            position: Position::new(x, 0),
+            shape: game_state.current_piece.clone(),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        // The corridor is empty from (1, 0) to (4, 0); only (2, 0) and (3, 0) split it
+        assert_eq!(chokepoint_score(&bar(0), &game_state), 0.0);
+        assert_eq!(chokepoint_score(&bar(2), &game_state), 2.0);
+        assert_eq!(chokepoint_score(&bar(3), &game_state), 1.0);
+    }
+
+    #[test]
+    fn test_opponent_path_length_routes_around_territory() {
+        let grid: Grid = "\
+.@...
+@@@..
+..$.."
+            .parse()
+            .unwrap();
+        let dot = Placement {
+            position: Position::new(1, 0),
+            shape: Shape::from_chars(1, 1, vec![vec!['#']]),
+            cells_added: 0,
+            territory_touches: 1,
+        };
+
+        // Manhattan distance to (2, 2) is 3, but our own row is in the way
+        assert_eq!(opponent_path_length(&dot, &grid, 2), Some(5));
+        assert_eq!(opponent_path_length(&dot, &grid, 3), None);
+
+        // The flood fill adds the path term; a third player's cell walls
+        // off the same cells without offering a route
+        let no_opponent: Grid = ".@...\n@@@..\n..&..".parse().unwrap();
+        let path_term = analyze_flood_fill(&dot, &grid, 2) - analyze_flood_fill(&dot, &no_opponent, 2);
+        assert!((path_term - FLOOD_FILL_PATH_WEIGHT / 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_flood_fill_path_term_as_player_two() {
+        // Player 2 moves; player 1's territory is three steps away
+        let game_state = GameState::builder_from_size(6, 1)
+            .with_player(2)
+            .with_territory(2, &[Position::new(0, 0)])
+            .with_territory(1, &[Position::new(5, 0)])
+            .build();
+        let domino = Placement {
+            position: Position::new(0, 0),
+            shape: Shape::from_chars(2, 1, vec![vec!['#', '#']]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        let simulated = domino.simulate(game_state.grid(), 2);
+
+        assert_eq!(opponent_path_length(&domino, &simulated, 1), Some(4));
+        let reach = 3.0 * 2.5;
+        let path_term = analyze_flood_fill(&domino, &simulated, 1) - reach;
+        assert!((path_term - FLOOD_FILL_PATH_WEIGHT / 5.0).abs() < 1e-4);
     }
 }
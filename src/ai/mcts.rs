@@ -0,0 +1,249 @@
+/// Monte Carlo Tree Search
+///
+/// Grows a game tree one node per iteration: UCB1 selection down to a
+/// leaf, expansion into one child per valid placement, a greedy rollout
+/// with random pieces from the new node, and backpropagation of the root
+/// player's final territory share. The move with the most visits wins.
+
+use crate::game_state::{GameState, Grid};
+use crate::placement::{valid_placements_iter, Placement};
+use crate::sim::PieceGenerator;
+use super::territory::rollout;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// UCB1 exploration constant (sqrt 2, the textbook value for [0, 1] rewards)
+pub const MCTS_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// One position in the search tree
+#[derive(Debug, Clone)]
+pub struct MctsNode {
+    /// Position after `placement`, with the next player to move
+    pub state: GameState,
+    /// Move that led here from the parent, `None` at the root
+    pub placement: Option<Placement>,
+    pub visits: u32,
+    /// Sum of the root player's territory share over all rollouts through here
+    pub total_score: f64,
+    /// Indices of the children in `MctsTree::nodes`
+    pub children: Vec<usize>,
+    /// Index of the parent in `MctsTree::nodes`, `None` at the root
+    pub parent: Option<usize>,
+    /// Whether the children have been generated
+    pub expanded: bool,
+}
+
+impl MctsNode {
+    fn new(state: GameState, placement: Option<Placement>, parent: Option<usize>) -> Self {
+        MctsNode {
+            state,
+            placement,
+            visits: 0,
+            total_score: 0.0,
+            children: Vec::new(),
+            parent,
+            expanded: false,
+        }
+    }
+
+    /// Mean rollout score for the root player, 0 if never visited
+    pub fn mean_score(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.total_score / self.visits as f64
+        }
+    }
+}
+
+/// Search tree stored as a flat arena, root at index 0
+#[derive(Debug)]
+pub struct MctsTree {
+    pub nodes: Vec<MctsNode>,
+    root_player: u8,
+    /// Seeds the piece sequence of each rollout
+    rng: StdRng,
+}
+
+impl MctsTree {
+    /// Create a tree whose root children are `placements`
+    ///
+    /// Rollout pieces are seeded from the position's Zobrist hash, so the
+    /// search is deterministic for a given position.
+    pub fn new(game_state: &GameState, placements: &[Placement]) -> Self {
+        let mut tree = MctsTree {
+            nodes: vec![MctsNode::new(game_state.clone(), None, None)],
+            root_player: game_state.player_number(),
+            rng: StdRng::seed_from_u64(game_state.zobrist_hash()),
+        };
+        tree.add_children(0, placements.to_vec());
+        tree
+    }
+
+    /// Run `iterations` rounds of select, expand, simulate, backpropagate
+    pub fn run(&mut self, iterations: u32) {
+        for _ in 0..iterations {
+            let leaf = self.selection();
+            let node = self.expansion(leaf);
+            let score = self.simulation(node);
+            self.backpropagation(node, score);
+        }
+    }
+
+    /// Most visited root move; ties go to the first placement
+    pub fn best_move(&self) -> Option<Placement> {
+        let mut best: Option<&MctsNode> = None;
+        for &child in &self.nodes[0].children {
+            let node = &self.nodes[child];
+            if best.is_none_or(|b| node.visits > b.visits) {
+                best = Some(node);
+            }
+        }
+        best.and_then(|node| node.placement.clone())
+    }
+
+    /// Descend by UCB1 until reaching an unvisited or unexpanded node
+    fn selection(&self) -> usize {
+        let mut current = 0;
+        loop {
+            let node = &self.nodes[current];
+            if !node.expanded || node.children.is_empty() {
+                return current;
+            }
+            if let Some(&unvisited) = node.children.iter().find(|&&c| self.nodes[c].visits == 0) {
+                return unvisited;
+            }
+
+            let maximizing = node.state.player_number() == self.root_player;
+            let parent_visits = node.visits.max(1) as f64;
+            let mut best = (node.children[0], f64::NEG_INFINITY);
+            for &child in &node.children {
+                let ucb = self.ucb1(child, parent_visits, maximizing);
+                if ucb > best.1 {
+                    best = (child, ucb);
+                }
+            }
+            current = best.0;
+        }
+    }
+
+    /// UCB1 value of `child` from the point of view of the player choosing it
+    fn ucb1(&self, child: usize, parent_visits: f64, maximizing: bool) -> f64 {
+        let node = &self.nodes[child];
+        let mean = node.mean_score();
+        let exploit = if maximizing { mean } else { 1.0 - mean };
+        exploit + MCTS_EXPLORATION * (parent_visits.ln() / node.visits as f64).sqrt()
+    }
+
+    /// Expand a visited leaf into one child per valid placement
+    ///
+    /// Returns the node to simulate from: the first new child, or the leaf
+    /// itself if it is unvisited or has no moves.
+    fn expansion(&mut self, leaf: usize) -> usize {
+        if self.nodes[leaf].visits == 0 || self.nodes[leaf].expanded {
+            return leaf;
+        }
+
+        let placements: Vec<Placement> = valid_placements_iter(&self.nodes[leaf].state).collect();
+        self.add_children(leaf, placements);
+        self.nodes[leaf].children.first().copied().unwrap_or(leaf)
+    }
+
+    /// Greedy rollout from `node`, returning the root player's territory share
+    fn simulation(&mut self, node: usize) -> f64 {
+        let state = &self.nodes[node].state;
+        let order = [state.player_number(), state.opponent_number()];
+        let mut pieces = PieceGenerator::new(self.rng.next_u64());
+        let end = rollout(state.grid().grid().clone(), order, &mut pieces);
+        territory_share(&end, self.root_player)
+    }
+
+    /// Add `score` to `node` and every ancestor
+    fn backpropagation(&mut self, node: usize, score: f64) {
+        let mut current = Some(node);
+        while let Some(index) = current {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            node.total_score += score;
+            current = node.parent;
+        }
+    }
+
+    fn add_children(&mut self, parent: usize, placements: Vec<Placement>) {
+        for placement in placements {
+            let state = self.nodes[parent].state.apply_placement(&placement);
+            let index = self.nodes.len();
+            self.nodes.push(MctsNode::new(state, Some(placement), Some(parent)));
+            self.nodes[parent].children.push(index);
+        }
+        self.nodes[parent].expanded = true;
+    }
+}
+
+/// `player`'s share of the occupied cells, 0 if none are occupied
+fn territory_share(grid: &Grid, player: u8) -> f64 {
+    let opponent = GameState::opponent_of(player);
+    let mine = grid.count_territory(player) as f64;
+    let total = mine + grid.count_territory(opponent) as f64;
+    if total > 0.0 { mine / total } else { 0.0 }
+}
+
+/// Select a move by running `iterations` rounds of MCTS
+///
+/// With zero iterations every root move is unvisited and the first
+/// placement is returned.
+pub fn mcts_select_move(
+    placements: &[Placement],
+    game_state: &GameState,
+    iterations: u32,
+) -> Option<Placement> {
+    let mut tree = MctsTree::new(game_state, placements);
+    tree.run(iterations);
+    tree.best_move()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{Position, Shape};
+    use crate::placement::find_all_valid_placements;
+
+    fn small_game_state() -> GameState {
+        GameState::builder_from_size(6, 6)
+            .with_territory(1, &[Position::new(1, 1)])
+            .with_territory(2, &[Position::new(4, 4)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build()
+    }
+
+    #[test]
+    fn test_mcts_visits_and_backpropagation() {
+        let game_state = small_game_state();
+        let placements = find_all_valid_placements(&game_state);
+        let mut tree = MctsTree::new(&game_state, &placements);
+
+        tree.run(30);
+
+        let root = &tree.nodes[0];
+        assert_eq!(root.visits, 30);
+        assert_eq!(root.children.len(), placements.len());
+        let child_visits: u32 = root.children.iter().map(|&c| tree.nodes[c].visits).sum();
+        assert_eq!(child_visits, 30);
+        assert!((0.0..=1.0).contains(&root.mean_score()));
+        // Every root move was tried before any was expanded further
+        assert!(root.children.iter().all(|&c| tree.nodes[c].visits > 0));
+    }
+
+    #[test]
+    fn test_mcts_select_move_is_deterministic() {
+        let game_state = small_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        let first = mcts_select_move(&placements, &game_state, 40);
+
+        assert!(first.as_ref().is_some_and(|p| placements.contains(p)));
+        assert_eq!(first, mcts_select_move(&placements, &game_state, 40));
+        assert_eq!(mcts_select_move(&placements, &game_state, 0), placements.first().cloned());
+        assert!(mcts_select_move(&[], &game_state, 10).is_none());
+    }
+}
@@ -10,15 +10,33 @@ pub mod advanced_strategies;
 pub mod optimization;
 pub mod optimized_evaluator;
 pub mod benchmark;
+pub mod territory;
+pub mod opponent_model;
+pub mod score;
+pub mod opening;
+pub mod pattern;
+pub mod search;
+pub mod mcts;
+pub mod weight_tuner;
+pub mod adaptive_strategy;
+pub mod competition;
+pub mod flow;
 
-use crate::game_state::GameState;
-use crate::placement::Placement;
+use crate::game_state::{GamePhase, GameState};
+use crate::placement::{valid_placements_iter, Placement};
+use std::time::{Duration, Instant};
 use evaluator::select_best_placement as evaluator_select;
+use heuristics::{advanced_score_pure, HeuristicWeights, ScoreMaps};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use opening::OpeningBook;
+use pattern::PatternBook;
 use strategies::balanced;
 use advanced_strategies::{
-    aggressive_expansion, opportunistic, defensive, strategic_blocking,
-    advanced_balanced, territorial_control
+    aggressive_expansion, opportunistic, defensive, strategic_blocking_cached,
+    advanced_balanced, territorial_control, territorial_voronoi, threat_aware, EvalWeights
 };
+use optimization::TurnCache;
 
 /// Strategy type enumeration
 /// 
@@ -31,7 +49,7 @@ pub enum AIStrategy {
     Balanced,
     /// Use evaluation heuristics (Phase 1)
     Evaluator,
-    /// Default (Phase 5: AdvancedBalanced)
+    /// Default: picks a strategy by game phase (see `strategy_for_phase`)
     Default,
     /// Aggressive territory expansion (Phase 5)
     AggressiveExpansion,
@@ -45,6 +63,42 @@ pub enum AIStrategy {
     AdvancedBalanced,
     /// Territorial control strategy (Phase 5)
     TerritorialControl,
+    /// Maximize Voronoi territory advantage after the placement
+    TerritorialVoronoi,
+    /// Avoid cells the opponent can easily expand into
+    ThreatAware,
+    /// Softmax sampling over `advanced_score` (see `RANDOM_WEIGHTED_TEMPERATURE`)
+    RandomWeighted,
+    /// Maximize our final territory count by Voronoi gain alone (late game)
+    EndgameMaximizer,
+    /// Minimax lookahead search to the given depth
+    Minimax(u8),
+    /// Monte Carlo Tree Search with the given number of iterations
+    Mcts(u32),
+}
+
+impl AIStrategy {
+    /// Every fixed strategy variant, in declaration order
+    ///
+    /// Parameterised strategies such as `Minimax` and `Mcts` are not included.
+    pub fn all() -> [AIStrategy; 14] {
+        [
+            AIStrategy::GreedyExpansion,
+            AIStrategy::Balanced,
+            AIStrategy::Evaluator,
+            AIStrategy::Default,
+            AIStrategy::AggressiveExpansion,
+            AIStrategy::Opportunistic,
+            AIStrategy::Defensive,
+            AIStrategy::StrategicBlocking,
+            AIStrategy::AdvancedBalanced,
+            AIStrategy::TerritorialControl,
+            AIStrategy::TerritorialVoronoi,
+            AIStrategy::ThreatAware,
+            AIStrategy::RandomWeighted,
+            AIStrategy::EndgameMaximizer,
+        ]
+    }
 }
 
 impl Default for AIStrategy {
@@ -61,49 +115,324 @@ pub fn select_move(
     placements: &[Placement],
     game_state: &GameState,
     strategy: AIStrategy,
+) -> Option<Placement> {
+    select_move_with_weights(placements, game_state, strategy, &EvalWeights::default())
+}
+
+/// Move selection with explicit scoring weights
+/// 
+/// `weights` is passed to every strategy that accepts them; the Phase 1
+/// strategies ignore it. Projections are cached for this call only; use
+/// `select_move_with_cache` to share them across a turn.
+pub fn select_move_with_weights(
+    placements: &[Placement],
+    game_state: &GameState,
+    strategy: AIStrategy,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    select_move_with_cache(placements, game_state, strategy, weights, &mut TurnCache::new())
+}
+
+/// `select_move_with_weights` reusing the growth projections in `cache`
+///
+/// `cache` must only hold projections for `game_state`: keep one per turn
+/// and clear it before the next.
+pub fn select_move_with_cache(
+    placements: &[Placement],
+    game_state: &GameState,
+    strategy: AIStrategy,
+    weights: &EvalWeights,
+    cache: &mut TurnCache,
 ) -> Option<Placement> {
     match strategy {
         // Phase 1 strategies
-        AIStrategy::GreedyExpansion => strategies::greedy_expansion(placements),
+        AIStrategy::GreedyExpansion => strategies::greedy_expansion(placements, game_state, None),
         AIStrategy::Balanced => balanced(placements),
         AIStrategy::Evaluator => evaluator_select(placements, game_state),
         // Phase 5 strategies
-        AIStrategy::AggressiveExpansion => aggressive_expansion(placements, game_state),
-        AIStrategy::Opportunistic => opportunistic(placements, game_state),
-        AIStrategy::Defensive => defensive(placements, game_state),
-        AIStrategy::StrategicBlocking => strategic_blocking(placements, game_state),
-        AIStrategy::AdvancedBalanced => advanced_balanced(placements, game_state),
-        AIStrategy::TerritorialControl => territorial_control(placements, game_state),
-        // Default is now AdvancedBalanced
-        AIStrategy::Default => advanced_balanced(placements, game_state),
+        AIStrategy::AggressiveExpansion => aggressive_expansion(placements, game_state, weights),
+        AIStrategy::Opportunistic => opportunistic(placements, game_state, weights),
+        AIStrategy::Defensive => defensive(placements, game_state, weights),
+        AIStrategy::StrategicBlocking => strategic_blocking_cached(placements, game_state, weights, cache),
+        AIStrategy::AdvancedBalanced => advanced_balanced(placements, game_state, weights),
+        AIStrategy::TerritorialControl => territorial_control(placements, game_state, weights),
+        AIStrategy::TerritorialVoronoi => territorial_voronoi(placements, game_state),
+        AIStrategy::ThreatAware => threat_aware(placements, game_state, weights),
+        AIStrategy::RandomWeighted => select_move_random_weighted(placements, game_state),
+        AIStrategy::EndgameMaximizer => strategies::endgame_maximizer(placements, game_state),
+        AIStrategy::Minimax(depth) => select_move_minimax(placements, game_state, depth),
+        AIStrategy::Mcts(iterations) => mcts::mcts_select_move(placements, game_state, iterations),
+        // Default adapts to the game phase
+        AIStrategy::Default => {
+            let strategy = strategy_for_state(game_state);
+            if strategy == AIStrategy::TerritorialControl {
+                let open = late_game_candidates(placements, game_state);
+                let candidates = if open.is_empty() { placements } else { &open };
+                select_move_with_cache(candidates, game_state, strategy, weights, cache)
+            } else {
+                select_move_with_cache(placements, game_state, strategy, weights, cache)
+            }
+        }
+    }
+}
+
+/// Placements `AIStrategy::Default` considers in the late game
+///
+/// Drops dead ends: placements whose cells border no empty cell once
+/// placed (`PlacementQuality::growth_room` of 0) claim their cells but
+/// leave nothing to grow into. Empty if every placement is a dead end.
+pub fn late_game_candidates(placements: &[Placement], game_state: &GameState) -> Vec<Placement> {
+    placements
+        .iter()
+        .filter(|p| p.quality(game_state).growth_room > 0)
+        .cloned()
+        .collect()
+}
+
+/// Divisor applied to `advanced_score` before softmax sampling
+///
+/// One extra claimed cell is worth 10 points under the default weights,
+/// so placements a cell apart are picked at odds of about e : 1.
+pub const RANDOM_WEIGHTED_TEMPERATURE: f32 = 10.0;
+
+/// Softmax sampling over `advanced_score` with default weights
+///
+/// The RNG is seeded from the position's Zobrist hash, so the choice varies
+/// between positions but replays and seeded simulations stay reproducible.
+pub fn select_move_random_weighted(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+    let weights = HeuristicWeights::default();
+    let maps = ScoreMaps::new(game_state);
+    let mut rng = StdRng::seed_from_u64(game_state.zobrist_hash());
+    strategies::random_weighted(
+        placements,
+        |p| {
+            let simulated = p.simulate(game_state.grid(), game_state.player_number());
+            advanced_score_pure(p, &simulated, game_state, &maps, &weights).value() / RANDOM_WEIGHTED_TEMPERATURE
+        },
+        &mut rng,
+    )
+}
+
+/// Share of the board, in percent, that must still be empty before
+/// `AIStrategy::Default` switches to `EndgameMaximizer`
+pub const ENDGAME_EMPTY_PERCENT: usize = 20;
+
+/// Strategy used by `AIStrategy::Default` in each game phase
+///
+/// The late game ends with `EndgameMaximizer`; see `strategy_for_state`.
+pub fn strategy_for_phase(phase: GamePhase) -> AIStrategy {
+    match phase {
+        GamePhase::Early => AIStrategy::AggressiveExpansion,
+        GamePhase::Mid => AIStrategy::AdvancedBalanced,
+        GamePhase::Late => AIStrategy::TerritorialControl,
+    }
+}
+
+/// Strategy used by `AIStrategy::Default` on `game_state`
+///
+/// `EndgameMaximizer` once fewer than `ENDGAME_EMPTY_PERCENT` percent of
+/// the cells are empty, otherwise the phase strategy.
+pub fn strategy_for_state(game_state: &GameState) -> AIStrategy {
+    let area = game_state.grid().width * game_state.grid().height;
+    if game_state.grid().empty_count() * 100 < ENDGAME_EMPTY_PERCENT * area {
+        AIStrategy::EndgameMaximizer
+    } else {
+        strategy_for_phase(game_state.current_phase())
     }
 }
 
-/// Select move using default strategy (Evaluator)
+/// Minimax move selection with alpha-beta pruning
+/// 
+/// Searches `depth` plies (depth 2 = my move and the opponent's best reply)
+/// using `GameState::apply_placement` to build successor states. The
+/// opponent's next piece is unknown, so every ply assumes the current
+/// piece. Leaves are scored by territory difference for the player to move
+/// at the root. Ties go to the first placement.
+pub fn select_move_minimax(
+    placements: &[Placement],
+    game_state: &GameState,
+    depth: u8,
+) -> Option<Placement> {
+    minimax_search(placements, game_state, depth, None).unwrap_or(None)
+}
+
+/// Anytime move selection within a wall-clock budget
+/// 
+/// Computes a greedy answer first, then runs progressively more expensive
+/// searches (evaluator, advanced balanced, minimax depth 1 and 2), keeping
+/// the result of the last one that finished in time. Minimax checks the
+/// deadline at every node and is abandoned once it passes; the other
+/// stages are only started while budget remains.
+pub fn select_move_timed(
+    placements: &[Placement],
+    game_state: &GameState,
+    budget: Duration,
+) -> Option<Placement> {
+    let deadline = Instant::now() + budget;
+    let mut best = strategies::greedy_expansion(placements, game_state, None);
+
+    let stages = [
+        AIStrategy::Evaluator,
+        AIStrategy::AdvancedBalanced,
+        AIStrategy::Minimax(1),
+        AIStrategy::Minimax(2),
+    ];
+    for stage in stages {
+        if Instant::now() >= deadline {
+            break;
+        }
+        let result = match stage {
+            AIStrategy::Minimax(depth) => {
+                match minimax_search(placements, game_state, depth, Some(deadline)) {
+                    Ok(result) => result,
+                    Err(SearchTimeout) => break,
+                }
+            }
+            _ => select_move(placements, game_state, stage),
+        };
+        if Instant::now() > deadline {
+            break;
+        }
+        if result.is_some() {
+            best = result;
+        }
+    }
+
+    best
+}
+
+/// Marker for a search abandoned at its deadline
+struct SearchTimeout;
+
+/// Root of the minimax search, giving up at `deadline` if one is set
+fn minimax_search(
+    placements: &[Placement],
+    game_state: &GameState,
+    depth: u8,
+    deadline: Option<Instant>,
+) -> Result<Option<Placement>, SearchTimeout> {
+    let root_player = game_state.player_number();
+    let mut best: Option<(&Placement, i64)> = None;
+    let mut alpha = i64::MIN;
+
+    for placement in placements {
+        let child = game_state.apply_placement(placement);
+        let score = minimax_value(&child, depth.saturating_sub(1), alpha, i64::MAX, root_player, deadline)?;
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((placement, score));
+            alpha = alpha.max(score);
+        }
+    }
+
+    Ok(best.map(|(placement, _)| placement.clone()))
+}
+
+/// Value of `state` for `root_player`, searched `depth` more plies
+fn minimax_value(
+    state: &GameState,
+    depth: u8,
+    mut alpha: i64,
+    mut beta: i64,
+    root_player: u8,
+    deadline: Option<Instant>,
+) -> Result<i64, SearchTimeout> {
+    if deadline.is_some_and(|d| Instant::now() >= d) {
+        return Err(SearchTimeout);
+    }
+    if depth == 0 {
+        return Ok(territory_difference(state, root_player));
+    }
+
+    // Placements are generated lazily so a cutoff skips validating the rest
+    let maximizing = state.player_number() == root_player;
+    let mut value = if maximizing { i64::MIN } else { i64::MAX };
+    let mut any_move = false;
+    for placement in valid_placements_iter(state) {
+        any_move = true;
+        let child = state.apply_placement(&placement);
+        let score = minimax_value(&child, depth - 1, alpha, beta, root_player, deadline)?;
+        if maximizing {
+            value = value.max(score);
+            alpha = alpha.max(value);
+        } else {
+            value = value.min(score);
+            beta = beta.min(value);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    if !any_move {
+        return Ok(territory_difference(state, root_player));
+    }
+    Ok(value)
+}
+
+/// Root player's territory minus the opponent's
+fn territory_difference(state: &GameState, root_player: u8) -> i64 {
+    let opponent = GameState::opponent_of(root_player);
+    state.grid().count_territory(root_player) as i64 - state.grid().count_territory(opponent) as i64
+}
+
+/// Select move using the default, phase-adaptive strategy
+///
+/// Book moves from `OpeningBook` take priority during the opening; after
+/// that, shapes with a `PatternBook` entry use its preference instead of
+/// the phase strategy until the late game, where territory counting takes
+/// over.
 pub fn select_move_default(
     placements: &[Placement],
     game_state: &GameState,
 ) -> Option<Placement> {
-    select_move(placements, game_state, AIStrategy::Default)
+    select_move_default_with_weights(placements, game_state, &EvalWeights::default())
+}
+
+/// `select_move_default` with explicit scoring weights for the phase strategy
+pub fn select_move_default_with_weights(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+) -> Option<Placement> {
+    select_move_default_with_cache(placements, game_state, weights, &mut TurnCache::new())
+}
+
+/// `select_move_default_with_weights` reusing the projections in `cache`
+///
+/// See `select_move_with_cache`.
+pub fn select_move_default_with_cache(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &EvalWeights,
+    cache: &mut TurnCache,
+) -> Option<Placement> {
+    if let Some(book_move) = OpeningBook::default().lookup(game_state) {
+        return Some(book_move);
+    }
+    if game_state.current_phase() != GamePhase::Late
+        && let Some(select) = PatternBook::default().lookup(&game_state.current_piece)
+    {
+        return select(placements, game_state);
+    }
+
+    select_move_with_cache(placements, game_state, AIStrategy::Default, weights, cache)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game_state::{Grid, Shape, Position};
+    use crate::game_state::{Shape, Position};
+    use crate::placement::find_all_valid_placements;
 
     fn create_test_game_state() -> GameState {
-        let grid = Grid::from_chars(
-            10, 10,
-            (0..10).map(|_| (0..10).map(|_| '.').collect()).collect(),
-        );
-        
         let shape = Shape::from_chars(
             2, 2,
             vec![vec!['.', '#'], vec!['#', '.']],
         );
         
-        GameState::new(1, grid, shape)
+        GameState::builder_from_size(10, 10)
+            .with_piece(shape)
+            .build()
     }
 
     fn create_placements() -> Vec<Placement> {
@@ -219,6 +548,21 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn test_select_move_with_cache_shares_turn_projections() {
+        let placements = create_placements();
+        let game_state = create_test_game_state();
+        let weights = EvalWeights::default();
+        let mut cache = TurnCache::new();
+
+        let first = select_move_with_cache(&placements, &game_state, AIStrategy::StrategicBlocking, &weights, &mut cache);
+        assert_eq!(cache.stats().entries, 2);
+        let second = select_move_with_cache(&placements, &game_state, AIStrategy::StrategicBlocking, &weights, &mut cache);
+        assert_eq!(cache.stats().entries, 2);
+        assert_eq!(first, second);
+        assert_eq!(first, select_move(&placements, &game_state, AIStrategy::StrategicBlocking));
+    }
+
     #[test]
     fn test_select_move_advanced_balanced() {
         let placements = create_placements();
@@ -249,4 +593,213 @@ mod tests {
         assert!(result.is_some());
         // Default is now AdvancedBalanced instead of Evaluator
     }
+
+    #[test]
+    fn test_select_move_with_default_weights_matches_select_move() {
+        let placements = create_placements();
+        let game_state = create_test_game_state();
+        
+        for strategy in AIStrategy::all() {
+            assert_eq!(
+                select_move_with_weights(&placements, &game_state, strategy, &EvalWeights::default()),
+                select_move(&placements, &game_state, strategy),
+            );
+        }
+    }
+
+    #[test]
+    fn test_select_move_with_weights_changes_selection() {
+        let game_state = create_test_game_state();
+        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let placements = vec![
+            Placement {
+                position: Position { x: 5, y: 5 },
+                shape: shape.clone(),
+                cells_added: 1,
+                territory_touches: 3,
+            },
+            Placement {
+                position: Position { x: 5, y: 6 },
+                shape,
+                cells_added: 3,
+                territory_touches: 1,
+            },
+        ];
+        
+        let default_pick = select_move(&placements, &game_state, AIStrategy::Defensive);
+        assert_eq!(default_pick.unwrap().territory_touches, 3);
+        
+        // Penalize territory touches instead of rewarding them
+        let weights = EvalWeights {
+            defensive_touches: -1.0,
+            ..EvalWeights::default()
+        };
+        let weighted_pick =
+            select_move_with_weights(&placements, &game_state, AIStrategy::Defensive, &weights);
+        assert_eq!(weighted_pick.unwrap().cells_added, 3);
+    }
+
+    /// Small board with a two-cell piece for minimax searches
+    fn create_minimax_game_state() -> GameState {
+        GameState::builder_from_size(5, 3)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &[Position::new(4, 2)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build()
+    }
+
+    #[test]
+    fn test_select_move_minimax_returns_valid_placement() {
+        let game_state = create_minimax_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        for depth in 1..=2 {
+            let result = select_move_minimax(&placements, &game_state, depth).unwrap();
+            assert!(placements.contains(&result));
+        }
+    }
+
+    #[test]
+    fn test_select_move_minimax_empty() {
+        let game_state = create_test_game_state();
+        assert!(select_move_minimax(&[], &game_state, 2).is_none());
+    }
+
+    #[test]
+    fn test_minimax_strategy_dispatch() {
+        let game_state = create_minimax_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        let via_strategy = select_move(&placements, &game_state, AIStrategy::Minimax(2));
+        let direct = select_move_minimax(&placements, &game_state, 2);
+
+        assert_eq!(via_strategy, direct);
+    }
+
+    #[test]
+    fn test_minimax_value_matches_territory_at_depth_zero() {
+        let game_state = create_minimax_game_state();
+        assert_eq!(minimax_value(&game_state, 0, i64::MIN, i64::MAX, 1, None).ok(), Some(0));
+    }
+
+    #[test]
+    fn test_strategy_for_phase() {
+        assert_eq!(strategy_for_phase(GamePhase::Early), AIStrategy::AggressiveExpansion);
+        assert_eq!(strategy_for_phase(GamePhase::Mid), AIStrategy::AdvancedBalanced);
+        assert_eq!(strategy_for_phase(GamePhase::Late), AIStrategy::TerritorialControl);
+    }
+
+    #[test]
+    fn test_strategy_for_state_switches_to_endgame() {
+        // 10 cells: 8 occupied is late with 20% still empty, 9 leaves less
+        let state = |occupied: usize| {
+            let cells: Vec<Position> = (0..occupied).map(|x| Position::new(x, 0)).collect();
+            GameState::builder_from_size(10, 1).with_territory(1, &cells).build()
+        };
+
+        assert_eq!(strategy_for_state(&state(2)), AIStrategy::AggressiveExpansion);
+        assert_eq!(state(8).current_phase(), GamePhase::Late);
+        assert_eq!(strategy_for_state(&state(8)), AIStrategy::TerritorialControl);
+        assert_eq!(strategy_for_state(&state(9)), AIStrategy::EndgameMaximizer);
+    }
+
+    #[test]
+    fn test_default_late_game_skips_dead_ends() {
+        // 75% occupied: late game, but not yet the endgame
+        let grid: crate::game_state::Grid = "@..$.@\n@$$$$@".parse().unwrap();
+        let game_state = GameState::new(1, grid, Shape::from_chars(2, 1, vec![vec!['#', '#']]));
+        let placements = find_all_valid_placements(&game_state);
+        assert_eq!(strategy_for_state(&game_state), AIStrategy::TerritorialControl);
+
+        // The domino at (4, 0) ends between opponent cells
+        let positions: Vec<Position> = placements.iter().map(|p| p.position).collect();
+        assert_eq!(positions, vec![Position::new(0, 0), Position::new(4, 0)]);
+        let open = late_game_candidates(&placements, &game_state);
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].position, Position::new(0, 0));
+
+        let chosen = select_move(&placements, &game_state, AIStrategy::Default).unwrap();
+        assert_eq!(chosen.position, Position::new(0, 0));
+        // With only dead ends left, they are still played
+        assert!(select_move(&placements[1..], &game_state, AIStrategy::Default).is_some());
+    }
+
+    #[test]
+    fn test_select_move_default_dispatches_by_phase() {
+        let placements = create_placements();
+        let game_state = create_test_game_state();
+        assert_eq!(game_state.current_phase(), GamePhase::Early);
+
+        assert_eq!(
+            select_move_default(&placements, &game_state),
+            select_move(&placements, &game_state, AIStrategy::AggressiveExpansion)
+        );
+    }
+
+    #[test]
+    fn test_select_move_timed_zero_budget_is_greedy() {
+        let placements = create_placements();
+        let game_state = create_test_game_state();
+
+        let result = select_move_timed(&placements, &game_state, Duration::ZERO);
+
+        assert_eq!(result, strategies::greedy_expansion(&placements, &game_state, None));
+    }
+
+    #[test]
+    fn test_select_move_timed_generous_budget_reaches_minimax() {
+        let game_state = create_minimax_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        let result = select_move_timed(&placements, &game_state, Duration::from_secs(10));
+
+        assert_eq!(result, select_move_minimax(&placements, &game_state, 2));
+    }
+
+    #[test]
+    fn test_select_move_timed_empty() {
+        let game_state = create_test_game_state();
+        assert!(select_move_timed(&[], &game_state, Duration::from_millis(10)).is_none());
+    }
+
+    #[test]
+    fn test_minimax_search_times_out() {
+        let game_state = create_minimax_game_state();
+        let placements = find_all_valid_placements(&game_state);
+        let past = Instant::now();
+
+        assert!(minimax_search(&placements, &game_state, 2, Some(past)).is_err());
+    }
+
+    #[test]
+    fn test_pattern_book_does_not_lower_win_rate() {
+        // The default selector against itself without the pattern book,
+        // each side taking both seats
+        let without_patterns = |placements: &[Placement], game_state: &GameState| {
+            OpeningBook::default()
+                .lookup(game_state)
+                .or_else(|| select_move(placements, game_state, AIStrategy::Default))
+        };
+
+        let mut points = 0.0;
+        for seed in 0..2 {
+            for patterns_seat in [1, 2] {
+                let mut sim = crate::sim::Simulation::with_board(AIStrategy::Default, AIStrategy::Default, 30, 20, seed);
+                let result = sim.run_with(&mut |player, placements, game_state| {
+                    if player == patterns_seat {
+                        select_move_default(placements, game_state)
+                    } else {
+                        without_patterns(placements, game_state)
+                    }
+                });
+                points += match result.winner {
+                    0 => 0.5,
+                    winner if winner == patterns_seat => 1.0,
+                    _ => 0.0,
+                };
+            }
+        }
+
+        assert!(points >= 2.0, "pattern book scored {} of 4", points);
+    }
 }
@@ -0,0 +1,214 @@
+/// Opening book
+///
+/// Early in the game the board is almost empty and the first few moves set
+/// up which regions each player can claim. The book stores, per board
+/// size, cells the first pieces should cover. Book cells are offsets from
+/// the player's starting cell, normalized so positive offsets point toward
+/// the board center; they are mirrored to match the actual start corner.
+
+use crate::game_state::{GameState, Position};
+use crate::placement::{validate_placement, Placement};
+
+/// Last turn the book is consulted on
+pub const OPENING_MAX_TURN: usize = 5;
+
+/// Territory size above which the game is treated as past the opening
+///
+/// Engine input carries no turn counter (`turn_number` is 0), so the size
+/// of our territory is what actually bounds the book in play.
+pub const OPENING_MAX_CELLS: usize = 16;
+
+/// Preferred opening cells for one board size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningLine {
+    pub width: usize,
+    pub height: usize,
+    /// `(dx, dy)` offsets from the starting cell, toward the center first
+    pub moves: Vec<(isize, isize)>,
+}
+
+/// Opening lines keyed by board size
+#[derive(Debug, Clone)]
+pub struct OpeningBook {
+    lines: Vec<OpeningLine>,
+}
+
+impl OpeningBook {
+    /// Create a book from explicit lines
+    pub fn new(lines: Vec<OpeningLine>) -> Self {
+        OpeningBook { lines }
+    }
+
+    /// Line for a `width` x `height` board, if the book has one
+    pub fn line_for(&self, width: usize, height: usize) -> Option<&OpeningLine> {
+        self.lines
+            .iter()
+            .find(|line| line.width == width && line.height == height)
+    }
+
+    /// First valid book placement for the current position
+    ///
+    /// Returns `None` outside the opening, on boards without a line, or if
+    /// no book cell can be covered by a valid placement of the current
+    /// piece.
+    pub fn lookup(&self, game_state: &GameState) -> Option<Placement> {
+        if !is_opening(game_state) {
+            return None;
+        }
+
+        let line = self.line_for(game_state.grid().width, game_state.grid().height)?;
+        let start = starting_cell(game_state)?;
+
+        // Mirror offsets so that positive always points toward the center
+        let sign_x = if start.x * 2 < game_state.grid().width { 1 } else { -1 };
+        let sign_y = if start.y * 2 < game_state.grid().height { 1 } else { -1 };
+
+        line.moves.iter().find_map(|&(dx, dy)| {
+            let target = offset(start, dx * sign_x, dy * sign_y)?;
+            placement_covering(game_state, target)
+        })
+    }
+}
+
+impl Default for OpeningBook {
+    /// Lines for the standard filler maps (20x15, 40x30 and 100x99)
+    fn default() -> Self {
+        OpeningBook::new(vec![
+            OpeningLine {
+                width: 20,
+                height: 15,
+                moves: vec![(2, 2), (3, 1), (1, 3), (1, 1)],
+            },
+            OpeningLine {
+                width: 40,
+                height: 30,
+                moves: vec![(3, 3), (4, 2), (2, 4), (2, 2), (1, 1)],
+            },
+            OpeningLine {
+                width: 100,
+                height: 99,
+                moves: vec![(4, 4), (5, 3), (3, 5), (3, 3), (2, 2), (1, 1)],
+            },
+        ])
+    }
+}
+
+/// Whether the game is still early enough to follow the book
+fn is_opening(game_state: &GameState) -> bool {
+    game_state.turn_number <= OPENING_MAX_TURN
+        && game_state.my_territory().size() <= OPENING_MAX_CELLS
+}
+
+/// Owned cell closest to the territory centroid
+///
+/// During the opening the territory is a small blob around the cell the
+/// player started on, so this recovers the start without move history.
+fn starting_cell(game_state: &GameState) -> Option<Position> {
+    let territory = game_state.my_territory();
+    let (cx, cy) = territory.centroid;
+
+    territory.cells.iter().copied().min_by(|a, b| {
+        let da = (a.x as f32 - cx).powi(2) + (a.y as f32 - cy).powi(2);
+        let db = (b.x as f32 - cx).powi(2) + (b.y as f32 - cy).powi(2);
+        da.total_cmp(&db)
+    })
+}
+
+/// `pos` shifted by `(dx, dy)`, or `None` if that leaves the first quadrant
+fn offset(pos: Position, dx: isize, dy: isize) -> Option<Position> {
+    Some(Position::new(
+        pos.x.checked_add_signed(dx)?,
+        pos.y.checked_add_signed(dy)?,
+    ))
+}
+
+/// First valid placement of the current piece that covers `target`
+fn placement_covering(game_state: &GameState, target: Position) -> Option<Placement> {
+    let shape = &game_state.current_piece;
+
+    (0..shape.height)
+        .flat_map(|y| (0..shape.width).map(move |x| (x, y)))
+        .filter(|&(x, y)| shape.cells[y][x])
+        .find_map(|(x, y)| {
+            let position = Position::new(target.x.checked_sub(x)?, target.y.checked_sub(y)?);
+            validate_placement(game_state, position).ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{CellState, Shape};
+
+    fn square() -> Shape {
+        Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '#']])
+    }
+
+    fn covers(placement: &Placement, target: Position) -> bool {
+        let shape = &placement.shape;
+        (0..shape.height).any(|y| {
+            (0..shape.width).any(|x| {
+                shape.cells[y][x]
+                    && placement.position.x + x == target.x
+                    && placement.position.y + y == target.y
+            })
+        })
+    }
+
+    #[test]
+    fn test_lookup_mirrors_toward_center() {
+        let book = OpeningBook::default();
+        let top_left = GameState::builder_from_size(20, 15)
+            .with_territory(1, &[Position::new(3, 3)])
+            .with_piece(square())
+            .build();
+        let bottom_right = GameState::builder_from_size(20, 15)
+            .with_territory(1, &[Position::new(16, 11)])
+            .with_piece(square())
+            .build();
+
+        // (2, 2), (3, 1) and (1, 3) are out of reach of a 2x2 piece, so the
+        // book falls through to (1, 1)
+        let placement = book.lookup(&top_left).unwrap();
+        assert!(covers(&placement, Position::new(4, 4)));
+        assert!(covers(&placement, Position::new(3, 3)));
+
+        let placement = book.lookup(&bottom_right).unwrap();
+        assert!(covers(&placement, Position::new(15, 10)));
+    }
+
+    #[test]
+    fn test_lookup_outside_opening() {
+        let book = OpeningBook::default();
+        let unknown_size = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(3, 3)])
+            .with_piece(square())
+            .build();
+        let mut late = GameState::builder_from_size(20, 15)
+            .with_territory(1, &[Position::new(3, 3)])
+            .with_piece(square())
+            .build();
+        late.turn_number = OPENING_MAX_TURN + 1;
+
+        assert!(book.lookup(&unknown_size).is_none());
+        assert!(book.lookup(&late).is_none());
+    }
+
+    #[test]
+    fn test_lookup_validates_book_moves() {
+        let book = OpeningBook::new(vec![OpeningLine {
+            width: 20,
+            height: 15,
+            moves: vec![(1, 1)],
+        }]);
+        let mut game_state = GameState::builder_from_size(20, 15)
+            .with_territory(1, &[Position::new(3, 3)])
+            .with_piece(square())
+            .build();
+        assert!(book.lookup(&game_state).is_some());
+
+        // The only placement covering (4, 4) and touching (3, 3) now collides
+        game_state.set_cell(Position::new(4, 3), CellState::Player2);
+        assert!(book.lookup(&game_state).is_none());
+    }
+}
@@ -0,0 +1,265 @@
+/// Opponent modeling
+///
+/// Watches successive game states to work out which piece the opponent
+/// placed, guesses which `AIStrategy` would have made that move, and uses
+/// the accumulated guesses to predict the opponent's next move.
+
+use super::{select_move, AIStrategy};
+use crate::game_state::{CellState, GameState, Grid, Position, Shape};
+use crate::placement::{find_all_valid_placements, Placement};
+
+/// Recorded turns without opponent growth after which it is taken to be
+/// out of moves (two of its turns when both sides are recorded)
+pub const OPPONENT_OUT_WINDOW: usize = 4;
+
+/// Confidence-weighted estimate of the opponent's strategy
+#[derive(Debug, Clone)]
+pub struct OpponentModel {
+    /// Accumulated weight per strategy, in `AIStrategy::all()` order
+    histogram: Vec<(AIStrategy, f64)>,
+    /// Number of opponent moves that matched at least one strategy
+    observations: usize,
+}
+
+impl OpponentModel {
+    /// Create a model with no observations
+    pub fn new() -> Self {
+        OpponentModel {
+            histogram: AIStrategy::all().iter().map(|&s| (s, 0.0)).collect(),
+            observations: 0,
+        }
+    }
+
+    /// Number of opponent moves that matched at least one strategy
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+
+    /// Accumulated weight of each strategy
+    pub fn histogram(&self) -> &[(AIStrategy, f64)] {
+        &self.histogram
+    }
+
+    /// Update the model from two of our successive turns
+    ///
+    /// `before` is the state we moved from and `after` the next state we
+    /// were given, so the board between them holds our move followed by the
+    /// opponent's. Each strategy that reproduces the opponent's move from
+    /// the board it saw gets an equal share of one unit of weight.
+    /// Returns the matching strategies.
+    pub fn observe(&mut self, before: &GameState, after: &GameState) -> Vec<AIStrategy> {
+        let me = before.player_number();
+        let opponent = before.opponent_number();
+        let diff = GameState::diff(before, after);
+        let placed = diff.placed_by(opponent);
+        if placed.is_empty() {
+            return Vec::new();
+        }
+
+        // The board the opponent moved on: ours plus the cells we placed
+        let mut seen = before.grid().grid().clone();
+        for pos in diff.placed_by(me) {
+            seen.set(pos, CellState::player_states(me).map_or(CellState::Empty, |s| s.0));
+        }
+
+        let matching: Vec<AIStrategy> = AIStrategy::all()
+            .into_iter()
+            .filter(|&strategy| {
+                candidate_pieces(&seen, &placed, opponent).into_iter().any(|piece| {
+                    let state = GameState::new(opponent, seen.clone(), piece);
+                    let placements = find_all_valid_placements(&state);
+                    select_move(&placements, &state, strategy)
+                        .is_some_and(|p| new_cells(&p, &seen) == placed)
+                })
+            })
+            .collect();
+
+        if !matching.is_empty() {
+            let share = 1.0 / matching.len() as f64;
+            for (strategy, weight) in &mut self.histogram {
+                if matching.contains(strategy) {
+                    *weight += share;
+                }
+            }
+            self.observations += 1;
+        }
+        matching
+    }
+
+    /// Most likely opponent strategy with its share of the total weight
+    ///
+    /// Returns `None` before any move has been matched.
+    pub fn most_likely_strategy(&self) -> Option<(AIStrategy, f64)> {
+        let total: f64 = self.histogram.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        self.histogram
+            .iter()
+            .fold(None, |best: Option<(AIStrategy, f64)>, &(strategy, weight)| match best {
+                Some((_, best_weight)) if best_weight >= weight => best,
+                _ => Some((strategy, weight)),
+            })
+            .map(|(strategy, weight)| (strategy, weight / total))
+    }
+
+    /// Predict the opponent's next move on `game_state`'s board
+    ///
+    /// The opponent's next piece is unknown, so our current piece stands in
+    /// for it. Uses the most likely strategy, or the default strategy
+    /// before any observations. Returns `None` without searching once the
+    /// history shows the opponent claiming no cells for the last
+    /// `OPPONENT_OUT_WINDOW` turns: a player who cannot place is out.
+    pub fn predict_next_move(&self, game_state: &GameState) -> Option<Placement> {
+        if game_state.history().len() > OPPONENT_OUT_WINDOW
+            && game_state.territory_delta(OPPONENT_OUT_WINDOW).1 == 0
+        {
+            return None;
+        }
+        let strategy = self
+            .most_likely_strategy()
+            .map_or_else(AIStrategy::default, |(strategy, _)| strategy);
+        let state = GameState::new(
+            game_state.opponent_number(),
+            game_state.grid().grid().clone(),
+            game_state.current_piece.clone(),
+        );
+        let placements = find_all_valid_placements(&state);
+        select_move(&placements, &state, strategy)
+    }
+}
+
+impl Default for OpponentModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Empty cells of `grid` covered by `placement`, in row-major order
+fn new_cells(placement: &Placement, grid: &Grid) -> Vec<Position> {
+    let mut cells: Vec<Position> = placement
+        .get_absolute_positions()
+        .into_iter()
+        .filter(|&pos| grid.get(pos) == Some(CellState::Empty))
+        .collect();
+    cells.sort_by_key(|p| (p.y, p.x));
+    cells
+}
+
+/// Pieces that could have produced `placed` on `grid`
+///
+/// Only the newly claimed cells are visible; the one cell that overlapped
+/// the player's territory is not. Each territory cell next to the placed
+/// cells gives one candidate piece.
+fn candidate_pieces(grid: &Grid, placed: &[Position], player: u8) -> Vec<Shape> {
+    let mut overlaps: Vec<Position> = Vec::new();
+    for pos in placed {
+        for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+            let Some(neighbor) = pos.checked_add(dx, dy) else {
+                continue;
+            };
+            if grid.get(neighbor).is_some_and(|c| c.is_player(player)) && !overlaps.contains(&neighbor) {
+                overlaps.push(neighbor);
+            }
+        }
+    }
+
+    overlaps
+        .into_iter()
+        .map(|overlap| {
+            let mut cells = placed.to_vec();
+            cells.push(overlap);
+            let min_x = cells.iter().map(|p| p.x).min().unwrap_or(0);
+            let min_y = cells.iter().map(|p| p.y).min().unwrap_or(0);
+            let width = cells.iter().map(|p| p.x).max().unwrap_or(0) - min_x + 1;
+            let height = cells.iter().map(|p| p.y).max().unwrap_or(0) - min_y + 1;
+
+            let mut raw = vec![vec!['.'; width]; height];
+            for p in cells {
+                raw[p.y - min_y][p.x - min_x] = '*';
+            }
+            Shape::from_chars(width, height, raw)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::TurnHistory;
+
+    /// Player 1 at (1, 2), player 2 at (6, 2) on an 8x5 board, bar piece
+    fn create_opponent_game_state() -> GameState {
+        GameState::builder_from_size(8, 5)
+            .with_territory(1, &[Position::new(1, 2)])
+            .with_territory(2, &[Position::new(6, 2)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build()
+    }
+
+    /// Next state after we play `ours` and the opponent plays its greedy move
+    fn play_round(before: &GameState, ours: &Placement) -> GameState {
+        let mid = before.apply_placement(ours);
+        let reply = select_move(
+            &find_all_valid_placements(&mid),
+            &mid,
+            AIStrategy::GreedyExpansion,
+        )
+        .unwrap();
+        mid.apply_placement(&reply)
+    }
+
+    #[test]
+    fn test_observe_matches_greedy_opponent() {
+        let before = create_opponent_game_state();
+        let ours = find_all_valid_placements(&before).remove(0);
+        let after = play_round(&before, &ours);
+
+        let mut model = OpponentModel::new();
+        let matching = model.observe(&before, &after);
+
+        assert!(matching.contains(&AIStrategy::GreedyExpansion));
+        assert_eq!(model.observations(), 1);
+        let (_, confidence) = model.most_likely_strategy().unwrap();
+        assert!(confidence > 0.0 && confidence <= 1.0);
+    }
+
+    #[test]
+    fn test_observe_without_opponent_move() {
+        let state = create_opponent_game_state();
+        let mut model = OpponentModel::new();
+
+        assert!(model.observe(&state, &state).is_empty());
+        assert_eq!(model.observations(), 0);
+        assert!(model.most_likely_strategy().is_none());
+    }
+
+    #[test]
+    fn test_predict_next_move_is_valid_for_opponent() {
+        let state = create_opponent_game_state();
+        let prediction = OpponentModel::new().predict_next_move(&state).unwrap();
+
+        let opponent_view = GameState::new(2, state.grid().grid().clone(), state.current_piece.clone());
+        assert!(find_all_valid_placements(&opponent_view).contains(&prediction));
+    }
+
+    #[test]
+    fn test_predict_next_move_skips_stuck_opponent() {
+        let state = create_opponent_game_state();
+        let turns = |growth: usize| -> Vec<TurnHistory> {
+            (0..=OPPONENT_OUT_WINDOW)
+                .map(|turn| TurnHistory {
+                    turn: turn as u32,
+                    player: if turn % 2 == 0 { 1 } else { 2 },
+                    territory: [1 + turn, 1 + growth * turn],
+                    placement: None,
+                    cells_gained: 0,
+                })
+                .collect()
+        };
+
+        let model = OpponentModel::new();
+        assert!(model.predict_next_move(&state.clone().with_history(turns(1))).is_some());
+        assert!(model.predict_next_move(&state.with_history(turns(0))).is_none());
+    }
+}
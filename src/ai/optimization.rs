@@ -3,17 +3,27 @@
 /// This module provides caching and optimization strategies to reduce
 /// redundant calculations during placement evaluation.
 
-use crate::game_state::{Grid, Position, GameState};
-use crate::placement::Placement;
-use std::collections::HashMap;
+use crate::game_state::{Grid, Position, GameState, Shape};
+use crate::placement::{Placement, PlacementOrdering};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::sync::Mutex;
+use super::evaluator::evaluate_placement;
+use super::heuristics::{HeuristicWeights, WeaknessMap};
+use super::score::Score;
 
 /// Cache for flood-fill reachability analysis results
 /// 
-/// Stores the count of reachable empty cells from each analyzed position,
-/// avoiding redundant flood-fill calculations.
+/// Stores the `analyze_flood_fill` score from each analyzed position,
+/// avoiding redundant flood-fill calculations. Scores are kept as `f32`
+/// so the fractional opponent path term survives.
 #[derive(Debug, Clone)]
 pub struct FloodFillCache {
-    cache: HashMap<(usize, usize), usize>,
+    cache: HashMap<(usize, usize), f32>,
 }
 
 impl FloodFillCache {
@@ -24,10 +34,20 @@ impl FloodFillCache {
         }
     }
 
+    /// Create an empty flood-fill cache with room for `n` entries
+    ///
+    /// Scoring stores at most one entry per board cell, so sizing to the
+    /// cell count avoids rehashing while the cache fills.
+    pub fn with_capacity(n: usize) -> Self {
+        FloodFillCache {
+            cache: HashMap::with_capacity(n),
+        }
+    }
+
     /// Get cached result or compute and cache
-    pub fn get_or_compute<F>(&mut self, pos: (usize, usize), compute: F) -> usize
+    pub fn get_or_compute<F>(&mut self, pos: (usize, usize), compute: F) -> f32
     where
-        F: FnOnce() -> usize,
+        F: FnOnce() -> f32,
     {
         if let Some(&result) = self.cache.get(&pos) {
             return result;
@@ -38,6 +58,16 @@ impl FloodFillCache {
         result
     }
 
+    /// Get a cached result without computing it
+    pub fn get(&self, pos: (usize, usize)) -> Option<f32> {
+        self.cache.get(&pos).copied()
+    }
+
+    /// Store a computed result
+    pub fn insert(&mut self, pos: (usize, usize), result: f32) {
+        self.cache.insert(pos, result);
+    }
+
     /// Clear the cache
     pub fn clear(&mut self) {
         self.cache.clear();
@@ -74,6 +104,16 @@ impl DensityCache {
         }
     }
 
+    /// Create an empty density cache with room for `n` entries
+    ///
+    /// Scoring stores at most one entry per board cell, so sizing to the
+    /// cell count avoids rehashing while the cache fills.
+    pub fn with_capacity(n: usize) -> Self {
+        DensityCache {
+            cache: HashMap::with_capacity(n),
+        }
+    }
+
     /// Get cached result or compute and cache
     pub fn get_or_compute<F>(&mut self, pos: (usize, usize), compute: F) -> usize
     where
@@ -88,6 +128,16 @@ impl DensityCache {
         result
     }
 
+    /// Get a cached result without computing it
+    pub fn get(&self, pos: (usize, usize)) -> Option<usize> {
+        self.cache.get(&pos).copied()
+    }
+
+    /// Store a computed result
+    pub fn insert(&mut self, pos: (usize, usize), result: usize) {
+        self.cache.insert(pos, result);
+    }
+
     /// Clear the cache
     pub fn clear(&mut self) {
         self.cache.clear();
@@ -108,6 +158,230 @@ impl Default for DensityCache {
     }
 }
 
+/// Cache for per-turn board projections
+///
+/// Growth projections simulate several moves and are identical for every
+/// candidate placement in a turn, so they are computed once per
+/// `(player, turns)` key and reused.
+#[derive(Debug, Clone)]
+pub struct TurnCache {
+    projections: HashMap<(u8, usize), f32>,
+}
+
+impl TurnCache {
+    /// Create a new empty turn cache
+    pub fn new() -> Self {
+        TurnCache {
+            projections: HashMap::new(),
+        }
+    }
+
+    /// Get cached projection or compute and cache
+    pub fn get_or_compute_projection<F>(&mut self, key: (u8, usize), compute: F) -> f32
+    where
+        F: FnOnce() -> f32,
+    {
+        *self.projections.entry(key).or_insert_with(compute)
+    }
+
+    /// Clear the cache (call at the start of each turn)
+    pub fn clear(&mut self) {
+        self.projections.clear();
+    }
+
+    /// Get cache statistics
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.projections.len(),
+            capacity: self.projections.capacity(),
+        }
+    }
+}
+
+impl Default for TurnCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cache for per-shape evaluation results
+///
+/// Keyed by `Shape::canonical_key`, so shapes with the same filled pattern
+/// but different framing share an entry.
+#[derive(Debug, Clone)]
+pub struct ShapeCache {
+    cache: HashMap<Vec<(usize, usize)>, f32>,
+}
+
+impl ShapeCache {
+    /// Create a new empty shape cache
+    pub fn new() -> Self {
+        ShapeCache {
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Get cached result or compute and cache
+    pub fn get_or_compute<F>(&mut self, shape: &Shape, compute: F) -> f32
+    where
+        F: FnOnce() -> f32,
+    {
+        *self.cache.entry(shape.canonical_key()).or_insert_with(compute)
+    }
+
+    /// Clear the cache
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Get cache statistics
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.cache.len(),
+            capacity: self.cache.capacity(),
+        }
+    }
+}
+
+impl Default for ShapeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Default number of entries kept by `EvalCache`
+pub const EVAL_CACHE_CAPACITY: usize = 4096;
+
+/// Least-recently-used cache of whole-placement scores
+///
+/// Keyed by `(placement position, cells_added)`, which identifies a
+/// placement only for a fixed board and piece: clear it between turns.
+/// `BatchScorer` stores its batch scores here and `evaluate_cached` its
+/// `evaluate_placement` scores, so one context serves only one of them.
+/// Once `capacity` entries are stored, inserting evicts the entry that
+/// was read or written longest ago.
+#[derive(Debug, Clone)]
+pub struct EvalCache {
+    /// Score and last-use tick per key
+    cache: HashMap<(Position, usize), (f32, u64)>,
+    /// Keys by last-use tick, oldest first
+    recency: BTreeMap<u64, (Position, usize)>,
+    capacity: usize,
+    tick: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl EvalCache {
+    /// Create an empty cache holding at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        EvalCache {
+            cache: HashMap::new(),
+            recency: BTreeMap::new(),
+            capacity,
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Cached score, marking the entry as recently used
+    ///
+    /// Counts a hit or a miss.
+    pub fn get(&mut self, key: (Position, usize)) -> Option<f32> {
+        let tick = self.next_tick();
+        match self.cache.get_mut(&key) {
+            Some((score, last_used)) => {
+                self.recency.remove(last_used);
+                *last_used = tick;
+                self.recency.insert(tick, key);
+                self.hits += 1;
+                Some(*score)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store a score, evicting the least recently used entry when full
+    pub fn insert(&mut self, key: (Position, usize), score: f32) {
+        if self.capacity == 0 {
+            return;
+        }
+        let tick = self.next_tick();
+        if let Some((_, last_used)) = self.cache.insert(key, (score, tick)) {
+            self.recency.remove(&last_used);
+        } else if self.cache.len() > self.capacity
+            && let Some((_, oldest)) = self.recency.pop_first()
+        {
+            self.cache.remove(&oldest);
+        }
+        self.recency.insert(tick, key);
+    }
+
+    /// Get cached result or compute and cache
+    pub fn get_or_compute<F>(&mut self, key: (Position, usize), compute: F) -> f32
+    where
+        F: FnOnce() -> f32,
+    {
+        if let Some(score) = self.get(key) {
+            return score;
+        }
+        let score = compute();
+        self.insert(key, score);
+        score
+    }
+
+    /// Clear the cache and its hit counters
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.recency.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Lookups answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Lookups that found no entry
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Share of lookups that hit, 0 before any lookup
+    pub fn hit_rate(&self) -> f32 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f32 / lookups as f32
+        }
+    }
+
+    /// Get cache statistics (`capacity` is the LRU bound)
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.cache.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+impl Default for EvalCache {
+    fn default() -> Self {
+        Self::new(EVAL_CACHE_CAPACITY)
+    }
+}
+
 /// Statistics about cache performance
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CacheStats {
@@ -126,68 +400,28 @@ impl CacheStats {
     }
 }
 
-/// Optimized flood-fill implementation with early termination
+/// Statistics for every cache in a `ScoringContext`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextStats {
+    pub flood_fill: CacheStats,
+    pub density: CacheStats,
+    pub eval: CacheStats,
+    /// `EvalCache::hit_rate` of the score cache
+    pub eval_hit_rate: f32,
+}
+
+/// Flood-fill count with early termination
 /// 
-/// Uses early termination when exploring for territory estimation
+/// Approximation of `Grid::reachable_empty_cells`: the BFS stops after
+/// expanding `max_iterations` cells, so on large open regions the count is
+/// truncated. Use the unbounded method when the exact region matters.
 pub fn flood_fill_bounded(
     grid: &Grid,
     start_positions: &[Position],
     max_iterations: usize,
 ) -> usize {
-    use std::collections::{VecDeque, HashSet};
-
-    let mut visited = HashSet::new();
-    let mut queue = VecDeque::new();
-    let mut iterations = 0;
-
-    // Initialize queue with starting positions
-    for &pos in start_positions {
-        if grid.is_valid(pos) {
-            queue.push_back(pos);
-            visited.insert(pos);
-        }
-    }
-
-    let mut reachable_count = 0;
-
-    while let Some(pos) = queue.pop_front() {
-        // Early termination check
-        if iterations >= max_iterations {
-            break;
-        }
-        iterations += 1;
-
-        // Check all 4 adjacent cells
-        let neighbors = [
-            Position::new(pos.x.wrapping_add(1), pos.y),
-            Position::new(pos.x.wrapping_sub(1), pos.y),
-            Position::new(pos.x, pos.y.wrapping_add(1)),
-            Position::new(pos.x, pos.y.wrapping_sub(1)),
-        ];
-
-        for neighbor in neighbors {
-            if !visited.contains(&neighbor) && grid.is_valid(neighbor) {
-                if let Some(state) = grid.get(neighbor) {
-                    use crate::game_state::CellState;
-                    // Only continue through empty cells or our territory
-                    if matches!(state, CellState::Empty | CellState::Player1 | CellState::Player1Last) {
-                        visited.insert(neighbor);
-
-                        if state == CellState::Empty {
-                            reachable_count += 1;
-                        }
-
-                        // Only queue empty cells for further exploration
-                        if state == CellState::Empty {
-                            queue.push_back(neighbor);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    reachable_count
+    grid.reachable_empty_cells_limited(start_positions, None, max_iterations)
+        .len()
 }
 
 /// Score calculation with caching
@@ -196,6 +430,7 @@ pub fn flood_fill_bounded(
 pub struct ScoringContext {
     flood_fill_cache: FloodFillCache,
     density_cache: DensityCache,
+    eval_cache: EvalCache,
 }
 
 impl ScoringContext {
@@ -204,9 +439,27 @@ impl ScoringContext {
         ScoringContext {
             flood_fill_cache: FloodFillCache::new(),
             density_cache: DensityCache::new(),
+            eval_cache: EvalCache::default(),
+        }
+    }
+
+    /// Create a scoring context whose caches hold `n` entries without growing
+    pub fn with_capacity(n: usize) -> Self {
+        ScoringContext {
+            flood_fill_cache: FloodFillCache::with_capacity(n),
+            density_cache: DensityCache::with_capacity(n),
+            eval_cache: EvalCache::default(),
         }
     }
 
+    /// Create a scoring context with caches sized for `grid`
+    ///
+    /// The flood-fill and density caches are keyed by board cell, so
+    /// `width * height` entries is all they can ever need.
+    pub fn for_grid(grid: &Grid) -> Self {
+        Self::with_capacity(grid.width * grid.height)
+    }
+
     /// Get flood-fill cache (mutable)
     pub fn flood_fill_cache_mut(&mut self) -> &mut FloodFillCache {
         &mut self.flood_fill_cache
@@ -217,18 +470,37 @@ impl ScoringContext {
         &mut self.density_cache
     }
 
+    /// Get the `evaluate_placement` score cache
+    pub fn eval_cache(&self) -> &EvalCache {
+        &self.eval_cache
+    }
+
+    /// `evaluate_placement` score, cached by position and cells added
+    ///
+    /// The key ignores the board, so call `reset` before scoring a new
+    /// position.
+    pub fn evaluate_cached(&mut self, placement: &Placement, game_state: &GameState) -> f32 {
+        self.eval_cache
+            .get_or_compute((placement.position, placement.cells_added), || {
+                evaluate_placement(placement, game_state).value()
+            })
+    }
+
     /// Reset all caches between evaluations
     pub fn reset(&mut self) {
         self.flood_fill_cache.clear();
         self.density_cache.clear();
+        self.eval_cache.clear();
     }
 
     /// Get combined cache statistics
-    pub fn cache_stats(&self) -> (CacheStats, CacheStats) {
-        (
-            self.flood_fill_cache.stats(),
-            self.density_cache.stats(),
-        )
+    pub fn cache_stats(&self) -> ContextStats {
+        ContextStats {
+            flood_fill: self.flood_fill_cache.stats(),
+            density: self.density_cache.stats(),
+            eval: self.eval_cache.stats(),
+            eval_hit_rate: self.eval_cache.hit_rate(),
+        }
     }
 }
 
@@ -238,11 +510,19 @@ impl Default for ScoringContext {
     }
 }
 
+/// Room above `cells_added` times the expansion weight allowed for in
+/// `BatchScorer::score_top_k`'s optimistic estimate
+pub const TOP_K_BOUND_SLACK: f32 = 10.0;
+
 /// Placement scoring with cached results
 /// 
-/// Enables batch scoring of multiple placements with shared cache
+/// Enables batch scoring of multiple placements with shared cache. The
+/// caches are kept between batches while the board, player and piece stay
+/// the same, so re-scoring a placement is an `EvalCache` hit.
 pub struct BatchScorer {
     context: ScoringContext,
+    /// Zobrist hash and piece of the state the caches were filled for
+    scored_state: Option<(u64, Shape)>,
 }
 
 impl BatchScorer {
@@ -250,76 +530,193 @@ impl BatchScorer {
     pub fn new() -> Self {
         BatchScorer {
             context: ScoringContext::new(),
+            scored_state: None,
+        }
+    }
+
+    /// Create a batch scorer with caches sized for `grid`
+    ///
+    /// See `ScoringContext::for_grid`.
+    pub fn for_grid(grid: &Grid) -> Self {
+        BatchScorer {
+            context: ScoringContext::for_grid(grid),
+            scored_state: None,
         }
     }
 
     /// Score all placements with shared cache
+    ///
+    /// Each candidate is simulated at most once; the resulting grid is
+    /// shared by every heuristic that needs the post-placement board.
     pub fn score_all(
         &mut self,
         placements: &[Placement],
         game_state: &GameState,
     ) -> Vec<(Placement, f32)> {
-        self.context.reset();
+        self.begin_batch(game_state);
+        let weakness = WeaknessMap::new(game_state);
 
         placements
             .iter()
             .map(|placement| {
-                let score = self.score_single(placement, game_state);
+                let score = self.score_single(placement, game_state, &weakness);
                 (placement.clone(), score)
             })
             .collect()
     }
 
-    /// Score a single placement using cache
-    fn score_single(&mut self, placement: &Placement, game_state: &GameState) -> f32 {
-        use crate::ai::heuristics;
-
-        // Base expansion score (not cached - fast computation)
-        let base_expansion = (placement.cells_added as f32) * 10.0;
-
-        // Flood-fill (cached)
-        let abs_positions = placement.get_absolute_positions();
-        let flood_fill = if !abs_positions.is_empty() {
-            let first_pos = abs_positions[0];
-            let key = (first_pos.x, first_pos.y);
-            let reachable = self
-                .context
-                .flood_fill_cache_mut()
-                .get_or_compute(key, || {
-                    heuristics::analyze_flood_fill(placement, game_state) as usize
-                });
-            (reachable as f32) * 1.5
-        } else {
-            0.0
-        };
+    /// Score placements, stopping once the best score stops improving
+    ///
+    /// Placements are processed in descending `cells_added` order (stable),
+    /// and scoring stops after `stability_threshold` consecutive placements
+    /// fail to beat the running maximum. The returned subset may therefore
+    /// be incomplete: this is an approximate, faster alternative to
+    /// `score_all` for boards with many valid placements.
+    pub fn score_all_with_early_exit(
+        &mut self,
+        placements: &[Placement],
+        game_state: &GameState,
+        stability_threshold: usize,
+    ) -> Vec<(Placement, f32)> {
+        self.begin_batch(game_state);
+        let weakness = WeaknessMap::new(game_state);
+
+        let mut ordered: Vec<&Placement> = placements.iter().collect();
+        PlacementOrdering::ByCellsAdded.sort(&mut ordered, game_state, |p| *p);
 
-        // Weak positions (not cached - depends on current board state)
-        let weak_positions = heuristics::detect_weak_positions(placement, game_state);
-
-        // Density (cached per position)
-        let density = if !abs_positions.is_empty() {
-            let first_pos = abs_positions[0];
-            let key = (first_pos.x, first_pos.y);
-            let nearby = self
-                .context
-                .density_cache_mut()
-                .get_or_compute(key, || {
-                    heuristics::analyze_density(placement, game_state) as usize
-                });
-            (nearby as f32) * 1.2
+        let mut scored = Vec::with_capacity(ordered.len());
+        let mut best = f32::NEG_INFINITY;
+        let mut stale = 0;
+        for placement in ordered {
+            let score = self.score_single(placement, game_state, &weakness);
+            scored.push((placement.clone(), score));
+
+            if score > best {
+                best = score;
+                stale = 0;
+            } else {
+                stale += 1;
+                if stale >= stability_threshold {
+                    break;
+                }
+            }
+        }
+
+        scored
+    }
+
+    /// Best `k` placements by score, skipping ones that cannot make the cut
+    ///
+    /// Placements are visited in `ordering` (see `PlacementOrdering`), or
+    /// with `None` in a random order seeded from the position's Zobrist
+    /// hash; either way the result is deterministic. Visiting promising
+    /// placements first fills the heap with high scores sooner, so more
+    /// of the rest are skipped. Once `k` are kept (in a min-heap), a placement is only scored in full if its optimistic
+    /// estimate beats the weakest kept score: `cells_added` times the
+    /// expansion weight, plus `TOP_K_BOUND_SLACK`, plus the largest
+    /// non-expansion part of any score seen so far. The estimate is not a
+    /// guarantee, so like `score_all_with_early_exit` this is approximate.
+    /// Returns at most `k` placements, best first; ties keep input order.
+    pub fn score_top_k(
+        &mut self,
+        k: usize,
+        placements: &[Placement],
+        game_state: &GameState,
+        ordering: Option<PlacementOrdering>,
+    ) -> Vec<(Placement, f32)> {
+        self.begin_batch(game_state);
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let weakness = WeaknessMap::new(game_state);
+        let expansion = HeuristicWeights::default().expansion;
+        let mut order: Vec<(usize, &Placement)> = placements.iter().enumerate().collect();
+        match ordering {
+            Some(ordering) => ordering.sort(&mut order, game_state, |&(_, placement)| placement),
+            None => order.shuffle(&mut StdRng::seed_from_u64(game_state.zobrist_hash())),
+        }
+
+        // Weakest kept entry on top: (score, input index), both reversed
+        let mut kept: BinaryHeap<Reverse<(Score, Reverse<usize>)>> = BinaryHeap::with_capacity(k + 1);
+        let mut largest_rest = f32::NEG_INFINITY;
+        for (index, placement) in order {
+            let base = placement.cells_added as f32 * expansion;
+            if kept.len() == k {
+                let bound = base + TOP_K_BOUND_SLACK + largest_rest.max(0.0);
+                if kept.peek().is_some_and(|Reverse((weakest, _))| Score(bound) <= *weakest) {
+                    continue;
+                }
+            }
+
+            let score = self.score_single(placement, game_state, &weakness);
+            largest_rest = largest_rest.max(score - base);
+            kept.push(Reverse((Score(score), Reverse(index))));
+            if kept.len() > k {
+                kept.pop();
+            }
+        }
+
+        let mut best: Vec<(Score, usize)> = kept.into_iter().map(|Reverse((score, Reverse(index)))| (score, index)).collect();
+        best.sort_by_key(|&(score, index)| (Reverse(score), index));
+        best.into_iter().map(|(score, index)| (placements[index].clone(), score.value())).collect()
+    }
+
+    /// Score all placements in parallel
+    ///
+    /// With `use_shared_cache`, all threads share this scorer's context
+    /// behind a mutex (held only for lookups and inserts). Otherwise each
+    /// rayon worker owns its own `ScoringContext`, which needs no
+    /// synchronization but reuses less. Results keep the input order.
+    pub fn score_all_parallel(
+        &mut self,
+        placements: &[Placement],
+        game_state: &GameState,
+        use_shared_cache: bool,
+    ) -> Vec<(Placement, f32)> {
+        self.begin_batch(game_state);
+        let weakness = WeaknessMap::new(game_state);
+
+        if use_shared_cache {
+            let shared = Mutex::new(std::mem::take(&mut self.context));
+            let scored = placements
+                .par_iter()
+                .map(|placement| {
+                    let score = score_with_cache(&mut SharedContext(&shared), placement, game_state, &weakness);
+                    (placement.clone(), score)
+                })
+                .collect();
+            self.context = shared.into_inner().unwrap_or_else(|e| e.into_inner());
+            scored
         } else {
-            0.0
-        };
+            placements
+                .par_iter()
+                .map_init(|| ScoringContext::for_grid(game_state.grid()), |context, placement| {
+                    let score = score_with_cache(context, placement, game_state, &weakness);
+                    (placement.clone(), score)
+                })
+                .collect()
+        }
+    }
 
-        // Edge control (fast, not cached)
-        let edge_control = heuristics::analyze_edge_control(placement, &game_state.grid);
+    /// Reset the caches unless they were filled for `game_state`
+    fn begin_batch(&mut self, game_state: &GameState) {
+        let same_state = self.scored_state.as_ref().is_some_and(|(hash, piece)| {
+            *hash == game_state.zobrist_hash() && *piece == game_state.current_piece
+        });
+        if !same_state {
+            self.context.reset();
+            self.scored_state = Some((game_state.zobrist_hash(), game_state.current_piece.clone()));
+        }
+    }
 
-        // Combined score
-        base_expansion + flood_fill + (weak_positions * 2.0) + density + (edge_control * 0.5)
+    /// Score a single placement using cache
+    fn score_single(&mut self, placement: &Placement, game_state: &GameState, weakness: &WeaknessMap) -> f32 {
+        score_with_cache(&mut self.context, placement, game_state, weakness)
     }
 
     /// Get cache performance statistics
-    pub fn cache_stats(&self) -> (CacheStats, CacheStats) {
+    pub fn cache_stats(&self) -> ContextStats {
         self.context.cache_stats()
     }
 }
@@ -330,6 +727,145 @@ impl Default for BatchScorer {
     }
 }
 
+/// Cache access used while scoring a placement
+trait ScoreCache {
+    fn score(&mut self, key: (Position, usize), compute: &mut dyn FnMut(&mut Self) -> f32) -> f32;
+    fn flood_fill(&mut self, key: (usize, usize), compute: &dyn Fn() -> f32) -> f32;
+    fn density(&mut self, key: (usize, usize), compute: &dyn Fn() -> usize) -> usize;
+}
+
+impl ScoreCache for ScoringContext {
+    fn score(&mut self, key: (Position, usize), compute: &mut dyn FnMut(&mut Self) -> f32) -> f32 {
+        if let Some(score) = self.eval_cache.get(key) {
+            return score;
+        }
+        let score = compute(self);
+        self.eval_cache.insert(key, score);
+        score
+    }
+
+    fn flood_fill(&mut self, key: (usize, usize), compute: &dyn Fn() -> f32) -> f32 {
+        self.flood_fill_cache.get_or_compute(key, compute)
+    }
+
+    fn density(&mut self, key: (usize, usize), compute: &dyn Fn() -> usize) -> usize {
+        self.density_cache.get_or_compute(key, compute)
+    }
+}
+
+/// A `ScoringContext` shared between threads
+///
+/// The lock is released while computing, so two threads may compute the
+/// same entry; both produce the same value.
+struct SharedContext<'a>(&'a Mutex<ScoringContext>);
+
+impl SharedContext<'_> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, ScoringContext> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ScoreCache for SharedContext<'_> {
+    fn score(&mut self, key: (Position, usize), compute: &mut dyn FnMut(&mut Self) -> f32) -> f32 {
+        if let Some(score) = self.lock().eval_cache.get(key) {
+            return score;
+        }
+        let score = compute(self);
+        self.lock().eval_cache.insert(key, score);
+        score
+    }
+
+    fn flood_fill(&mut self, key: (usize, usize), compute: &dyn Fn() -> f32) -> f32 {
+        if let Some(result) = self.lock().flood_fill_cache.get(key) {
+            return result;
+        }
+        let result = compute();
+        self.lock().flood_fill_cache.insert(key, result);
+        result
+    }
+
+    fn density(&mut self, key: (usize, usize), compute: &dyn Fn() -> usize) -> usize {
+        if let Some(result) = self.lock().density_cache.get(key) {
+            return result;
+        }
+        let result = compute();
+        self.lock().density_cache.insert(key, result);
+        result
+    }
+}
+
+/// Score a single placement, caching the score and its flood-fill and
+/// density parts
+///
+/// `weakness` is the `WeaknessMap` of `game_state`, shared by the batch.
+fn score_with_cache<C: ScoreCache>(
+    cache: &mut C,
+    placement: &Placement,
+    game_state: &GameState,
+    weakness: &WeaknessMap,
+) -> f32 {
+    cache.score((placement.position, placement.cells_added), &mut |cache| {
+        score_parts(cache, placement, game_state, weakness)
+    })
+}
+
+/// Uncached sum of the `score_with_cache` terms
+fn score_parts(
+    cache: &mut impl ScoreCache,
+    placement: &Placement,
+    game_state: &GameState,
+    weakness: &WeaknessMap,
+) -> f32 {
+    use crate::ai::heuristics;
+    let weights = heuristics::HeuristicWeights::default();
+
+    // Base expansion score (not cached - fast computation)
+    let base_expansion = (placement.cells_added as f32) * weights.expansion;
+
+    // Flood-fill (cached, simulated once per candidate on cache miss)
+    let abs_positions = placement.get_absolute_positions();
+    let flood_fill = if !abs_positions.is_empty() {
+        let first_pos = abs_positions[0];
+        let key = (first_pos.x, first_pos.y);
+        let score = cache.flood_fill(key, &|| {
+            let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+            heuristics::analyze_flood_fill(placement, &simulated, game_state.opponent_number())
+        });
+        score * weights.flood_fill
+    } else {
+        0.0
+    };
+
+    // Weak positions (distance map computed once per batch)
+    let weak_positions = heuristics::weak_positions_with_map(
+        placement,
+        weakness,
+        heuristics::DEFAULT_WEAKNESS_THRESHOLD,
+    );
+
+    // Density (cached per position)
+    let density = if !abs_positions.is_empty() {
+        let first_pos = abs_positions[0];
+        let key = (first_pos.x, first_pos.y);
+        let nearby = cache.density(key, &|| {
+            heuristics::analyze_density(placement, game_state) as usize
+        });
+        (nearby as f32) * weights.density
+    } else {
+        0.0
+    };
+
+    // Edge control (fast, not cached)
+    let edge_control = heuristics::analyze_edge_control(placement, game_state.grid());
+
+    // Combined score
+    base_expansion
+        + flood_fill
+        + weak_positions * weights.weak_positions
+        + density
+        + edge_control * weights.edge_control
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,8 +880,8 @@ mod tests {
     fn test_flood_fill_cache_compute() {
         let mut cache = FloodFillCache::new();
         
-        let result = cache.get_or_compute((1, 2), || 42);
-        assert_eq!(result, 42);
+        let result = cache.get_or_compute((1, 2), || 42.0);
+        assert_eq!(result, 42.0);
         assert_eq!(cache.stats().entries, 1);
     }
 
@@ -353,11 +889,11 @@ mod tests {
     fn test_flood_fill_cache_hit() {
         let mut cache = FloodFillCache::new();
         
-        cache.get_or_compute((1, 2), || 42);
-        let result = cache.get_or_compute((1, 2), || 999);
+        cache.get_or_compute((1, 2), || 42.0);
+        let result = cache.get_or_compute((1, 2), || 999.0);
         
         // Should return cached value, not 999
-        assert_eq!(result, 42);
+        assert_eq!(result, 42.0);
     }
 
     #[test]
@@ -366,6 +902,19 @@ mod tests {
         assert_eq!(cache.stats().entries, 0);
     }
 
+    #[test]
+    fn test_caches_with_capacity() {
+        let flood_fill = FloodFillCache::with_capacity(100);
+        let density = DensityCache::with_capacity(100);
+        assert_eq!(flood_fill.stats().entries, 0);
+        assert!(flood_fill.stats().capacity >= 100);
+        assert!(density.stats().capacity >= 100);
+
+        let grid: Grid = "....\n....\n....".parse().unwrap();
+        let stats = ScoringContext::for_grid(&grid).cache_stats();
+        assert!(stats.flood_fill.capacity >= 12 && stats.density.capacity >= 12);
+    }
+
     #[test]
     fn test_density_cache_compute() {
         let mut cache = DensityCache::new();
@@ -398,31 +947,29 @@ mod tests {
     #[test]
     fn test_scoring_context_new() {
         let context = ScoringContext::new();
-        let (ff, den) = context.cache_stats();
+        let stats = context.cache_stats();
         
-        assert_eq!(ff.entries, 0);
-        assert_eq!(den.entries, 0);
+        assert_eq!(stats.flood_fill.entries, 0);
+        assert_eq!(stats.density.entries, 0);
+        assert_eq!(stats.eval.entries, 0);
     }
 
     #[test]
     fn test_batch_scorer_new() {
         let scorer = BatchScorer::new();
-        let (_ff, _den) = scorer.cache_stats();
+        let stats = scorer.cache_stats();
         
         // Should have empty caches initially
-        assert!(true);
+        assert_eq!(stats.eval.entries, 0);
+        assert_eq!(stats.eval_hit_rate, 0.0);
     }
 
     #[test]
     fn test_flood_fill_bounded_respects_max_iterations() {
-        let raw = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '@', '@', '.', '.'],
-            vec!['.', '@', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-        ];
-        let grid = crate::game_state::Grid::from_chars(5, 5, raw);
+        let grid = crate::game_state::GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(1, 2)])
+            .build()
+            .into_grid();
         let start = vec![Position::new(1, 1)];
         
         // With max_iterations = 0, should return 0
@@ -432,14 +979,10 @@ mod tests {
 
     #[test]
     fn test_flood_fill_bounded_with_high_limit() {
-        let raw = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '@', '@', '.', '.'],
-            vec!['.', '@', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-        ];
-        let grid = crate::game_state::Grid::from_chars(5, 5, raw);
+        let grid = crate::game_state::GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(1, 2)])
+            .build()
+            .into_grid();
         let start = vec![Position::new(0, 0)];
         
         // With high limit, should explore normally
@@ -447,10 +990,27 @@ mod tests {
         assert!(result > 0);
     }
 
+    #[test]
+    fn test_shape_cache_shares_equivalent_shapes() {
+        let mut cache = ShapeCache::new();
+        let tight = Shape::from_chars(2, 1, vec![vec!['#', '#']]);
+        let framed = Shape::from_chars(3, 2, vec![vec!['.', '.', '.'], vec!['.', '#', '#']]);
+
+        let first = cache.get_or_compute(&tight, || 4.5);
+        let second = cache.get_or_compute(&framed, || 99.0);
+
+        assert_eq!(first, 4.5);
+        assert_eq!(second, 4.5);
+        assert_eq!(cache.stats().entries, 1);
+
+        cache.clear();
+        assert_eq!(cache.stats().entries, 0);
+    }
+
     #[test]
     fn test_cache_clear() {
         let mut cache = FloodFillCache::new();
-        cache.get_or_compute((1, 2), || 42);
+        cache.get_or_compute((1, 2), || 42.0);
         assert_eq!(cache.stats().entries, 1);
         
         cache.clear();
@@ -460,17 +1020,253 @@ mod tests {
     #[test]
     fn test_context_reset() {
         let mut context = ScoringContext::new();
-        context.flood_fill_cache_mut().get_or_compute((1, 2), || 42);
+        context.flood_fill_cache_mut().get_or_compute((1, 2), || 42.0);
         context.density_cache_mut().get_or_compute((2, 3), || 15);
         
-        let (ff1, den1) = context.cache_stats();
-        assert!(ff1.entries > 0);
-        assert!(den1.entries > 0);
+        let before = context.cache_stats();
+        assert!(before.flood_fill.entries > 0);
+        assert!(before.density.entries > 0);
         
         context.reset();
         
-        let (ff2, den2) = context.cache_stats();
-        assert_eq!(ff2.entries, 0);
-        assert_eq!(den2.entries, 0);
+        let after = context.cache_stats();
+        assert_eq!(after.flood_fill.entries, 0);
+        assert_eq!(after.density.entries, 0);
+    }
+
+    #[test]
+    fn test_eval_cache_evicts_least_recently_used() {
+        let key = |x: usize| (Position::new(x, 0), 1);
+        let mut cache = EvalCache::new(2);
+
+        cache.insert(key(0), 1.0);
+        cache.insert(key(1), 2.0);
+        assert_eq!(cache.get(key(0)), Some(1.0));
+        // key(1) is now the oldest entry
+        cache.insert(key(2), 3.0);
+
+        assert_eq!(cache.get(key(1)), None);
+        assert_eq!(cache.get(key(0)), Some(1.0));
+        assert_eq!(cache.get(key(2)), Some(3.0));
+        assert_eq!(cache.stats(), CacheStats { entries: 2, capacity: 2 });
+        assert_eq!((cache.hits(), cache.misses()), (3, 1));
+        assert_eq!(cache.hit_rate(), 0.75);
+
+        cache.clear();
+        assert_eq!((cache.stats().entries, cache.hit_rate()), (0, 0.0));
+    }
+
+    #[test]
+    fn test_scoring_context_evaluate_cached() {
+        let game_state = create_islands_game_state();
+        let placements = crate::placement::find_all_valid_placements(&game_state);
+        let mut context = ScoringContext::new();
+
+        for _ in 0..2 {
+            for placement in &placements {
+                let score = context.evaluate_cached(placement, &game_state);
+                assert_eq!(score, evaluate_placement(placement, &game_state).value());
+            }
+        }
+
+        assert_eq!(context.eval_cache().hits(), placements.len());
+        assert_eq!(context.eval_cache().hit_rate(), 0.5);
+        context.reset();
+        assert_eq!(context.eval_cache().stats().entries, 0);
+    }
+
+    /// Four separated single-cell islands with an L piece on a 20x15 board
+    fn create_islands_game_state() -> GameState {
+        use crate::game_state::Shape;
+
+        let islands: Vec<Position> = [2, 6, 10, 14].iter().map(|&x| Position::new(x, 3)).collect();
+        let shape = Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]);
+        GameState::builder_from_size(20, 15)
+            .with_territory(1, &islands)
+            .with_territory(2, &[Position::new(16, 11)])
+            .with_piece(shape)
+            .build()
+    }
+
+    fn top_score(scored: &[(Placement, f32)]) -> Option<(Position, f32)> {
+        scored
+            .iter()
+            .max_by_key(|&(_, score)| Score(*score))
+            .map(|(p, s)| (p.position, *s))
+    }
+
+    #[test]
+    fn test_early_exit_matches_full_top_score() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = create_islands_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        let full = BatchScorer::new().score_all(&placements, &game_state);
+        let early = BatchScorer::new().score_all_with_early_exit(&placements, &game_state, 10);
+
+        assert!(early.len() <= full.len());
+        assert_eq!(
+            top_score(&early).map(|(_, s)| s),
+            top_score(&full).map(|(_, s)| s)
+        );
+    }
+
+    #[test]
+    fn test_early_exit_stops_after_threshold() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = create_islands_game_state();
+        let placements = find_all_valid_placements(&game_state);
+        assert!(placements.len() > 2);
+
+        let early = BatchScorer::new().score_all_with_early_exit(&placements, &game_state, 1);
+        let complete = BatchScorer::new()
+            .score_all_with_early_exit(&placements, &game_state, placements.len());
+
+        assert!(early.len() < placements.len());
+        assert_eq!(complete.len(), placements.len());
+    }
+
+    #[test]
+    fn test_score_top_k_keeps_best_scores() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = create_islands_game_state();
+        let placements = find_all_valid_placements(&game_state);
+        let full = BatchScorer::new().score_all(&placements, &game_state);
+        assert!(placements.len() > 3);
+
+        let top = BatchScorer::new().score_top_k(3, &placements, &game_state, None);
+        assert_eq!(top.len(), 3);
+        assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(top.iter().all(|entry| full.contains(entry)));
+        assert_eq!(top.first().map(|(_, s)| *s), top_score(&full).map(|(_, s)| s));
+
+        let all = BatchScorer::new().score_top_k(placements.len() + 1, &placements, &game_state, None);
+        assert_eq!(all.len(), placements.len());
+        assert!(BatchScorer::new().score_top_k(0, &placements, &game_state, None).is_empty());
+    }
+
+    #[test]
+    fn test_score_top_k_ordering_finds_best_first() {
+        use crate::game_state::Shape;
+        use crate::placement::find_all_valid_placements;
+
+        // Domino placements (1 cell added) listed before 2x2 ones (3 added)
+        let game_state = create_islands_game_state();
+        let with_piece = |shape: Shape| {
+            let mut state = game_state.clone();
+            state.current_piece = shape;
+            find_all_valid_placements(&state)
+        };
+        let small = with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]));
+        let large = with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '#']]));
+        let placements: Vec<Placement> = small.iter().chain(&large).cloned().collect();
+
+        let mut in_order = BatchScorer::new();
+        let row_major = in_order.score_top_k(1, &placements, &game_state, Some(PlacementOrdering::RowMajor));
+        let mut largest_first = BatchScorer::new();
+        let by_cells = largest_first.score_top_k(1, &placements, &game_state, Some(PlacementOrdering::ByCellsAdded));
+
+        // Scanning the dominoes first scores everything; visiting the 2x2
+        // placements first lets every domino be skipped
+        assert_eq!(in_order.cache_stats().eval.entries, placements.len());
+        assert_eq!(largest_first.cache_stats().eval.entries, large.len());
+        assert_eq!(by_cells[0].0.cells_added, 3);
+        assert_eq!(row_major[0].0.cells_added, 3);
+    }
+
+    #[test]
+    fn test_score_all_parallel_matches_sequential() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = create_islands_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        let sequential = BatchScorer::new().score_all(&placements, &game_state);
+        let per_thread = BatchScorer::new().score_all_parallel(&placements, &game_state, false);
+        let mut scorer = BatchScorer::new();
+        let shared = scorer.score_all_parallel(&placements, &game_state, true);
+
+        assert_eq!(per_thread, sequential);
+        assert_eq!(shared, sequential);
+        // The shared context is kept by the scorer
+        assert_eq!(scorer.cache_stats().flood_fill.entries, placements.len());
+        assert_eq!(scorer.cache_stats().eval.entries, placements.len());
+    }
+
+    #[test]
+    fn test_batch_scorer_reuses_scores_for_same_state() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = create_islands_game_state();
+        let placements = find_all_valid_placements(&game_state);
+        let mut scorer = BatchScorer::new();
+
+        let first = scorer.score_all(&placements, &game_state);
+        assert_eq!(scorer.cache_stats().eval_hit_rate, 0.0);
+        let second = scorer.score_all(&placements, &game_state);
+        let stats = scorer.cache_stats();
+        assert_eq!(second, first);
+        assert_eq!(stats.eval.entries, placements.len());
+        assert_eq!(stats.eval_hit_rate, 0.5);
+
+        // A different board starts from empty caches
+        let next = game_state.apply_placement(&placements[0]);
+        scorer.score_all(&find_all_valid_placements(&next), &next);
+        assert_eq!(scorer.cache_stats().eval_hit_rate, 0.0);
+    }
+
+    #[test]
+    fn test_pre_simulated_scoring_benchmark() {
+        use crate::ai::benchmark::{BenchmarkResult, PerformanceMetrics, Timer};
+        use crate::ai::heuristics::{advanced_score, advanced_score_pure, HeuristicWeights, ScoreMaps};
+        use crate::placement::find_all_valid_placements;
+
+        // Separated single-cell islands, each offering 3 L-piece contacts
+        let game_state = create_islands_game_state();
+        let placements: Vec<_> = find_all_valid_placements(&game_state)
+            .into_iter()
+            .take(10)
+            .collect();
+        assert_eq!(placements.len(), 10);
+
+        let mut baseline = PerformanceMetrics::new();
+        let mut optimized = PerformanceMetrics::new();
+        let maps = ScoreMaps::new(&game_state);
+        for placement in &placements {
+            let timer = Timer::start();
+            let shimmed = advanced_score(placement, &game_state, &HeuristicWeights::default());
+            baseline.record(timer.elapsed());
+
+            let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+            let timer = Timer::start();
+            let pure = advanced_score_pure(placement, &simulated, &game_state, &maps, &HeuristicWeights::default());
+            optimized.record(timer.elapsed());
+
+            // Both paths must agree exactly
+            assert_eq!(shimmed, pure);
+        }
+
+        let result = BenchmarkResult {
+            baseline_metrics: baseline,
+            optimized_metrics: optimized,
+        };
+        assert_eq!(result.baseline_metrics.operations, 10);
+        assert_eq!(result.optimized_metrics.operations, 10);
+        assert!(result.speedup() > 0.0);
+    }
+
+    #[test]
+    fn test_turn_cache_hit() {
+        let mut cache = TurnCache::new();
+
+        assert_eq!(cache.get_or_compute_projection((2, 3), || 12.0), 12.0);
+        assert_eq!(cache.get_or_compute_projection((2, 3), || 99.0), 12.0);
+        assert_eq!(cache.stats().entries, 1);
+
+        cache.clear();
+        assert_eq!(cache.stats().entries, 0);
     }
 }
@@ -6,7 +6,9 @@
 use crate::game_state::GameState;
 use crate::placement::Placement;
 use super::optimization::BatchScorer;
-use super::heuristics::advanced_score;
+use super::heuristics::{advanced_score, HeuristicWeights};
+use super::score::Score;
+use std::cmp::Ordering;
 
 /// Optimized move selection using cached batch scoring
 /// 
@@ -20,41 +22,81 @@ pub fn select_best_placement_optimized(
         return None;
     }
 
-    let mut scorer = BatchScorer::new();
+    let mut scorer = BatchScorer::for_grid(game_state.grid());
     let scored = scorer.score_all(placements, game_state);
 
     scored
         .into_iter()
-        .max_by(|a, b| {
-            a.1.partial_cmp(&b.1)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
+        .max_by_key(|&(_, score)| Score(score))
+        .map(|(placement, _score)| placement)
+}
+
+/// Parallel move selection using rayon
+/// 
+/// `use_shared_cache` selects between one mutex-guarded cache shared by
+/// all threads and a private cache per worker thread.
+pub fn select_best_placement_parallel(
+    placements: &[Placement],
+    game_state: &GameState,
+    use_shared_cache: bool,
+) -> Option<Placement> {
+    if placements.is_empty() {
+        return None;
+    }
+
+    let mut scorer = BatchScorer::for_grid(game_state.grid());
+    let scored = scorer.score_all_parallel(placements, game_state, use_shared_cache);
+
+    scored
+        .into_iter()
+        .max_by_key(|&(_, score)| Score(score))
         .map(|(placement, _score)| placement)
 }
 
 /// Fast scoring for a single placement without cache overhead
 /// 
 /// For single placements, avoids cache initialization overhead
-pub fn score_single_fast(placement: &Placement, game_state: &GameState) -> f32 {
-    advanced_score(placement, game_state)
+pub fn score_single_fast(placement: &Placement, game_state: &GameState) -> Score {
+    advanced_score(placement, game_state, &HeuristicWeights::default())
 }
 
 /// Rank placements by score using cached batch scoring
+///
+/// Ties are broken by row-major position (`y * grid_width + x`), so the
+/// ranking is fully deterministic regardless of input order.
 pub fn rank_placements_optimized(
     placements: &[Placement],
     game_state: &GameState,
+) -> Vec<(Placement, f32)> {
+    let width = game_state.grid().width;
+    rank_placements_optimized_with_tiebreak(placements, game_state, |a, b| {
+        let index_a = a.position.y * width + a.position.x;
+        let index_b = b.position.y * width + b.position.x;
+        index_a.cmp(&index_b)
+    })
+}
+
+/// Rank placements by score using a custom tie-breaker
+///
+/// Placements are sorted by score descending; `tiebreak` orders placements
+/// whose scores compare equal.
+pub fn rank_placements_optimized_with_tiebreak(
+    placements: &[Placement],
+    game_state: &GameState,
+    tiebreak: impl Fn(&Placement, &Placement) -> Ordering,
 ) -> Vec<(Placement, f32)> {
     if placements.is_empty() {
         return Vec::new();
     }
 
-    let mut scorer = BatchScorer::new();
+    let mut scorer = BatchScorer::for_grid(game_state.grid());
     let mut scored = scorer.score_all(placements, game_state);
-    
-    // Sort by score descending
+
+    // Sort by score descending, then by tie-breaker
     scored.sort_by(|a, b| {
-        b.1.partial_cmp(&a.1)
-            .unwrap_or(std::cmp::Ordering::Equal)
+        Score(b.1)
+            .cmp(&Score(a.1))
+            .then_with(|| tiebreak(&a.0, &b.0))
     });
 
     scored
@@ -63,19 +105,15 @@ pub fn rank_placements_optimized(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game_state::{Grid, Shape};
+    use crate::game_state::Shape;
 
     fn create_test_game_state() -> GameState {
-        let raw = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '@', '@', '.', '.'],
-            vec!['.', '@', '.', '.', '.'],
-            vec!['.', '.', '.', '$', '$'],
-            vec!['.', '.', '.', '$', '.'],
-        ];
-        let grid = Grid::from_chars(5, 5, raw);
-        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
-        GameState::new(1, grid, shape)
+        use crate::game_state::Position;
+        GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(1, 2)])
+            .with_territory(2, &[Position::new(3, 3), Position::new(4, 3), Position::new(3, 4)])
+            .with_piece(Shape::from_chars(1, 1, vec![vec!['#']]))
+            .build()
     }
 
     fn create_test_placements() -> Vec<Placement> {
@@ -139,7 +177,7 @@ mod tests {
         let score = score_single_fast(&placement, &game_state);
 
         // Should return a positive score
-        assert!(score > 0.0);
+        assert!(score > Score(0.0));
     }
 
     #[test]
@@ -182,4 +220,95 @@ mod tests {
 
         assert_eq!(ranked.len(), 0);
     }
+
+    #[test]
+    fn test_rank_placements_optimized_deterministic() {
+        let placements = create_test_placements();
+        let game_state = create_test_game_state();
+
+        let first = rank_placements_optimized(&placements, &game_state);
+        let second = rank_placements_optimized(&placements, &game_state);
+
+        let first_positions: Vec<_> = first.iter().map(|(p, _)| p.position).collect();
+        let second_positions: Vec<_> = second.iter().map(|(p, _)| p.position).collect();
+        assert_eq!(first_positions, second_positions);
+    }
+
+    #[test]
+    fn test_rank_placements_optimized_ties_independent_of_input_order() {
+        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let make = |x, y| Placement {
+            position: crate::game_state::Position::new(x, y),
+            shape: shape.clone(),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        // Two empty corner cells far from both territories score identically
+        let forward = vec![make(4, 0), make(0, 4)];
+        let reversed = vec![make(0, 4), make(4, 0)];
+        let game_state = create_test_game_state();
+
+        let ranked_forward = rank_placements_optimized(&forward, &game_state);
+        let ranked_reversed = rank_placements_optimized(&reversed, &game_state);
+
+        assert_eq!(ranked_forward[0].1, ranked_forward[1].1);
+        let forward_positions: Vec<_> = ranked_forward.iter().map(|(p, _)| p.position).collect();
+        let reversed_positions: Vec<_> = ranked_reversed.iter().map(|(p, _)| p.position).collect();
+        assert_eq!(forward_positions, reversed_positions);
+        // Default tie-breaker is row-major: (4, 0) comes before (0, 4)
+        assert_eq!(forward_positions[0], crate::game_state::Position::new(4, 0));
+    }
+
+    #[test]
+    fn test_rank_placements_optimized_with_custom_tiebreak() {
+        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let make = |x, y| Placement {
+            position: crate::game_state::Position::new(x, y),
+            shape: shape.clone(),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        let placements = vec![make(4, 0), make(0, 4)];
+        let game_state = create_test_game_state();
+
+        // Column-major tie-breaker puts (0, 4) first
+        let ranked = rank_placements_optimized_with_tiebreak(&placements, &game_state, |a, b| {
+            (a.position.x, a.position.y).cmp(&(b.position.x, b.position.y))
+        });
+
+        assert_eq!(ranked[0].0.position, crate::game_state::Position::new(0, 4));
+    }
+
+    #[test]
+    fn test_select_best_placement_parallel_matches_sequential() {
+        let placements = create_test_placements();
+        let game_state = create_test_game_state();
+
+        let sequential = select_best_placement_optimized(&placements, &game_state);
+        assert_eq!(select_best_placement_parallel(&placements, &game_state, false), sequential);
+        assert_eq!(select_best_placement_parallel(&placements, &game_state, true), sequential);
+        assert!(select_best_placement_parallel(&[], &game_state, true).is_none());
+    }
+
+    #[test]
+    fn test_parallel_scoring_matches_sequential_on_large_board() {
+        use crate::game_state::Position;
+        use crate::placement::find_all_valid_placements;
+
+        // 30x20 board with a grid of single-cell islands for many placements
+        let islands: Vec<Position> = (0..4)
+            .flat_map(|row| (0..7).map(move |col| Position::new(2 + col * 4, 2 + row * 5)))
+            .collect();
+        let game_state = GameState::builder_from_size(30, 20)
+            .with_territory(1, &islands)
+            .with_territory(2, &[Position::new(29, 19)])
+            .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+        assert!(placements.len() >= 50);
+
+        let expected = BatchScorer::new().score_all(&placements, &game_state);
+        assert_eq!(BatchScorer::new().score_all_parallel(&placements, &game_state, false), expected);
+        assert_eq!(BatchScorer::new().score_all_parallel(&placements, &game_state, true), expected);
+    }
 }
@@ -0,0 +1,208 @@
+/// Pattern-based move recognition
+///
+/// Some piece shapes have a known best use regardless of the fine details
+/// of the board: a straight line is best laid out into open space, a solid
+/// square is best spent walling off the opponent. A `PatternBook` maps a
+/// shape class (`Pattern`) to a placement preference that overrides the
+/// generic scorer for pieces of that class before the late game.
+
+use crate::game_state::{CellState, GameState, Grid, Position, Shape, Symmetry};
+use crate::placement::Placement;
+use crate::utils::manhattan_distance;
+
+/// Placement preference stored in a `PatternBook`
+pub type PatternSelector = fn(&[Placement], &GameState) -> Option<Placement>;
+
+/// Shape class: symmetry, size and extent of the filled cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pattern {
+    pub symmetry: Symmetry,
+    /// Number of filled cells
+    pub area: usize,
+    /// Width of the filled cells' bounding box
+    pub width: usize,
+    /// Height of the filled cells' bounding box
+    pub height: usize,
+}
+
+impl Pattern {
+    /// Class of `shape`, or `None` for a shape with no filled cells
+    pub fn of(shape: &Shape) -> Option<Pattern> {
+        let (_, _, width, height) = shape.bounding_box()?;
+        Some(Pattern {
+            symmetry: shape.symmetry_class(),
+            area: shape.area(),
+            width,
+            height,
+        })
+    }
+
+    /// Straight line of `length` cells, horizontal or vertical
+    pub fn line(length: usize, horizontal: bool) -> Pattern {
+        let (width, height) = if horizontal { (length, 1) } else { (1, length) };
+        Pattern { symmetry: Symmetry::Full, area: length, width, height }
+    }
+
+    /// Solid `side` x `side` square
+    pub fn square(side: usize) -> Pattern {
+        Pattern { symmetry: Symmetry::Full, area: side * side, width: side, height: side }
+    }
+}
+
+/// Shape classes mapped to placement preferences
+#[derive(Debug, Clone)]
+pub struct PatternBook {
+    entries: Vec<(Pattern, PatternSelector)>,
+}
+
+impl PatternBook {
+    /// Create a book from explicit entries; the first matching entry wins
+    pub fn new(entries: Vec<(Pattern, PatternSelector)>) -> Self {
+        PatternBook { entries }
+    }
+
+    /// Preference for `shape`'s class, if the book has one
+    pub fn lookup(&self, shape: &Shape) -> Option<PatternSelector> {
+        let pattern = Pattern::of(shape)?;
+        self.entries
+            .iter()
+            .find(|(entry, _)| *entry == pattern)
+            .map(|&(_, select)| select)
+    }
+}
+
+impl Default for PatternBook {
+    /// Lines of 3 to 5 cells go toward open space; 2x2 and 3x3 squares
+    /// go toward the opponent
+    fn default() -> Self {
+        let mut entries: Vec<(Pattern, PatternSelector)> = Vec::new();
+        for length in 3..=5 {
+            entries.push((Pattern::line(length, true), toward_open_space));
+            entries.push((Pattern::line(length, false), toward_open_space));
+        }
+        for side in 2..=3 {
+            entries.push((Pattern::square(side), toward_opponent));
+        }
+        PatternBook::new(entries)
+    }
+}
+
+/// Placement whose new cells border the most empty cells
+///
+/// A line bridging into open space keeps the most room to grow. Ties go to
+/// more cells added, then to the first placement.
+pub fn toward_open_space(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+    let mut best: Option<(&Placement, (usize, usize))> = None;
+    for placement in placements {
+        let after = placement.simulate(game_state.grid(), game_state.player_number());
+        let key = (open_neighbors(placement, game_state.grid(), &after), placement.cells_added);
+        if best.is_none_or(|(_, best_key)| key > best_key) {
+            best = Some((placement, key));
+        }
+    }
+    best.map(|(placement, _)| placement.clone())
+}
+
+/// Placement whose new cells come closest to the opponent
+///
+/// Solid blocks make the sturdiest walls. Ties go to more cells added, then
+/// to the first placement; with no opponent cells only `cells_added`
+/// counts.
+pub fn toward_opponent(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+    let opponent = game_state.get_opponent_positions();
+    let distance = |placement: &Placement| {
+        new_cells(placement, game_state.grid())
+            .flat_map(|cell| opponent.iter().map(move |&o| manhattan_distance(cell, o)))
+            .min()
+            .unwrap_or(usize::MAX)
+    };
+
+    let mut best: Option<(&Placement, (usize, usize))> = None;
+    for placement in placements {
+        let key = (distance(placement), usize::MAX - placement.cells_added);
+        if best.is_none_or(|(_, best_key)| key < best_key) {
+            best = Some((placement, key));
+        }
+    }
+    best.map(|(placement, _)| placement.clone())
+}
+
+/// Cells of `placement` that are empty on `grid`
+fn new_cells<'a>(placement: &'a Placement, grid: &'a Grid) -> impl Iterator<Item = Position> + 'a {
+    placement
+        .get_absolute_positions()
+        .into_iter()
+        .filter(|&pos| grid.get(pos) == Some(CellState::Empty))
+}
+
+/// Distinct empty cells of `after` next to the cells `placement` claims on `before`
+fn open_neighbors(placement: &Placement, before: &Grid, after: &Grid) -> usize {
+    let mut open: Vec<Position> = new_cells(placement, before)
+        .flat_map(|cell| [(1, 0), (-1, 0), (0, 1), (0, -1)].map(|(dx, dy)| cell.checked_add(dx, dy)))
+        .flatten()
+        .filter(|&pos| after.get(pos) == Some(CellState::Empty))
+        .collect();
+    open.sort_by_key(|p| (p.y, p.x));
+    open.dedup();
+    open.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::placement::find_all_valid_placements;
+
+    fn shape(rows: &[&str]) -> Shape {
+        Shape::from_chars(rows[0].len(), rows.len(), rows.iter().map(|r| r.chars().collect()).collect())
+    }
+
+    #[test]
+    fn test_pattern_of_ignores_framing() {
+        assert_eq!(Pattern::of(&shape(&["...", "###"])), Some(Pattern::line(3, true)));
+        assert_eq!(Pattern::of(&shape(&[".#", ".#", ".#"])), Some(Pattern::line(3, false)));
+        assert_eq!(Pattern::of(&shape(&["##", "##"])), Some(Pattern::square(2)));
+        assert_eq!(Pattern::of(&shape(&["..", ".."])), None);
+    }
+
+    #[test]
+    fn test_lookup_matches_known_classes() {
+        let book = PatternBook::default();
+
+        assert!(book.lookup(&shape(&["####"])).is_some());
+        assert!(book.lookup(&shape(&["##", "##"])).is_some());
+        assert!(book.lookup(&shape(&["##", "#."])).is_none());
+        assert!(book.lookup(&shape(&["##"])).is_none());
+        assert!(PatternBook::new(Vec::new()).lookup(&shape(&["###"])).is_none());
+    }
+
+    #[test]
+    fn test_line_bridges_into_open_space() {
+        // Our wall hems in the cells below (3, 5), so the line that ends
+        // there borders less open space than the ones further up
+        let game_state = GameState::builder_from_size(7, 7)
+            .with_territory(1, &[Position::new(3, 3), Position::new(2, 5), Position::new(4, 5)])
+            .with_territory(2, &[Position::new(6, 0)])
+            .with_piece(shape(&["#", "#", "#"]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+        assert!(placements.iter().any(|p| p.position == Position::new(3, 3)));
+
+        let select = PatternBook::default().lookup(&game_state.current_piece).unwrap();
+        let best = select(&placements, &game_state).unwrap();
+        assert_eq!(best.position, Position::new(3, 2));
+        assert!(select(&[], &game_state).is_none());
+    }
+
+    #[test]
+    fn test_square_moves_toward_opponent() {
+        let game_state = GameState::builder_from_size(8, 8)
+            .with_territory(1, &[Position::new(3, 3)])
+            .with_territory(2, &[Position::new(7, 7)])
+            .with_piece(shape(&["##", "##"]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+
+        let best = toward_opponent(&placements, &game_state).unwrap();
+        assert_eq!(best.position, Position::new(3, 3));
+    }
+}
@@ -0,0 +1,112 @@
+/// Placement score type
+///
+/// `Score` wraps an `f32` with a total order so scores can be sorted and
+/// maximised without `partial_cmp` fallbacks. NaN orders below every
+/// other value, as if it were negative infinity.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul};
+
+/// Score of a placement; higher is better
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Score(pub f32);
+
+impl Score {
+    /// Raw score value
+    pub fn value(self) -> f32 {
+        self.0
+    }
+
+    /// Value used for ordering: NaN becomes negative infinity and -0.0
+    /// becomes 0.0, so equal-looking scores compare equal
+    fn ordering_key(self) -> f32 {
+        if self.0.is_nan() {
+            f32::NEG_INFINITY
+        } else {
+            self.0 + 0.0
+        }
+    }
+}
+
+impl From<f32> for Score {
+    fn from(value: f32) -> Self {
+        Score(value)
+    }
+}
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ordering_key().total_cmp(&other.ordering_key())
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        Score(self.0 + other.0)
+    }
+}
+
+impl Mul<f32> for Score {
+    type Output = Score;
+
+    fn mul(self, factor: f32) -> Score {
+        Score(self.0 * factor)
+    }
+}
+
+impl Sum for Score {
+    fn sum<I: Iterator<Item = Score>>(iter: I) -> Score {
+        iter.fold(Score::default(), Add::add)
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_ordering() {
+        let mut scores = [Score(2.5), Score(f32::NAN), Score(-1.0), Score(10.0)];
+        scores.sort();
+
+        assert_eq!(scores[0].ordering_key(), f32::NEG_INFINITY);
+        assert_eq!(&scores[1..], &[Score(-1.0), Score(2.5), Score(10.0)]);
+        assert_eq!(Score(-0.0), Score(0.0));
+        assert_eq!(Score(f32::NAN), Score(f32::NEG_INFINITY));
+        assert!(Score(f32::NAN) < Score(f32::MIN));
+    }
+
+    #[test]
+    fn test_score_arithmetic_and_display() {
+        let total: Score = [Score(1.0), Score(2.0)].into_iter().sum();
+
+        assert_eq!(total, Score(3.0));
+        assert_eq!(Score(1.5) + Score(2.0), Score(3.5));
+        assert_eq!(Score(1.5) * 2.0, Score(3.0));
+        assert_eq!(Score(1.23456).to_string(), "1.23");
+    }
+}
@@ -0,0 +1,414 @@
+/// Alpha-beta game-tree search over evaluator scores
+///
+/// Each move is worth its `evaluate_placement` score to the player making
+/// it; a line of play is worth the root player's move scores minus the
+/// opponent's. Successor states come from `GameState::apply_placement`,
+/// which keeps the current piece since the next one is unknown. A
+/// `TranspositionTable` keyed by Zobrist hash lets positions reached by
+/// different move orders share work.
+
+use crate::game_state::GameState;
+use crate::placement::{valid_placements_iter, Placement};
+use super::evaluator::evaluate_placement;
+use super::optimization::CacheStats;
+use super::SearchTimeout;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Deepest iteration `select_move_iterative_deepening` will start
+pub const MAX_SEARCH_DEPTH: u8 = 32;
+
+/// How a stored score relates to the true value of a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundType {
+    /// The score is the exact value
+    Exact,
+    /// The search failed high: the true value is at least the score
+    Lower,
+    /// The search failed low: the true value is at most the score
+    Upper,
+}
+
+/// Result of searching one position
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranspositionEntry {
+    /// Plies searched below the position
+    pub depth: u8,
+    pub score: f32,
+    pub bound: BoundType,
+    /// Best (or refuting) placement found, tried first on revisits
+    pub best_move: Option<Placement>,
+}
+
+/// Search results keyed by `GameState::zobrist_hash`
+///
+/// The hash covers the board and the player to move but not the piece, so
+/// a table is only valid for one search (one root state and piece). Scores
+/// are sums over the remaining plies, so an entry is only reused at the
+/// same depth it was searched to.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: HashMap<u64, TranspositionEntry>,
+}
+
+impl TranspositionTable {
+    /// Create an empty table
+    pub fn new() -> Self {
+        TranspositionTable {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Entry for a position, if one was stored
+    pub fn get(&self, hash: u64) -> Option<&TranspositionEntry> {
+        self.entries.get(&hash)
+    }
+
+    /// Store an entry, replacing any previous one for the position
+    pub fn insert(&mut self, hash: u64, entry: TranspositionEntry) {
+        self.entries.insert(hash, entry);
+    }
+
+    /// Clear the table
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Get table statistics
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            capacity: self.entries.capacity(),
+        }
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Best achievable value of `game_state` searched `depth` more plies
+///
+/// `maximizing_player` is whether the player to move is the root player.
+/// The value is the sum of evaluator scores along the principal line,
+/// counted positive for the root player's moves and negative for the
+/// opponent's. A player with no valid placement ends the line. Values
+/// outside `(alpha, beta)` are bounds rather than exact.
+pub fn alpha_beta(
+    game_state: &GameState,
+    depth: u8,
+    alpha: f32,
+    beta: f32,
+    maximizing_player: bool,
+) -> f32 {
+    let mut table = TranspositionTable::new();
+    alpha_beta_with_table(game_state, depth, alpha, beta, maximizing_player, &mut table)
+}
+
+/// `alpha_beta` sharing results through a transposition table
+///
+/// Stored entries at the same depth narrow the window or answer the node
+/// outright, and a stored best move is searched first.
+pub fn alpha_beta_with_table(
+    game_state: &GameState,
+    depth: u8,
+    alpha: f32,
+    beta: f32,
+    maximizing_player: bool,
+    table: &mut TranspositionTable,
+) -> f32 {
+    match search(game_state, depth, alpha, beta, maximizing_player, table, None) {
+        Ok(value) => value,
+        Err(SearchTimeout) => unreachable!("search without a deadline cannot time out"),
+    }
+}
+
+/// Alpha-beta node search, giving up at `deadline` if one is set
+fn search(
+    game_state: &GameState,
+    depth: u8,
+    mut alpha: f32,
+    mut beta: f32,
+    maximizing_player: bool,
+    table: &mut TranspositionTable,
+    deadline: Option<Instant>,
+) -> Result<f32, SearchTimeout> {
+    if deadline.is_some_and(|d| Instant::now() >= d) {
+        return Err(SearchTimeout);
+    }
+    if depth == 0 {
+        return Ok(0.0);
+    }
+
+    let hash = game_state.zobrist_hash();
+    let mut tt_move = None;
+    if let Some(entry) = table.get(hash) {
+        if entry.depth == depth {
+            match entry.bound {
+                BoundType::Exact => return Ok(entry.score),
+                BoundType::Lower => alpha = alpha.max(entry.score),
+                BoundType::Upper => beta = beta.min(entry.score),
+            }
+            if alpha >= beta {
+                return Ok(entry.score);
+            }
+        }
+        tt_move = entry.best_move.clone();
+    }
+    let (original_alpha, original_beta) = (alpha, beta);
+
+    let sign = if maximizing_player { 1.0 } else { -1.0 };
+    let mut value = if maximizing_player { f32::NEG_INFINITY } else { f32::INFINITY };
+    let mut best_move = None;
+
+    // The stored best move was valid in this exact position, so try it first
+    let rest = valid_placements_iter(game_state).filter(|p| Some(p) != tt_move.as_ref());
+    for placement in tt_move.clone().into_iter().chain(rest) {
+        let gain = sign * evaluate_placement(&placement, game_state).value();
+        let child = game_state.apply_placement(&placement);
+        // Shift the window by this move's gain so the child searches its own share
+        let score = gain
+            + search(&child, depth - 1, alpha - gain, beta - gain, !maximizing_player, table, deadline)?;
+
+        let improved = if maximizing_player { score > value } else { score < value };
+        if improved || best_move.is_none() {
+            value = score;
+            best_move = Some(placement);
+        }
+        if maximizing_player {
+            alpha = alpha.max(value);
+        } else {
+            beta = beta.min(value);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    if best_move.is_none() {
+        return Ok(0.0);
+    }
+
+    let bound = if value <= original_alpha {
+        BoundType::Upper
+    } else if value >= original_beta {
+        BoundType::Lower
+    } else {
+        BoundType::Exact
+    };
+    table.insert(hash, TranspositionEntry { depth, score: value, bound, best_move });
+    Ok(value)
+}
+
+/// Alpha-beta move selection, a drop-in replacement for `select_move_default`
+///
+/// Searches `depth` plies (depth 2 = my move and the opponent's best reply;
+/// depth 0 behaves like depth 1, i.e. the best evaluator score). Ties go to
+/// the first placement.
+pub fn select_move_alphabeta(
+    placements: &[Placement],
+    game_state: &GameState,
+    depth: u8,
+) -> Option<Placement> {
+    let mut table = TranspositionTable::new();
+    search_root(placements, game_state, depth, &mut table, None).unwrap_or(None)
+}
+
+/// Anytime alpha-beta within a wall-clock budget
+///
+/// Searches depth 1, 2, 3, ... (up to `MAX_SEARCH_DEPTH`) with one shared
+/// transposition table, so each iteration tries the previous iteration's
+/// best moves first. Returns the result of the deepest iteration that
+/// completed. Depth 1 always runs to completion, so a result is returned
+/// whenever `placements` is non-empty, even with a zero budget.
+pub fn select_move_iterative_deepening(
+    placements: &[Placement],
+    game_state: &GameState,
+    budget: Duration,
+) -> Option<Placement> {
+    let deadline = Instant::now() + budget;
+    let mut table = TranspositionTable::new();
+    let mut best_so_far = search_root(placements, game_state, 1, &mut table, None).unwrap_or(None);
+
+    for depth in 2..=MAX_SEARCH_DEPTH {
+        match search_root(placements, game_state, depth, &mut table, Some(deadline)) {
+            Ok(result) => best_so_far = result,
+            Err(SearchTimeout) => break,
+        }
+    }
+
+    best_so_far
+}
+
+/// Root of the alpha-beta search, giving up at `deadline` if one is set
+fn search_root(
+    placements: &[Placement],
+    game_state: &GameState,
+    depth: u8,
+    table: &mut TranspositionTable,
+    deadline: Option<Instant>,
+) -> Result<Option<Placement>, SearchTimeout> {
+    let mut best: Option<(&Placement, f32)> = None;
+    let mut alpha = f32::NEG_INFINITY;
+
+    for placement in placements {
+        let gain = evaluate_placement(placement, game_state).value();
+        let child = game_state.apply_placement(placement);
+        let score = gain
+            + search(
+                &child,
+                depth.saturating_sub(1),
+                alpha - gain,
+                f32::INFINITY - gain,
+                false,
+                table,
+                deadline,
+            )?;
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((placement, score));
+            alpha = alpha.max(score);
+        }
+    }
+
+    Ok(best.map(|(placement, _)| placement.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{Position, Shape};
+    use crate::placement::find_all_valid_placements;
+
+    /// Exhaustive minimax over the same values, without pruning
+    fn minimax(game_state: &GameState, depth: u8, maximizing_player: bool) -> f32 {
+        if depth == 0 {
+            return 0.0;
+        }
+        let sign = if maximizing_player { 1.0 } else { -1.0 };
+        let scores: Vec<f32> = find_all_valid_placements(game_state)
+            .iter()
+            .map(|p| {
+                let gain = sign * evaluate_placement(p, game_state).value();
+                gain + minimax(&game_state.apply_placement(p), depth - 1, !maximizing_player)
+            })
+            .collect();
+
+        if scores.is_empty() {
+            0.0
+        } else if maximizing_player {
+            scores.into_iter().fold(f32::NEG_INFINITY, f32::max)
+        } else {
+            scores.into_iter().fold(f32::INFINITY, f32::min)
+        }
+    }
+
+    fn small_game_state() -> GameState {
+        GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1)])
+            .with_territory(2, &[Position::new(3, 3)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build()
+    }
+
+    #[test]
+    fn test_alpha_beta_matches_minimax() {
+        let game_state = small_game_state();
+
+        for depth in 0..=3 {
+            assert_eq!(
+                alpha_beta(&game_state, depth, f32::NEG_INFINITY, f32::INFINITY, true),
+                minimax(&game_state, depth, true),
+                "depth {}",
+                depth
+            );
+        }
+    }
+
+    #[test]
+    fn test_select_move_alphabeta_matches_exhaustive_choice() {
+        let game_state = small_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        for depth in 1..=3 {
+            let mut expected: Option<(&Placement, f32)> = None;
+            for placement in &placements {
+                let score = evaluate_placement(placement, &game_state).value()
+                    + minimax(&game_state.apply_placement(placement), depth - 1, false);
+                if expected.is_none_or(|(_, best)| score > best) {
+                    expected = Some((placement, score));
+                }
+            }
+
+            assert_eq!(
+                select_move_alphabeta(&placements, &game_state, depth).as_ref(),
+                expected.map(|(p, _)| p),
+                "depth {}",
+                depth
+            );
+        }
+    }
+
+    #[test]
+    fn test_select_move_alphabeta_depth_one_is_evaluator_greedy() {
+        let game_state = small_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        let best = select_move_alphabeta(&placements, &game_state, 1).unwrap();
+        let best_score = evaluate_placement(&best, &game_state);
+
+        assert!(placements.iter().all(|p| evaluate_placement(p, &game_state) <= best_score));
+        assert!(select_move_alphabeta(&[], &game_state, 2).is_none());
+    }
+
+    #[test]
+    fn test_transposition_table_reuse() {
+        let game_state = small_game_state();
+        let expected = minimax(&game_state, 3, true);
+        let mut table = TranspositionTable::new();
+
+        let first = alpha_beta_with_table(&game_state, 3, f32::NEG_INFINITY, f32::INFINITY, true, &mut table);
+        let entries = table.stats().entries;
+        let second = alpha_beta_with_table(&game_state, 3, f32::NEG_INFINITY, f32::INFINITY, true, &mut table);
+
+        assert_eq!(first, expected);
+        assert_eq!(second, expected);
+        assert_eq!(table.stats().entries, entries);
+
+        let root = table.get(game_state.zobrist_hash()).unwrap();
+        assert_eq!((root.depth, root.bound, root.score), (3, BoundType::Exact, expected));
+        assert!(root.best_move.is_some());
+
+        table.clear();
+        assert_eq!(table.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_iterative_deepening_zero_budget_is_depth_one() {
+        let game_state = small_game_state();
+        let placements = find_all_valid_placements(&game_state);
+
+        assert_eq!(
+            select_move_iterative_deepening(&placements, &game_state, Duration::ZERO),
+            select_move_alphabeta(&placements, &game_state, 1)
+        );
+        assert!(select_move_iterative_deepening(&[], &game_state, Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_iterative_deepening_finishes_small_board() {
+        // A 1x3 corridor allows one move each, so every depth completes quickly
+        let game_state = GameState::builder_from_size(3, 1)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &[Position::new(2, 0)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+
+        let result = select_move_iterative_deepening(&placements, &game_state, Duration::from_secs(5));
+
+        assert_eq!(result, select_move_alphabeta(&placements, &game_state, MAX_SEARCH_DEPTH));
+        assert!(result.is_some());
+    }
+}
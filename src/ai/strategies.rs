@@ -5,45 +5,122 @@
 /// - Conservative: Prioritize stable positions
 /// - Aggressive: Attack opponent weaknesses
 
+use crate::game_state::{CellState, GameState, Position};
 use crate::placement::Placement;
+use crate::utils::manhattan_distance;
+use super::territory::compute_voronoi_partition;
+use rand::{Rng, RngExt};
+use std::cmp::Reverse;
 
 /// Greedy expansion strategy
 /// 
 /// Prioritizes maximum territory expansion regardless of risk.
 /// Best for early game where board is large and options plentiful.
-pub fn greedy_expansion(placements: &[Placement]) -> Option<Placement> {
-    if placements.is_empty() {
-        return None;
+/// Ties are broken by distance to `center` (the grid center if `None`).
+pub fn greedy_expansion(
+    placements: &[Placement],
+    game_state: &GameState,
+    center: Option<Position>,
+) -> Option<Placement> {
+    let center = center.unwrap_or_else(|| {
+        Position::new(game_state.grid().width / 2, game_state.grid().height / 2)
+    });
+    max_cells_near_center(placements.iter(), center)
+}
+
+/// Greedy expansion over a stream of placements
+///
+/// Same choice as `greedy_expansion` for placements given in row-major
+/// order, but consumes them one at a time and stops as soon as a
+/// placement claims every non-overlapping piece cell at `center` itself,
+/// since nothing later can beat it.
+pub fn greedy_expansion_iter(
+    placements: impl Iterator<Item = Placement>,
+    game_state: &GameState,
+    center: Option<Position>,
+) -> Option<Placement> {
+    let center = center.unwrap_or_else(|| {
+        Position::new(game_state.grid().width / 2, game_state.grid().height / 2)
+    });
+    let max_cells = game_state.current_piece.area().saturating_sub(1);
+    let key = |p: &Placement| (p.cells_added, Reverse(manhattan_distance(p.position, center)));
+
+    let mut best: Option<Placement> = None;
+    for placement in placements {
+        if best.as_ref().is_none_or(|b| key(&placement) > key(b)) {
+            let unbeatable = key(&placement) == (max_cells, Reverse(0));
+            best = Some(placement);
+            if unbeatable {
+                break;
+            }
+        }
     }
-    
-    // Find placement with maximum cells_added
+    best
+}
+
+/// Placement with the most cells added, closest to `center` on ties
+///
+/// Remaining ties go to the first placement in row-major order.
+fn max_cells_near_center<'a>(
+    placements: impl Iterator<Item = &'a Placement>,
+    center: Position,
+) -> Option<Placement> {
     placements
-        .iter()
-        .max_by_key(|p| p.cells_added)
+        .max_by_key(|p| {
+            (
+                p.cells_added,
+                Reverse(manhattan_distance(p.position, center)),
+                Reverse((p.position.y, p.position.x)),
+            )
+        })
         .cloned()
 }
 
 /// Conservative strategy
 /// 
-/// Prefers placements that touch more of own territory.
+/// Prefers placements that touch more of own territory, with a bonus
+/// for placements that merge two disconnected territory islands.
 /// Safer but less aggressive.
-pub fn conservative(placements: &[Placement]) -> Option<Placement> {
+pub fn conservative(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
     if placements.is_empty() {
         return None;
     }
     
-    // First by territory touches, then by cells_added
+    let components = game_state.grid().connected_components(game_state.player_number());
+    
+    // Score: (territory_touches * 2) + cells_added + merger bonus,
+    // ties broken by territory touches
     placements
         .iter()
-        .max_by(|a, b| {
-            match a.territory_touches.cmp(&b.territory_touches) {
-                std::cmp::Ordering::Equal => a.cells_added.cmp(&b.cells_added),
-                other => other,
-            }
+        .max_by_key(|p| {
+            let merger_bonus = if bridges_components(p, &components) { 10 } else { 0 };
+            (p.territory_touches * 2 + p.cells_added + merger_bonus, p.territory_touches)
         })
         .cloned()
 }
 
+/// Check whether a placement connects two or more territory components
+///
+/// A component counts as connected if any placement cell lies on it or
+/// is 4-adjacent to it.
+fn bridges_components(placement: &Placement, components: &[Vec<Position>]) -> bool {
+    if components.len() < 2 {
+        return false;
+    }
+    
+    let cells = placement.get_absolute_positions();
+    let touched = components
+        .iter()
+        .filter(|component| {
+            component.iter().any(|c| {
+                cells.iter().any(|p| p.x.abs_diff(c.x) + p.y.abs_diff(c.y) <= 1)
+            })
+        })
+        .count();
+    
+    touched >= 2
+}
+
 /// Edge avoidance strategy
 /// 
 /// Avoids placements too close to board edges.
@@ -68,7 +145,7 @@ pub fn edge_avoidance(placements: &[Placement], grid_width: usize, grid_height:
     
     if safe_placements.is_empty() {
         // Fallback to greedy if no safe placements
-        greedy_expansion(placements)
+        max_cells_near_center(placements.iter(), Position::new(grid_width / 2, grid_height / 2))
     } else {
         safe_placements
             .iter()
@@ -86,17 +163,120 @@ pub fn balanced(placements: &[Placement]) -> Option<Placement> {
         return None;
     }
     
-    // Score by combined metric: (cells_added * 2) + territory_touches
+    // Score by combined metric: (cells_added * 2) + territory_touches,
+    // ties go to the first placement in row-major order
     placements
         .iter()
-        .max_by_key(|p| (p.cells_added * 2) + p.territory_touches)
+        .max_by_key(|p| {
+            (
+                (p.cells_added * 2) + p.territory_touches,
+                Reverse((p.position.y, p.position.x)),
+            )
+        })
         .cloned()
 }
 
+/// Softmax sampling over placements
+///
+/// Picks each placement with probability proportional to
+/// `exp(score_fn(p))`, so higher scores are more likely but never
+/// certain. Scale the scores to tune how greedy the sampling is. NaN
+/// scores are never picked unless every score is NaN.
+pub fn random_weighted(
+    placements: &[Placement],
+    score_fn: impl Fn(&Placement) -> f32,
+    rng: &mut impl Rng,
+) -> Option<Placement> {
+    let scores: Vec<f32> = placements.iter().map(score_fn).collect();
+    // Shift by the maximum so the largest weight is exp(0) = 1
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max.is_nan() || max == f32::NEG_INFINITY {
+        return placements.first().cloned();
+    }
+
+    let weights: Vec<f64> = scores
+        .iter()
+        .map(|&s| if s.is_nan() { 0.0 } else { f64::from(s - max).exp() })
+        .collect();
+    let mut target = rng.random::<f64>() * weights.iter().sum::<f64>();
+    for (placement, &weight) in placements.iter().zip(&weights) {
+        if target < weight {
+            return Some(placement.clone());
+        }
+        target -= weight;
+    }
+
+    // Rounding can leave `target` just above the last weight
+    placements
+        .iter()
+        .zip(&weights)
+        .rev()
+        .find(|&(_, &weight)| weight > 0.0)
+        .map(|(placement, _)| placement.clone())
+}
+
+/// Reactive strategy: answer the opponent's last move
+///
+/// Prefers placements whose new cells come closest to `opponent_last`
+/// (the cells the opponent just claimed, e.g. from `GameState::diff`),
+/// then the most `cells_added`, so we contest the space their piece was
+/// heading for. Without a last move only `cells_added` counts. Ties go to
+/// the first placement.
+pub fn reactive(
+    placements: &[Placement],
+    game_state: &GameState,
+    opponent_last: Option<&[Position]>,
+) -> Option<Placement> {
+    let targets = opponent_last.unwrap_or(&[]);
+    let distance = |placement: &Placement| {
+        placement
+            .get_absolute_positions()
+            .into_iter()
+            .filter(|&pos| game_state.grid().get(pos) == Some(CellState::Empty))
+            .flat_map(|pos| targets.iter().map(move |&t| manhattan_distance(pos, t)))
+            .min()
+            .unwrap_or(usize::MAX)
+    };
+
+    let mut best: Option<(&Placement, (Reverse<usize>, usize))> = None;
+    for placement in placements {
+        let key = (Reverse(distance(placement)), placement.cells_added);
+        if best.is_none_or(|(_, best_key)| key > best_key) {
+            best = Some((placement, key));
+        }
+    }
+    best.map(|(placement, _)| placement.clone())
+}
+
+/// Endgame strategy: maximize the territory we end up with
+///
+/// Scores each placement only by our claimed cells plus the empty cells
+/// we reach before the opponent after it is applied. With little room
+/// left, that count is the final score, so no other heuristic is used.
+/// Ties go to the first placement.
+pub fn endgame_maximizer(placements: &[Placement], game_state: &GameState) -> Option<Placement> {
+    let player = game_state.player_number();
+
+    let mut best: Option<(&Placement, usize)> = None;
+    for placement in placements {
+        let mut grid = game_state.grid().grid().clone();
+        grid.apply_placement(placement, player);
+        let gain = grid.count_territory(player) + compute_voronoi_partition(&grid).cells_for(player);
+        if best.is_none_or(|(_, best_gain)| gain > best_gain) {
+            best = Some((placement, gain));
+        }
+    }
+    best.map(|(placement, _)| placement.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game_state::{Position, Shape};
+    use crate::game_state::Shape;
+
+    fn create_empty_game_state() -> GameState {
+        GameState::builder_from_size(10, 10).build()
+    }
 
     fn create_placements() -> Vec<Placement> {
         let shape = Shape::from_chars(
@@ -129,7 +309,7 @@ mod tests {
     #[test]
     fn test_greedy_expansion_selects_max_cells() {
         let placements = create_placements();
-        let result = greedy_expansion(&placements);
+        let result = greedy_expansion(&placements, &create_empty_game_state(), None);
         
         assert!(result.is_some());
         let selected = result.unwrap();
@@ -140,11 +320,62 @@ mod tests {
     #[test]
     fn test_greedy_expansion_empty() {
         let placements: Vec<Placement> = vec![];
-        let result = greedy_expansion(&placements);
+        let result = greedy_expansion(&placements, &create_empty_game_state(), None);
         
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_greedy_expansion_tie_prefers_center() {
+        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let near = Placement {
+            position: Position::new(4, 5),
+            shape: shape.clone(),
+            cells_added: 3,
+            territory_touches: 1,
+        };
+        let far = Placement {
+            position: Position::new(0, 9),
+            shape,
+            cells_added: 3,
+            territory_touches: 1,
+        };
+        let game_state = create_empty_game_state();
+
+        for _ in 0..3 {
+            let forward = greedy_expansion(&[near.clone(), far.clone()], &game_state, None);
+            let backward = greedy_expansion(&[far.clone(), near.clone()], &game_state, None);
+            assert_eq!(forward.unwrap().position, near.position);
+            assert_eq!(backward.unwrap().position, near.position);
+        }
+
+        // An explicit center overrides the grid center
+        let corner = greedy_expansion(&[near.clone(), far.clone()], &game_state, Some(Position::new(0, 9)));
+        assert_eq!(corner.unwrap().position, far.position);
+    }
+
+    #[test]
+    fn test_balanced_tie_is_order_independent() {
+        let shape = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let a = Placement {
+            position: Position::new(7, 2),
+            shape: shape.clone(),
+            cells_added: 2,
+            territory_touches: 1,
+        };
+        let b = Placement {
+            position: Position::new(1, 6),
+            shape,
+            cells_added: 2,
+            territory_touches: 1,
+        };
+
+        let forward = balanced(&[a.clone(), b.clone()]).unwrap();
+        let backward = balanced(&[b, a.clone()]).unwrap();
+        assert_eq!(forward.position, a.position);
+        assert_eq!(backward.position, a.position);
+    }
+
     #[test]
     fn test_conservative_prefers_contacts() {
         let shape = Shape::from_chars(
@@ -167,7 +398,7 @@ mod tests {
             },
         ];
         
-        let result = conservative(&placements);
+        let result = conservative(&placements, &create_empty_game_state());
         
         assert!(result.is_some());
         let selected = result.unwrap();
@@ -258,4 +489,124 @@ mod tests {
         // Should pick one of them (tie case)
         assert!(result.is_some());
     }
+
+    #[test]
+    fn test_conservative_prefers_bridging_placement() {
+        // Two separated 3-cell territories on row 2
+        let islands: Vec<Position> = [0, 1, 2, 4, 5, 6].iter().map(|&x| Position::new(x, 2)).collect();
+        let shape = Shape::from_chars(1, 2, vec![vec!['#'], vec!['#']]);
+        let game_state = GameState::builder_from_size(7, 4)
+            .with_territory(1, &islands)
+            .with_piece(shape.clone())
+            .build();
+
+        // Vertical piece in the gap column joins both islands
+        let bridging = Placement {
+            position: Position { x: 3, y: 1 },
+            shape: shape.clone(),
+            cells_added: 2,
+            territory_touches: 1,
+        };
+        // Same piece hanging off the left island only
+        let non_bridging = Placement {
+            position: Position { x: 0, y: 0 },
+            shape,
+            cells_added: 2,
+            territory_touches: 1,
+        };
+
+        let placements = vec![bridging.clone(), non_bridging.clone()];
+        assert_eq!(conservative(&placements, &game_state), Some(bridging.clone()));
+
+        // Order of candidates doesn't matter
+        let placements = vec![non_bridging, bridging.clone()];
+        assert_eq!(conservative(&placements, &game_state), Some(bridging));
+    }
+
+    #[test]
+    fn test_greedy_expansion_iter_matches_slice_version() {
+        use crate::placement::{find_all_valid_placements, valid_placements_iter};
+
+        let game_state = GameState::builder_from_size(9, 7)
+            .with_territory(1, &[Position::new(2, 2), Position::new(6, 4)])
+            .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+
+        for center in [None, Some(Position::new(0, 0)), Some(Position::new(6, 4))] {
+            assert_eq!(
+                greedy_expansion_iter(valid_placements_iter(&game_state), &game_state, center),
+                greedy_expansion(&placements, &game_state, center)
+            );
+        }
+        assert!(greedy_expansion_iter(std::iter::empty(), &game_state, None).is_none());
+    }
+
+    #[test]
+    fn test_random_weighted_follows_scores() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let placements = create_placements();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut counts = [0; 3];
+        for _ in 0..1000 {
+            let pick = random_weighted(&placements, |p| p.cells_added as f32, &mut rng).unwrap();
+            let index = placements.iter().position(|p| *p == pick).unwrap();
+            counts[index] += 1;
+        }
+
+        // Weights are e^3 : e^2 : e^4, so the best placement dominates but
+        // every placement gets sampled
+        assert!(counts[2] > counts[0] && counts[0] > counts[1] && counts[1] > 0);
+
+        // An overwhelming score is effectively greedy; NaN is never chosen
+        let greedy = random_weighted(&placements, |p| p.cells_added as f32 * 100.0, &mut rng);
+        assert_eq!(greedy.unwrap().position, Position::new(9, 9));
+        let nan_first = |p: &Placement| if p.position.x == 0 { f32::NAN } else { 0.0 };
+        for _ in 0..20 {
+            let pick = random_weighted(&placements, nan_first, &mut rng).unwrap();
+            assert_ne!(pick.position, Position::new(0, 0));
+        }
+        assert!(random_weighted(&[], |_| 0.0, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_reactive_answers_last_move() {
+        use crate::placement::find_all_valid_placements;
+
+        let game_state = GameState::builder_from_size(8, 8)
+            .with_territory(1, &[Position::new(3, 3)])
+            .with_territory(2, &[Position::new(6, 0), Position::new(7, 0), Position::new(7, 1)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let placements = find_all_valid_placements(&game_state);
+        let last = [Position::new(7, 1)];
+
+        // The domino claiming (4, 3) is closest to (7, 1)
+        let best = reactive(&placements, &game_state, Some(&last)).unwrap();
+        assert_eq!(best.position, Position::new(3, 3));
+        assert_eq!(reactive(&placements, &game_state, None), placements.first().cloned());
+        assert!(reactive(&[], &game_state, Some(&last)).is_none());
+    }
+
+    #[test]
+    fn test_endgame_maximizer_takes_contested_cells() {
+        use crate::game_state::Grid;
+        use crate::placement::find_all_valid_placements;
+
+        // Both placements claim one cell, but only the one toward the
+        // opponent wins cells we would otherwise reach second
+        let grid: Grid = ".......\n...@...\n......$".parse().unwrap();
+        let mut game_state = GameState::builder_from_size(grid.width, grid.height)
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        game_state.set_grid(grid);
+        let placements = find_all_valid_placements(&game_state);
+        assert_eq!(placements.len(), 2);
+
+        let best = endgame_maximizer(&placements, &game_state).unwrap();
+        assert_eq!(best.position, Position::new(3, 1));
+        assert!(endgame_maximizer(&[], &game_state).is_none());
+    }
 }
@@ -0,0 +1,228 @@
+/// Territory partition analysis
+///
+/// Splits the empty part of the board between the two players by which
+/// one can reach each cell first (a Voronoi partition under BFS distance),
+/// and estimates final territory with random self-play rollouts.
+
+use crate::game_state::{GameState, Grid};
+use crate::placement::{valid_placements_iter, Placement};
+use crate::sim::PieceGenerator;
+use super::strategies::greedy_expansion_iter;
+use rand::Rng;
+
+/// Turn cap (both players) for a single Monte Carlo rollout
+pub const ROLLOUT_MAX_TURNS: usize = 60;
+
+/// Result of a Voronoi partition of the empty cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoronoiMap {
+    /// Empty cells player 1 reaches first
+    pub player1_cells: usize,
+    /// Empty cells player 2 reaches first
+    pub player2_cells: usize,
+    /// Empty cells reached at the same time or by neither player
+    pub neutral_cells: usize,
+    /// Per-cell owner in row-major order (`y * width + x`)
+    ///
+    /// Occupied cells hold their owner; empty cells hold the player that
+    /// reaches them first, or `None` if neutral.
+    pub ownership: Vec<Option<u8>>,
+}
+
+impl VoronoiMap {
+    /// Empty cells claimed by `player`
+    pub fn cells_for(&self, player: u8) -> usize {
+        match player {
+            1 => self.player1_cells,
+            2 => self.player2_cells,
+            _ => 0,
+        }
+    }
+}
+
+/// Partition the empty cells between players 1 and 2
+///
+/// Equivalent to a simultaneous BFS from both territories through empty
+/// cells: each empty cell goes to the player with the shorter distance,
+/// and ties are neutral.
+pub fn compute_voronoi_partition(grid: &Grid) -> VoronoiMap {
+    let (player1, player2) = grid.voronoi_distance_pair();
+
+    let mut map = VoronoiMap {
+        player1_cells: 0,
+        player2_cells: 0,
+        neutral_cells: 0,
+        ownership: Vec::with_capacity(grid.cells.len()),
+    };
+
+    for (pos, state) in grid.iter_positions() {
+        if let Some(owner) = state.player_num() {
+            map.ownership.push(Some(owner));
+            continue;
+        }
+
+        let owner = match (player1[pos.y][pos.x], player2[pos.y][pos.x]) {
+            (Some(d1), Some(d2)) if d1 < d2 => Some(1),
+            (Some(d1), Some(d2)) if d2 < d1 => Some(2),
+            (Some(_), None) => Some(1),
+            (None, Some(_)) => Some(2),
+            _ => None,
+        };
+        match owner {
+            Some(1) => map.player1_cells += 1,
+            Some(_) => map.player2_cells += 1,
+            None => map.neutral_cells += 1,
+        }
+        map.ownership.push(owner);
+    }
+
+    map
+}
+
+/// Estimate the final territory share after `placement` by random rollouts
+///
+/// Each of the `simulations` rollouts starts from the post-placement board
+/// with the opponent to move, deals random pieces seeded from `rng`, and
+/// plays `greedy_expansion` for both players until neither can move or
+/// `ROLLOUT_MAX_TURNS` is reached. Returns the current player's average
+/// share of the occupied cells (`mine / (mine + theirs)`) at the end of
+/// the rollouts. With zero simulations the share right after the
+/// placement is returned.
+pub fn monte_carlo_territory(
+    game_state: &GameState,
+    placement: &Placement,
+    simulations: usize,
+    rng: &mut impl Rng,
+) -> f32 {
+    let player = game_state.player_number();
+    let opponent = game_state.opponent_number();
+    let mut start = game_state.grid().grid().clone();
+    start.apply_placement(placement, player);
+
+    let share = |grid: &Grid| {
+        let mine = grid.count_territory(player) as f32;
+        let total = mine + grid.count_territory(opponent) as f32;
+        if total > 0.0 { mine / total } else { 0.0 }
+    };
+
+    if simulations == 0 {
+        return share(&start);
+    }
+
+    let total: f32 = (0..simulations)
+        .map(|_| {
+            let mut pieces = PieceGenerator::new(rng.next_u64());
+            share(&rollout(start.clone(), [opponent, player], &mut pieces))
+        })
+        .sum();
+    total / simulations as f32
+}
+
+/// Play greedy moves in `order` until both players are stuck or the cap
+pub fn rollout(mut grid: Grid, order: [u8; 2], pieces: &mut PieceGenerator) -> Grid {
+    let mut active = [true, true];
+    let mut turn = 0;
+
+    while active.iter().any(|&a| a) && turn < ROLLOUT_MAX_TURNS {
+        let index = turn % 2;
+        turn += 1;
+        if !active[index] {
+            continue;
+        }
+
+        let state = GameState::new(order[index], grid.clone(), pieces.next_piece());
+        match greedy_expansion_iter(valid_placements_iter(&state), &state, None) {
+            Some(choice) => {
+                grid.apply_placement(&choice, order[index]);
+            }
+            None => active[index] = false,
+        }
+    }
+
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::{Position, Shape};
+    use rand::SeedableRng;
+    use crate::placement::find_all_valid_placements;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_voronoi_symmetric_board() {
+        let grid = GameState::builder_from_size(5, 1)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &[Position::new(4, 0)])
+            .build()
+            .into_grid();
+        let map = compute_voronoi_partition(&grid);
+
+        assert_eq!(map.player1_cells, 1);
+        assert_eq!(map.player2_cells, 1);
+        assert_eq!(map.neutral_cells, 1);
+        assert_eq!(map.ownership, vec![Some(1), Some(1), None, Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn test_voronoi_counts_cover_empty_cells() {
+        let grid = GameState::builder_from_size(8, 6).symmetric_start().build().into_grid();
+        let map = compute_voronoi_partition(&grid);
+
+        assert_eq!(
+            map.player1_cells + map.player2_cells + map.neutral_cells,
+            grid.count_empty()
+        );
+        assert_eq!(map.ownership.len(), 48);
+        assert_eq!(map.cells_for(1), map.cells_for(2));
+    }
+
+    #[test]
+    fn test_voronoi_walled_off_region() {
+        // Player 1 cannot cross player 2's wall, so the right side is all
+        // player 2's; next to the wall player 2 is closer or tied
+        let wall: Vec<Position> = (0..3).map(|y| Position::new(2, y)).collect();
+        let grid = GameState::builder_from_size(5, 3)
+            .with_territory(1, &[Position::new(0, 1)])
+            .with_territory(2, &wall)
+            .build()
+            .into_grid();
+        let map = compute_voronoi_partition(&grid);
+
+        assert_eq!(map.player1_cells, 2);
+        assert_eq!(map.player2_cells, 8);
+        assert_eq!(map.neutral_cells, 1);
+        // (1, 1) is equidistant from both players
+        assert_eq!(map.ownership[6], None);
+    }
+
+    fn create_rollout_game_state() -> GameState {
+        GameState::builder_from_size(8, 6)
+            .symmetric_start()
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build()
+    }
+
+    #[test]
+    fn test_monte_carlo_territory_reproducible() {
+        let state = create_rollout_game_state();
+        let placement = find_all_valid_placements(&state).remove(0);
+
+        let first = monte_carlo_territory(&state, &placement, 5, &mut StdRng::seed_from_u64(7));
+        let second = monte_carlo_territory(&state, &placement, 5, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(first, second);
+        assert!(first > 0.0 && first < 1.0);
+    }
+
+    #[test]
+    fn test_monte_carlo_territory_without_simulations() {
+        let state = create_rollout_game_state();
+        let placement = find_all_valid_placements(&state).remove(0);
+
+        // Two cells for us against the opponent's one
+        let share = monte_carlo_territory(&state, &placement, 0, &mut StdRng::seed_from_u64(0));
+        assert!((share - 2.0 / 3.0).abs() < 1e-6);
+    }
+}
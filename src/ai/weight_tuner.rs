@@ -0,0 +1,145 @@
+/// Heuristic weight tuning by self-play hill climbing
+///
+/// A player that picks the placement with the best `advanced_score` under
+/// candidate weights plays a fixed reference player (the same scoring
+/// under the initial weights). Each weight is nudged up and down in turn
+/// and the change is kept when it raises the win rate. Every evaluation
+/// replays the same seeds, so candidates are compared on identical piece
+/// sequences and the result is deterministic. Games cycle through the
+/// `sim::board_variants` of one starting board, so the weights are not
+/// tuned to a single corner of the board.
+
+use crate::game_state::GameState;
+use crate::placement::Placement;
+use crate::sim::{board_variants, PieceGenerator, Simulation};
+use super::heuristics::{advanced_score_pure, HeuristicWeights, ScoreMaps};
+use super::AIStrategy;
+
+/// Relative step applied to a weight when perturbing it
+pub const TUNING_EPSILON: f32 = 0.2;
+
+/// Board size used for tuning games
+pub const TUNING_BOARD: (usize, usize) = (12, 10);
+
+/// Hill-climb `initial` for `iterations` rounds of `games_per_eval` games
+///
+/// Each round tries multiplying every `advanced_score` weight by
+/// `1 + TUNING_EPSILON` and `1 - TUNING_EPSILON`, keeping any change that
+/// beats the best win rate so far. The evaluator-only weights
+/// (`centrality`, `adjacency`) do not affect `advanced_score` and are left
+/// unchanged.
+pub fn tune_weights(initial: HeuristicWeights, games_per_eval: usize, iterations: usize) -> HeuristicWeights {
+    let mut best = initial;
+    let mut best_rate = win_rate(&best, &initial, games_per_eval);
+
+    for _ in 0..iterations {
+        for field in 0..TUNED_FIELDS {
+            for factor in [1.0 + TUNING_EPSILON, 1.0 - TUNING_EPSILON] {
+                let mut candidate = best;
+                *weight_mut(&mut candidate, field) *= factor;
+
+                let rate = win_rate(&candidate, &initial, games_per_eval);
+                if rate > best_rate {
+                    best = candidate;
+                    best_rate = rate;
+                    break;
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Number of weights `advanced_score` uses
+const TUNED_FIELDS: usize = 8;
+
+/// The `index`th weight used by `advanced_score`
+fn weight_mut(weights: &mut HeuristicWeights, index: usize) -> &mut f32 {
+    match index {
+        0 => &mut weights.expansion,
+        1 => &mut weights.flood_fill,
+        2 => &mut weights.weak_positions,
+        3 => &mut weights.density,
+        4 => &mut weights.edge_control,
+        5 => &mut weights.influence,
+        6 => &mut weights.connectivity,
+        _ => &mut weights.bottleneck,
+    }
+}
+
+/// Share of `games` won by `candidate` against `reference`, ties counting half
+///
+/// Seats alternate so neither side always moves first, and each pair of
+/// games moves on to the next board variant; game `i` uses seed `i`.
+fn win_rate(candidate: &HeuristicWeights, reference: &HeuristicWeights, games: usize) -> f32 {
+    if games == 0 {
+        return 0.0;
+    }
+
+    let (width, height) = TUNING_BOARD;
+    let base = Simulation::with_board(AIStrategy::Default, AIStrategy::Default, width, height, 0).grid;
+    let variants = board_variants(&base);
+    let mut points = 0.0;
+    for game in 0..games {
+        let candidate_seat = if game % 2 == 0 { 1 } else { 2 };
+        let grid = variants[(game / 2) % variants.len()].clone();
+        // The strategies are unused: every move comes from the selector below
+        let mut sim = Simulation::with_grid(AIStrategy::Default, AIStrategy::Default, grid, PieceGenerator::new(game as u64));
+        let result = sim.run_with(&mut |player, placements, game_state| {
+            let weights = if player == candidate_seat { candidate } else { reference };
+            best_by_weights(placements, game_state, weights)
+        });
+
+        if result.winner == candidate_seat {
+            points += 1.0;
+        } else if result.winner == 0 {
+            points += 0.5;
+        }
+    }
+
+    points / games as f32
+}
+
+/// Placement with the highest `advanced_score`; ties go to the first
+fn best_by_weights(
+    placements: &[Placement],
+    game_state: &GameState,
+    weights: &HeuristicWeights,
+) -> Option<Placement> {
+    let maps = ScoreMaps::new(game_state);
+    let mut best: Option<(&Placement, _)> = None;
+    for placement in placements {
+        let simulated = placement.simulate(game_state.grid(), game_state.player_number());
+        let score = advanced_score_pure(placement, &simulated, game_state, &maps, weights);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((placement, score));
+        }
+    }
+    best.map(|(placement, _)| placement.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_win_rate_bounds() {
+        let weights = HeuristicWeights::default();
+
+        let rate = win_rate(&weights, &weights, 2);
+        assert!((0.0..=1.0).contains(&rate));
+        assert_eq!(win_rate(&weights, &weights, 0), 0.0);
+    }
+
+    #[test]
+    fn test_tune_weights_is_deterministic() {
+        let initial = HeuristicWeights::default();
+
+        assert_eq!(tune_weights(initial, 2, 0), initial);
+
+        let tuned = tune_weights(initial, 2, 1);
+        assert_eq!(tuned, tune_weights(initial, 2, 1));
+        assert_eq!((tuned.centrality, tuned.adjacency), (initial.centrality, initial.adjacency));
+    }
+}
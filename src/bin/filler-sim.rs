@@ -0,0 +1,71 @@
+/// Self-play harness
+///
+/// Runs N simulated games between two strategies and prints win rates.
+///
+/// Usage: `filler-sim [games] [player1] [player2] [width] [height]`
+///
+/// Strategies are named as in `AIStrategy`'s `Debug` output (for example
+/// `GreedyExpansion` or `AdvancedBalanced`). Game `i` uses seed `i`.
+
+use filler::ai::AIStrategy;
+use filler::sim::simulate_game;
+use std::process::ExitCode;
+
+const DEFAULT_GAMES: u64 = 20;
+const DEFAULT_WIDTH: usize = 20;
+const DEFAULT_HEIGHT: usize = 15;
+
+/// Look up a fixed strategy by its `Debug` name
+fn parse_strategy(name: &str) -> Option<AIStrategy> {
+    AIStrategy::all()
+        .into_iter()
+        .find(|strategy| format!("{:?}", strategy) == name)
+}
+
+/// Parse the `index`th argument, or return `default` if it is absent
+fn arg_or<T: std::str::FromStr>(args: &[String], index: usize, default: T) -> Result<T, String> {
+    match args.get(index) {
+        Some(raw) => raw.parse().map_err(|_| format!("Invalid argument: {:?}", raw)),
+        None => Ok(default),
+    }
+}
+
+fn strategy_arg(args: &[String], index: usize, default: AIStrategy) -> Result<AIStrategy, String> {
+    match args.get(index) {
+        Some(name) => parse_strategy(name).ok_or_else(|| format!("Unknown strategy: {:?}", name)),
+        None => Ok(default),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let games = arg_or(args, 0, DEFAULT_GAMES)?;
+    let player1 = strategy_arg(args, 1, AIStrategy::AdvancedBalanced)?;
+    let player2 = strategy_arg(args, 2, AIStrategy::GreedyExpansion)?;
+    let width = arg_or(args, 3, DEFAULT_WIDTH)?;
+    let height = arg_or(args, 4, DEFAULT_HEIGHT)?;
+
+    let mut wins = [0u64; 3];
+    for seed in 0..games {
+        let result = simulate_game(player1, player2, width, height, seed);
+        wins[result.winner as usize] += 1;
+    }
+
+    let rate = |count: u64| 100.0 * count as f64 / games.max(1) as f64;
+    println!("{} games on {} x {}", games, width, height);
+    println!("  p1 {:?}: {} wins ({:.1}%)", player1, wins[1], rate(wins[1]));
+    println!("  p2 {:?}: {} wins ({:.1}%)", player2, wins[2], rate(wins[2]));
+    println!("  ties: {} ({:.1}%)", wins[0], rate(wins[0]));
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Usage: filler-sim [games] [player1] [player2] [width] [height]");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,51 @@
+/// Strategy tournament
+///
+/// Plays every `AIStrategy` against every other and prints the standings
+/// followed by the head-to-head matrix as a markdown table.
+///
+/// Usage: `filler-tournament [games_per_pair] [width] [height] [seed]`
+
+use filler::ai::competition::run_strategy_tournament;
+use std::process::ExitCode;
+
+const DEFAULT_GAMES_PER_PAIR: usize = 4;
+const DEFAULT_WIDTH: usize = 20;
+const DEFAULT_HEIGHT: usize = 15;
+const DEFAULT_SEED: u64 = 0;
+
+/// Parse the `index`th argument, or return `default` if it is absent
+fn arg_or<T: std::str::FromStr>(args: &[String], index: usize, default: T) -> Result<T, String> {
+    match args.get(index) {
+        Some(raw) => raw.parse().map_err(|_| format!("Invalid argument: {:?}", raw)),
+        None => Ok(default),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let games_per_pair = arg_or(args, 0, DEFAULT_GAMES_PER_PAIR)?;
+    let width = arg_or(args, 1, DEFAULT_WIDTH)?;
+    let height = arg_or(args, 2, DEFAULT_HEIGHT)?;
+    let seed = arg_or(args, 3, DEFAULT_SEED)?;
+
+    let standings = run_strategy_tournament(width, height, games_per_pair, seed);
+
+    println!("{} games per pair on {} x {}", games_per_pair, width, height);
+    for (rank, (strategy, wins)) in standings.standings.iter().enumerate() {
+        println!("  {:>2}. {:?}: {} wins", rank + 1, strategy, wins);
+    }
+    println!();
+    print!("{}", standings.results.to_markdown_table());
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Usage: filler-tournament [games_per_pair] [width] [height] [seed]");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/// Error types for the Filler AI
+///
+/// `FillerError` covers everything that can go wrong while reading game
+/// input from the engine.
+
+use std::fmt;
+use std::io;
+
+/// Error reading or parsing engine input
+#[derive(Debug)]
+pub enum FillerError {
+    /// Underlying read failed
+    Io(io::Error),
+    /// `$$$ exec p<N> : [...]` line could not be parsed
+    InvalidPlayerLine { raw: String },
+    /// `Anfield W H:` header could not be parsed
+    InvalidAnfieldHeader { raw: String },
+    /// Anfield row `row` is malformed or has the wrong width
+    InvalidGridRow { row: usize, raw: String },
+    /// `Piece W H:` header could not be parsed
+    InvalidPieceDimensions { raw: String },
+    /// Piece row `row` has the wrong width
+    InvalidPieceRow { row: usize, raw: String },
+    /// A game log turn appeared before any `$$$ exec` line
+    MissingPlayerLine { turn: usize },
+}
+
+impl fmt::Display for FillerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillerError::Io(e) => write!(f, "Failed to read input: {}", e),
+            FillerError::InvalidPlayerLine { raw } => {
+                write!(f, "Invalid player line: {:?}", raw)
+            }
+            FillerError::InvalidAnfieldHeader { raw } => {
+                write!(f, "Invalid Anfield header: {:?}", raw)
+            }
+            FillerError::InvalidGridRow { row, raw } => {
+                write!(f, "Invalid grid row {}: {:?}", row, raw)
+            }
+            FillerError::InvalidPieceDimensions { raw } => {
+                write!(f, "Invalid Piece header: {:?}", raw)
+            }
+            FillerError::InvalidPieceRow { row, raw } => {
+                write!(f, "Invalid piece row {}: {:?}", row, raw)
+            }
+            FillerError::MissingPlayerLine { turn } => {
+                write!(f, "Turn {} has no player line", turn)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FillerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FillerError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FillerError {
+    fn from(e: io::Error) -> Self {
+        FillerError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_display_includes_raw_input() {
+        let err = FillerError::InvalidGridRow {
+            row: 3,
+            raw: "003 ..".to_string(),
+        };
+        assert_eq!(err.to_string(), "Invalid grid row 3: \"003 ..\"");
+    }
+
+    #[test]
+    fn test_from_io_error_keeps_source() {
+        let err: FillerError = io::Error::new(io::ErrorKind::UnexpectedEof, "eof").into();
+
+        assert!(matches!(err, FillerError::Io(_)));
+        assert!(err.source().is_some());
+    }
+}
@@ -0,0 +1,124 @@
+/// Game engine IO abstraction
+///
+/// The engine talks to the player over stdin and stdout, which do not
+/// exist in a browser. `GameIO` hides where turns come from and where
+/// moves go: `StdinStdout` is the real engine connection and `SliceIO`
+/// plays a turn from an in-memory string, for WASM builds and tests.
+
+use crate::error::FillerError;
+use std::io::BufRead;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, Write};
+
+/// Source of engine input and sink for moves
+pub trait GameIO {
+    /// Next input line including its newline, or `""` at end of input
+    fn read_line(&mut self) -> Result<String, FillerError>;
+
+    /// Send a move in the engine's `X Y\n` format
+    fn write_move(&mut self, x: usize, y: usize) -> Result<(), FillerError>;
+}
+
+/// The engine connection: lines from stdin, moves to stdout
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinStdout;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl GameIO for StdinStdout {
+    fn read_line(&mut self) -> Result<String, FillerError> {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        Ok(line)
+    }
+
+    fn write_move(&mut self, x: usize, y: usize) -> Result<(), FillerError> {
+        let mut handle = io::stdout().lock();
+        writeln!(handle, "{} {}", x, y)?;
+        handle.flush()?;
+        Ok(())
+    }
+}
+
+/// In-memory IO: input lines from a string, moves collected in `output`
+#[derive(Debug, Clone)]
+pub struct SliceIO<'a> {
+    input: &'a str,
+    /// Every move written so far, one `X Y\n` line each
+    pub output: String,
+}
+
+impl<'a> SliceIO<'a> {
+    /// Create an IO that reads `input` line by line
+    pub fn new(input: &'a str) -> Self {
+        SliceIO { input, output: String::new() }
+    }
+}
+
+impl GameIO for SliceIO<'_> {
+    fn read_line(&mut self) -> Result<String, FillerError> {
+        let end = self.input.find('\n').map_or(self.input.len(), |i| i + 1);
+        let (line, rest) = self.input.split_at(end);
+        self.input = rest;
+        Ok(line.to_string())
+    }
+
+    fn write_move(&mut self, x: usize, y: usize) -> Result<(), FillerError> {
+        self.output.push_str(&format!("{} {}\n", x, y));
+        Ok(())
+    }
+}
+
+/// Input-only IO over any reader, used to parse logs and byte buffers
+///
+/// Moves cannot be written and fail with `io::ErrorKind::Unsupported`.
+pub struct ReaderIO<'a> {
+    reader: &'a mut dyn BufRead,
+}
+
+impl<'a> ReaderIO<'a> {
+    /// Create an IO that reads lines from `reader`
+    pub fn new(reader: &'a mut dyn BufRead) -> Self {
+        ReaderIO { reader }
+    }
+}
+
+impl GameIO for ReaderIO<'_> {
+    fn read_line(&mut self) -> Result<String, FillerError> {
+        let mut line = String::new();
+        self.reader.read_line(&mut line)?;
+        Ok(line)
+    }
+
+    fn write_move(&mut self, _x: usize, _y: usize) -> Result<(), FillerError> {
+        Err(std::io::Error::from(std::io::ErrorKind::Unsupported).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_io_reads_lines_and_collects_moves() {
+        let mut io = SliceIO::new("first\nsecond");
+
+        assert_eq!(io.read_line().unwrap(), "first\n");
+        assert_eq!(io.read_line().unwrap(), "second");
+        assert_eq!(io.read_line().unwrap(), "");
+
+        io.write_move(3, 4).unwrap();
+        io.write_move(0, 0).unwrap();
+        assert_eq!(io.output, "3 4\n0 0\n");
+    }
+
+    #[test]
+    fn test_reader_io_is_input_only() {
+        let mut cursor = std::io::Cursor::new("line\n");
+        let mut io = ReaderIO::new(&mut cursor);
+
+        assert_eq!(io.read_line().unwrap(), "line\n");
+        assert_eq!(io.read_line().unwrap(), "");
+        assert!(matches!(io.write_move(1, 1), Err(FillerError::Io(_))));
+    }
+}
@@ -3,7 +3,12 @@
 /// This module provides the core data structures for representing
 /// the game state during a Filler game.
 
+use crate::placement::{find_all_valid_placements, Placement, PlacementError};
+use crate::utils::{bfs_distance_map, convex_hull};
+use rand::{Rng, RngExt};
+use std::collections::{HashSet, VecDeque};
 use std::fmt;
+use std::sync::{Arc, LazyLock, OnceLock};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellState {
@@ -12,6 +17,83 @@ pub enum CellState {
     Player2,    // Player 2 territory ($)
     Player1Last, // Last piece placed by Player 1 (a)
     Player2Last, // Last piece placed by Player 2 (s)
+    Player3,    // Player 3 territory (&), multi-player variants only
+    Player4,    // Player 4 territory (%), multi-player variants only
+    Player3Last, // Last piece placed by Player 3 (d)
+    Player4Last, // Last piece placed by Player 4 (f)
+}
+
+impl CellState {
+    /// Territory and last-piece states for a player number (1-4)
+    pub fn player_states(player: u8) -> Option<(CellState, CellState)> {
+        match player {
+            1 => Some((CellState::Player1, CellState::Player1Last)),
+            2 => Some((CellState::Player2, CellState::Player2Last)),
+            3 => Some((CellState::Player3, CellState::Player3Last)),
+            4 => Some((CellState::Player4, CellState::Player4Last)),
+            _ => None,
+        }
+    }
+
+    /// Player number owning this cell, or `None` if empty
+    pub fn player_num(&self) -> Option<u8> {
+        match self {
+            CellState::Empty => None,
+            CellState::Player1 | CellState::Player1Last => Some(1),
+            CellState::Player2 | CellState::Player2Last => Some(2),
+            CellState::Player3 | CellState::Player3Last => Some(3),
+            CellState::Player4 | CellState::Player4Last => Some(4),
+        }
+    }
+
+    /// Gray level used by `Grid::to_pgm`
+    ///
+    /// Player 1 is white and player 2 black; last-piece cells are brighter
+    /// than the player's other cells.
+    pub fn gray_level(&self) -> u8 {
+        match self {
+            CellState::Empty => 128,
+            CellState::Player1 => 220,
+            CellState::Player1Last => 255,
+            CellState::Player2 => 0,
+            CellState::Player2Last => 64,
+            CellState::Player3 => 170,
+            CellState::Player3Last => 200,
+            CellState::Player4 => 90,
+            CellState::Player4Last => 110,
+        }
+    }
+
+    /// RGB color used by `Grid::to_ppm`
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            CellState::Empty => [40, 40, 40],
+            CellState::Player1 => [30, 90, 220],
+            CellState::Player1Last => [120, 180, 255],
+            CellState::Player2 => [200, 40, 40],
+            CellState::Player2Last => [255, 140, 120],
+            CellState::Player3 => [40, 160, 60],
+            CellState::Player3Last => [140, 230, 140],
+            CellState::Player4 => [190, 150, 20],
+            CellState::Player4Last => [255, 220, 100],
+        }
+    }
+
+    /// Whether `player_num` owns this cell, last piece included
+    pub fn is_player(&self, player_num: u8) -> bool {
+        self.player_num() == Some(player_num)
+    }
+
+    /// Check if this cell is part of a player's last placed piece
+    pub fn is_last_placed(&self) -> bool {
+        matches!(
+            self,
+            CellState::Player1Last
+                | CellState::Player2Last
+                | CellState::Player3Last
+                | CellState::Player4Last
+        )
+    }
 }
 
 impl From<char> for CellState {
@@ -22,6 +104,10 @@ impl From<char> for CellState {
             '$' => CellState::Player2,
             'a' => CellState::Player1Last,
             's' => CellState::Player2Last,
+            '&' => CellState::Player3,
+            '%' => CellState::Player4,
+            'd' => CellState::Player3Last,
+            'f' => CellState::Player4Last,
             _ => CellState::Empty, // Default to empty for unknown chars
         }
     }
@@ -35,11 +121,55 @@ impl fmt::Display for CellState {
             CellState::Player2 => '$',
             CellState::Player1Last => 'a',
             CellState::Player2Last => 's',
+            CellState::Player3 => '&',
+            CellState::Player4 => '%',
+            CellState::Player3Last => 'd',
+            CellState::Player4Last => 'f',
         };
         write!(f, "{}", c)
     }
 }
 
+/// Number of cells covered by the precomputed Zobrist table (100x100)
+const ZOBRIST_TABLE_CELLS: usize = 100 * 100;
+/// Number of `CellState` variants
+const ZOBRIST_STATES: usize = 9;
+
+/// Precomputed Zobrist keys, indexed by `cell_index * ZOBRIST_STATES + state`
+static ZOBRIST_KEYS: LazyLock<Vec<u64>> = LazyLock::new(|| {
+    (0..ZOBRIST_TABLE_CELLS * ZOBRIST_STATES)
+        .map(|i| splitmix64(i as u64))
+        .collect()
+});
+
+/// SplitMix64 mixing function used to derive Zobrist keys
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Zobrist key for a cell index in a given state (0 for empty cells)
+///
+/// Boards larger than the table fall back to computing the key directly.
+fn zobrist_key(index: usize, state: CellState) -> u64 {
+    if state == CellState::Empty {
+        return 0;
+    }
+    let i = index * ZOBRIST_STATES + state as usize;
+    if index < ZOBRIST_TABLE_CELLS {
+        ZOBRIST_KEYS[i]
+    } else {
+        splitmix64(i as u64)
+    }
+}
+
+/// Zobrist key for the player to move
+fn zobrist_player_key(player: u8) -> u64 {
+    splitmix64(u64::MAX - player as u64)
+}
+
 /// Represents a position on the Anfield
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Position {
@@ -51,23 +181,40 @@ impl Position {
     pub fn new(x: usize, y: usize) -> Self {
         Position { x, y }
     }
+
+    /// Position shifted by `(dx, dy)`, `None` if either coordinate underflows
+    ///
+    /// Only the lower bound is checked; callers still test the result
+    /// against the grid size.
+    pub fn checked_add(&self, dx: i32, dy: i32) -> Option<Position> {
+        Some(Position::new(
+            self.x.checked_add_signed(dx as isize)?,
+            self.y.checked_add_signed(dy as isize)?,
+        ))
+    }
 }
 
+/// BFS distances indexed as `map[y][x]`, `None` where unreachable
+pub type DistanceMap = Vec<Vec<Option<usize>>>;
+
+/// Playouts averaged by `Grid::expected_final_territory`
+pub const RANDOM_FILL_SIMULATIONS: usize = 100;
+
 /// Represents the Anfield grid with cell states
-#[derive(Debug, Clone)]
+/// 
+/// Cells are stored row-major in a single flat vector (`y * width + x`)
+/// for cache locality.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Grid {
     pub width: usize,
     pub height: usize,
-    pub cells: Vec<Vec<CellState>>,
+    pub cells: Vec<CellState>,
 }
 
 impl Grid {
     /// Create a new grid from raw character data
     pub fn from_chars(width: usize, height: usize, raw: Vec<Vec<char>>) -> Self {
-        let cells = raw
-            .into_iter()
-            .map(|row| row.into_iter().map(CellState::from).collect())
-            .collect();
+        let cells = raw.into_iter().flatten().map(CellState::from).collect();
 
         Grid {
             width,
@@ -79,7 +226,7 @@ impl Grid {
     /// Get cell state at position
     pub fn get(&self, pos: Position) -> Option<CellState> {
         if pos.x < self.width && pos.y < self.height {
-            Some(self.cells[pos.y][pos.x])
+            Some(self.cells[pos.y * self.width + pos.x])
         } else {
             None
         }
@@ -88,48 +235,133 @@ impl Grid {
     /// Set cell state at position
     pub fn set(&mut self, pos: Position, state: CellState) -> bool {
         if pos.x < self.width && pos.y < self.height {
-            self.cells[pos.y][pos.x] = state;
+            self.cells[pos.y * self.width + pos.x] = state;
             true
         } else {
             false
         }
     }
 
+    /// Iterate over every cell in row-major order with its position
+    pub fn iter_positions(&self) -> impl Iterator<Item = (Position, CellState)> + '_ {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, &state)| (Position::new(i % width, i / width), state))
+    }
+
     /// Check if a position is within bounds
     pub fn is_valid(&self, pos: Position) -> bool {
         pos.x < self.width && pos.y < self.height
     }
 
+    /// Check if signed coordinates are within bounds
+    pub fn is_valid_signed(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Get cell state at signed coordinates, `None` if out of bounds
+    pub fn get_signed(&self, x: i32, y: i32) -> Option<CellState> {
+        if self.is_valid_signed(x, y) {
+            self.get(Position::new(x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Mirror the board left to right
+    ///
+    /// Cell states are kept as they are; only their coordinates move.
+    pub fn flip_horizontal(&self) -> Grid {
+        self.remap(self.width, self.height, |x, y| Position::new(self.width - 1 - x, y))
+    }
+
+    /// Mirror the board top to bottom
+    pub fn flip_vertical(&self) -> Grid {
+        self.remap(self.width, self.height, |x, y| Position::new(x, self.height - 1 - y))
+    }
+
+    /// Rotate the board 90 degrees clockwise, as `Shape::rotate_90` does
+    ///
+    /// Width and height swap.
+    pub fn rotate_90(&self) -> Grid {
+        self.remap(self.height, self.width, |x, y| Position::new(self.height - 1 - y, x))
+    }
+
+    /// `width` x `height` grid with each cell copied to `to(x, y)`
+    fn remap(&self, width: usize, height: usize, to: impl Fn(usize, usize) -> Position) -> Grid {
+        let mut grid = Grid { width, height, cells: vec![CellState::Empty; self.cells.len()] };
+        for (pos, state) in self.iter_positions() {
+            grid.set(to(pos.x, pos.y), state);
+        }
+        grid
+    }
+
     /// Get all positions occupied by player territory (including last piece)
     pub fn get_player_positions(&self, player_num: u8) -> Vec<Position> {
-        let mut positions = Vec::new();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let state = self.cells[y][x];
-                let is_player = match player_num {
-                    1 => state == CellState::Player1 || state == CellState::Player1Last,
-                    2 => state == CellState::Player2 || state == CellState::Player2Last,
-                    _ => false,
-                };
-                if is_player {
-                    positions.push(Position::new(x, y));
-                }
-            }
+        self.iter_positions()
+            .filter(|&(_, state)| state.is_player(player_num))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Mean squared distance of the player's cells from their centroid
+    ///
+    /// The spatial variance of the territory: small for a tight cluster,
+    /// large for territory spread across the board. 0 if they own no cells.
+    pub fn territory_spread(&self, player_num: u8) -> f32 {
+        let positions = self.get_player_positions(player_num);
+        if positions.is_empty() {
+            return 0.0;
         }
-        positions
+        let count = positions.len() as f32;
+        let cx = positions.iter().map(|p| p.x).sum::<usize>() as f32 / count;
+        let cy = positions.iter().map(|p| p.y).sum::<usize>() as f32 / count;
+        let total: f32 = positions
+            .iter()
+            .map(|pos| (pos.x as f32 - cx).powi(2) + (pos.y as f32 - cy).powi(2))
+            .sum();
+        total / count
+    }
+
+    /// Convex hull of the player's cells, by gift wrapping
+    ///
+    /// Vertices in the order `utils::convex_hull` returns them; empty if
+    /// they own no cells.
+    pub fn convex_hull_of_territory(&self, player_num: u8) -> Vec<Position> {
+        convex_hull(&self.get_player_positions(player_num))
+    }
+
+    /// Number of empty cells, counted by scanning the board
+    ///
+    /// `TrackedGrid::empty_count` answers the same question in O(1).
+    pub fn count_empty(&self) -> usize {
+        self.cells.iter().filter(|&&state| state == CellState::Empty).count()
     }
 
     /// Get all empty positions
     pub fn get_empty_positions(&self) -> Vec<Position> {
-        let mut positions = Vec::new();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.cells[y][x] == CellState::Empty {
-                    positions.push(Position::new(x, y));
-                }
-            }
-        }
-        positions
+        self.iter_positions()
+            .filter(|&(_, state)| state == CellState::Empty)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Player cells with at least one empty orthogonal neighbor
+    ///
+    /// These are the only cells a piece can overlap while still claiming
+    /// new cells next to them. Returned in row-major order.
+    pub fn territory_border(&self, player_num: u8) -> Vec<Position> {
+        self.iter_positions()
+            .filter(|&(pos, state)| {
+                state.is_player(player_num)
+                    && [(0, -1), (0, 1), (-1, 0), (1, 0)].iter().any(|&(dx, dy)| {
+                        pos.checked_add(dx, dy).and_then(|n| self.get(n)) == Some(CellState::Empty)
+                    })
+            })
+            .map(|(pos, _)| pos)
+            .collect()
     }
 
     /// Count territory for a player
@@ -137,215 +369,2959 @@ impl Grid {
         self.get_player_positions(player_num).len()
     }
 
-    /// Print the grid for debugging
-    pub fn print(&self) {
-        eprintln!("=== Grid: {} x {} ===", self.width, self.height);
-        for (y, row) in self.cells.iter().enumerate() {
-            eprint!("{:03} ", y);
-            for cell in row {
-                eprint!("{}", cell);
-            }
-            eprintln!();
-        }
+    /// BFS distance from `player_num`'s territory to every cell
+    ///
+    /// Paths run through empty cells only, so walls of opponent territory
+    /// lengthen distances beyond the Manhattan value. Occupied cells next to
+    /// the frontier get a distance but are not expanded; unreachable cells
+    /// are `None`. Indexed as `map[y][x]`.
+    pub fn distance_map(&self, player_num: u8) -> Vec<Vec<Option<usize>>> {
+        bfs_distance_map(self, &self.get_player_positions(player_num))
     }
-}
-
-/// Represents a piece shape
-#[derive(Debug, Clone, PartialEq)]
-pub struct Shape {
-    pub width: usize,
-    pub height: usize,
-    pub cells: Vec<Vec<bool>>, // true = filled, false = empty
-}
 
-impl Shape {
-    /// Create a new shape from raw character data
-    pub fn from_chars(width: usize, height: usize, raw: Vec<Vec<char>>) -> Self {
-        let cells = raw
-            .into_iter()
-            .map(|row| {
-                row.into_iter()
-                    .map(|c| c != '.')
-                    .collect()
-            })
-            .collect();
+    /// `distance_map` for players 1 and 2 from one joint BFS
+    ///
+    /// Seeds a single queue with both territories and tags each entry with
+    /// its player, so the grid is scanned for sources once and both searches
+    /// advance level by level together. Each map matches `distance_map` for
+    /// its player; a cell belongs to player 1 in the Voronoi partition when
+    /// player 1's distance is strictly smaller.
+    pub fn voronoi_distance_pair(&self) -> (DistanceMap, DistanceMap) {
+        let mut maps = [
+            vec![vec![None; self.width]; self.height],
+            vec![vec![None; self.width]; self.height],
+        ];
+        let mut queue = VecDeque::new();
 
-        Shape {
-            width,
-            height,
-            cells,
+        for (pos, state) in self.iter_positions() {
+            let index = match state.player_num() {
+                Some(1) => 0,
+                Some(2) => 1,
+                _ => continue,
+            };
+            maps[index][pos.y][pos.x] = Some(0);
+            queue.push_back((pos, index));
         }
-    }
 
-    /// Get all filled cell positions relative to top-left (0, 0)
-    pub fn get_filled_positions(&self) -> Vec<Position> {
-        let mut positions = Vec::new();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.cells[y][x] {
-                    positions.push(Position::new(x, y));
+        while let Some((pos, index)) = queue.pop_front() {
+            let distances = &mut maps[index];
+            let next_distance = distances[pos.y][pos.x].unwrap_or(0) + 1;
+
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let Some(neighbor) = pos.checked_add(dx, dy) else {
+                    continue;
+                };
+                let Some(state) = self.get(neighbor) else {
+                    continue;
+                };
+                let (nx, ny) = (neighbor.x, neighbor.y);
+                if distances[ny][nx].is_some() {
+                    continue;
+                }
+
+                distances[ny][nx] = Some(next_distance);
+                if state == CellState::Empty {
+                    queue.push_back((Position::new(nx, ny), index));
                 }
             }
         }
-        positions
+
+        let [player1, player2] = maps;
+        (player1, player2)
     }
 
-    /// Check if the shape has any filled cells
-    pub fn is_empty(&self) -> bool {
-        self.get_filled_positions().is_empty()
+    /// Every empty cell reachable from `start_positions`
+    ///
+    /// A complete BFS that walks through empty cells and, if
+    /// `through_player` is given, through that player's cells as well.
+    /// Start positions are included when they are empty themselves.
+    pub fn reachable_empty_cells(
+        &self,
+        start_positions: &[Position],
+        through_player: Option<u8>,
+    ) -> HashSet<Position> {
+        self.reachable_empty_cells_limited(start_positions, through_player, usize::MAX)
     }
 
-    /// Get bounding box of the filled cells
-    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
-        let positions = self.get_filled_positions();
-        if positions.is_empty() {
-            return None;
-        }
+    /// `reachable_empty_cells` that stops after expanding `max_expansions` cells
+    ///
+    /// An approximation: once the limit is hit the result is truncated to
+    /// whatever the BFS had discovered so far. Start positions count
+    /// towards the limit.
+    pub fn reachable_empty_cells_limited(
+        &self,
+        start_positions: &[Position],
+        through_player: Option<u8>,
+        max_expansions: usize,
+    ) -> HashSet<Position> {
+        let passable = |state: CellState| {
+            state == CellState::Empty || (through_player.is_some() && state.player_num() == through_player)
+        };
+        let mut visited: HashSet<Position> = HashSet::new();
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::new();
 
-        let min_x = positions.iter().map(|p| p.x).min().unwrap();
-        let max_x = positions.iter().map(|p| p.x).max().unwrap();
-        let min_y = positions.iter().map(|p| p.y).min().unwrap();
-        let max_y = positions.iter().map(|p| p.y).max().unwrap();
+        for &pos in start_positions {
+            if let Some(state) = self.get(pos)
+                && visited.insert(pos)
+            {
+                queue.push_back(pos);
+                if state == CellState::Empty {
+                    reachable.insert(pos);
+                }
+            }
+        }
 
-        Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
-    }
+        let mut expansions = 0;
+        while let Some(pos) = queue.pop_front() {
+            if expansions >= max_expansions {
+                break;
+            }
+            expansions += 1;
 
-    /// Print the shape for debugging
-    pub fn print(&self) {
-        eprintln!("=== Shape: {} x {} ===", self.width, self.height);
-        for row in &self.cells {
-            for &filled in row {
-                eprint!("{}", if filled { '#' } else { '.' });
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let Some(neighbor) = pos.checked_add(dx, dy) else {
+                    continue;
+                };
+                let Some(state) = self.get(neighbor) else {
+                    continue;
+                };
+                if passable(state) && visited.insert(neighbor) {
+                    if state == CellState::Empty {
+                        reachable.insert(neighbor);
+                    }
+                    queue.push_back(neighbor);
+                }
             }
-            eprintln!();
         }
+
+        reachable
     }
-}
 
-/// Represents the complete game state
-#[derive(Debug, Clone)]
-pub struct GameState {
-    pub player_number: u8,
-    pub grid: Grid,
-    pub current_piece: Shape,
-}
+    /// Split a player's territory into 4-connected islands
+    ///
+    /// Each returned `Vec<Position>` is one connected component.
+    pub fn connected_components(&self, player_num: u8) -> Vec<Vec<Position>> {
+        let positions = self.get_player_positions(player_num);
+        let owned: HashSet<Position> = positions.iter().copied().collect();
+        let mut visited: HashSet<Position> = HashSet::new();
+        let mut components = Vec::new();
 
-impl GameState {
-    /// Create a new game state
-    pub fn new(player_number: u8, grid: Grid, current_piece: Shape) -> Self {
-        GameState {
-            player_number,
-            grid,
-            current_piece,
+        for start in positions {
+            if !visited.insert(start) {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(pos) = queue.pop_front() {
+                component.push(pos);
+                for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                    if let Some(neighbor) = pos.checked_add(dx, dy)
+                        && owned.contains(&neighbor)
+                        && visited.insert(neighbor)
+                    {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            components.push(component);
         }
-    }
 
-    /// Get all positions belonging to the current player
-    pub fn get_my_positions(&self) -> Vec<Position> {
-        self.grid.get_player_positions(self.player_number)
+        components
     }
 
-    /// Get all positions belonging to the opponent
-    pub fn get_opponent_positions(&self) -> Vec<Position> {
-        let opponent = if self.player_number == 1 { 2 } else { 1 };
-        self.grid.get_player_positions(opponent)
+    /// Size of the largest 4-connected region of empty cells, 0 if none
+    pub fn largest_empty_region(&self) -> usize {
+        self.empty_regions().1.into_iter().max().unwrap_or(0)
     }
 
-    /// Get current territory size for current player
-    pub fn get_my_territory_size(&self) -> usize {
-        self.grid.count_territory(self.player_number)
+    /// Sizes of all 4-connected empty regions, largest first
+    pub fn region_sizes(&self) -> Vec<usize> {
+        let mut sizes = self.empty_regions().1;
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
     }
 
-    /// Get opponent territory size
-    pub fn get_opponent_territory_size(&self) -> usize {
-        let opponent = if self.player_number == 1 { 2 } else { 1 };
-        self.grid.count_territory(opponent)
+    /// Label every empty cell with its 4-connected empty region
+    ///
+    /// Returns the per-cell region index in row-major order (`None` for
+    /// occupied cells) and the size of each region.
+    fn empty_regions(&self) -> (Vec<Option<usize>>, Vec<usize>) {
+        let mut labels = vec![None; self.cells.len()];
+        let mut sizes = Vec::new();
+
+        for start in 0..self.cells.len() {
+            if self.cells[start] != CellState::Empty || labels[start].is_some() {
+                continue;
+            }
+
+            let region = sizes.len();
+            let mut size = 0;
+            labels[start] = Some(region);
+            let mut queue = VecDeque::from([start]);
+            while let Some(index) = queue.pop_front() {
+                size += 1;
+                let (x, y) = ((index % self.width) as i32, (index / self.width) as i32);
+                for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    if self.get_signed(x + dx, y + dy) != Some(CellState::Empty) {
+                        continue;
+                    }
+                    let neighbor = (y + dy) as usize * self.width + (x + dx) as usize;
+                    if labels[neighbor].is_none() {
+                        labels[neighbor] = Some(region);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        (labels, sizes)
     }
 
-    /// Print game state for debugging
-    pub fn print(&self) {
-        eprintln!("\n=== Game State ===");
-        eprintln!("Player: {}", self.player_number);
-        eprintln!(
-            "My Territory: {} | Opponent Territory: {}",
-            self.get_my_territory_size(),
-            self.get_opponent_territory_size()
-        );
-        self.grid.print();
-        eprintln!();
-        self.current_piece.print();
+    /// Largest empty region each player reaches, as `(player 1, player 2)`
+    ///
+    /// A region counts for a player if their `voronoi_distance_pair`
+    /// distance map reaches any of its cells, i.e. it borders their
+    /// territory.
+    pub fn largest_reachable_empty_regions(&self) -> (usize, usize) {
+        let (labels, sizes) = self.empty_regions();
+        let (player1, player2) = self.voronoi_distance_pair();
+
+        let largest = |distances: &DistanceMap| {
+            labels
+                .iter()
+                .enumerate()
+                .filter(|&(index, _)| distances[index / self.width][index % self.width].is_some())
+                .filter_map(|(_, &label)| label.map(|region| sizes[region]))
+                .max()
+                .unwrap_or(0)
+        };
+        (largest(&player1), largest(&player2))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Final territory counts if every empty cell went to the nearest player
+    ///
+    /// Distances are BFS distances through empty cells from each player's
+    /// territory; cells equally close to both go to either at random, and
+    /// cells neither can reach stay unclaimed. Returns `(player 1,
+    /// player 2)`, each counting their given territory plus claimed cells.
+    pub fn simulate_random_fill(
+        &self,
+        player1_territory: &[Position],
+        player2_territory: &[Position],
+        rng: &mut impl Rng,
+    ) -> (usize, usize) {
+        let player1 = bfs_distance_map(self, player1_territory);
+        let player2 = bfs_distance_map(self, player2_territory);
+        let (mut count1, mut count2) = (player1_territory.len(), player2_territory.len());
 
-    #[test]
-    fn test_cell_state_from_char() {
-        assert_eq!(CellState::from('.'), CellState::Empty);
-        assert_eq!(CellState::from('@'), CellState::Player1);
-        assert_eq!(CellState::from('$'), CellState::Player2);
-        assert_eq!(CellState::from('a'), CellState::Player1Last);
-        assert_eq!(CellState::from('s'), CellState::Player2Last);
+        for (pos, state) in self.iter_positions() {
+            if state != CellState::Empty {
+                continue;
+            }
+            match (player1[pos.y][pos.x], player2[pos.y][pos.x]) {
+                (Some(a), Some(b)) if a == b => {
+                    if rng.random_bool(0.5) { count1 += 1 } else { count2 += 1 }
+                }
+                (Some(a), Some(b)) if a < b => count1 += 1,
+                (Some(_), None) => count1 += 1,
+                (_, Some(_)) => count2 += 1,
+                (None, None) => {}
+            }
+        }
+
+        (count1, count2)
     }
 
-    #[test]
-    fn test_position() {
-        let p = Position::new(5, 10);
-        assert_eq!(p.x, 5);
-        assert_eq!(p.y, 10);
+    /// Mean of `RANDOM_FILL_SIMULATIONS` runs of `simulate_random_fill`
+    pub fn expected_final_territory(
+        &self,
+        player1_territory: &[Position],
+        player2_territory: &[Position],
+        rng: &mut impl Rng,
+    ) -> (f32, f32) {
+        let (total1, total2) = (0..RANDOM_FILL_SIMULATIONS).fold((0, 0), |(a, b), _| {
+            let (count1, count2) = self.simulate_random_fill(player1_territory, player2_territory, rng);
+            (a + count1, b + count2)
+        });
+        let runs = RANDOM_FILL_SIMULATIONS as f32;
+        (total1 as f32 / runs, total2 as f32 / runs)
     }
 
-    #[test]
-    fn test_grid_creation() {
-        let raw = vec![
-            vec!['.', '@', '.'],
-            vec!['.', '.', '.'],
-            vec!['.', '$', '.'],
-        ];
-        let grid = Grid::from_chars(3, 3, raw);
-        assert_eq!(grid.width, 3);
-        assert_eq!(grid.height, 3);
+    /// Copy with thin parts of `player_num`'s territory removed
+    ///
+    /// Every cell of theirs with fewer than `radius` orthogonal neighbors
+    /// of theirs becomes empty, leaving the solid core of the territory.
+    /// Neighbors are counted on the original grid.
+    pub fn erode_territory(&self, player_num: u8, radius: usize) -> Grid {
+        let mut eroded = self.clone();
+        for (pos, state) in self.iter_positions() {
+            if state.is_player(player_num) && self.count_owned_neighbors(pos, player_num) < radius {
+                eroded.set(pos, CellState::Empty);
+            }
+        }
+        eroded
     }
 
-    #[test]
-    fn test_grid_get_set() {
-        let raw = vec![vec!['.'; 3]; 3];
-        let mut grid = Grid::from_chars(3, 3, raw);
+    /// Copy with `player_num`'s territory grown into nearby empty cells
+    ///
+    /// Every empty cell with at least `radius` orthogonal neighbors owned by
+    /// `player_num` becomes theirs. Neighbors are counted on the original
+    /// grid.
+    pub fn dilate_territory(&self, player_num: u8, radius: usize) -> Grid {
+        let mut dilated = self.clone();
+        let Some((owned, _)) = CellState::player_states(player_num) else {
+            return dilated;
+        };
+        for (pos, state) in self.iter_positions() {
+            if state == CellState::Empty && self.count_owned_neighbors(pos, player_num) >= radius {
+                dilated.set(pos, owned);
+            }
+        }
+        dilated
+    }
 
-        let pos = Position::new(1, 1);
-        grid.set(pos, CellState::Player1);
-        assert_eq!(grid.get(pos), Some(CellState::Player1));
+    /// Orthogonal neighbors of `pos` owned by `player_num`
+    fn count_owned_neighbors(&self, pos: Position, player_num: u8) -> usize {
+        [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| pos.checked_add(dx, dy))
+            .filter(|&n| self.get(n).is_some_and(|c| c.is_player(player_num)))
+            .count()
     }
 
-    #[test]
-    fn test_grid_bounds() {
-        let raw = vec![vec!['.'; 3]; 3];
-        let grid = Grid::from_chars(3, 3, raw);
+    /// How many valid `attacker` placements of `piece` cover each empty cell
+    ///
+    /// Cells with high counts are where the attacker can expand most
+    /// easily. Indexed as `map[y][x]`; occupied cells stay 0 and counts
+    /// saturate at `u8::MAX`.
+    pub fn compute_threat_map(&self, attacker: u8, piece: &Shape) -> Vec<Vec<u8>> {
+        let mut threat = vec![vec![0u8; self.width]; self.height];
+        let attacker_view = GameState::new(attacker, self.clone(), piece.clone());
 
-        assert!(grid.is_valid(Position::new(0, 0)));
-        assert!(grid.is_valid(Position::new(2, 2)));
-        assert!(!grid.is_valid(Position::new(3, 3)));
+        for placement in find_all_valid_placements(&attacker_view) {
+            for pos in placement.get_absolute_positions() {
+                if self.get(pos) == Some(CellState::Empty) {
+                    threat[pos.y][pos.x] = threat[pos.y][pos.x].saturating_add(1);
+                }
+            }
+        }
+        threat
     }
 
-    #[test]
-    fn test_shape_from_chars() {
-        let raw = vec![vec!['.', '#'], vec!['#', '.']];
-        let shape = Shape::from_chars(2, 2, raw);
-        let filled = shape.get_filled_positions();
-        assert_eq!(filled.len(), 2);
+    /// Empty cells whose removal splits their empty region, in row-major order
+    ///
+    /// Runs Tarjan's articulation-point DFS over the 4-connected graph of
+    /// empty cells. The DFS keeps an explicit stack so large open boards do
+    /// not overflow the call stack.
+    pub fn articulation_points(&self) -> Vec<Position> {
+        self.articulation_cuts().into_iter().map(|(pos, _)| pos).collect()
     }
 
-    #[test]
-    fn test_shape_bounding_box() {
+    /// Articulation points with the number of empty cells each one cuts off
+    ///
+    /// Removing a bottleneck splits its region; the cells cut off are those
+    /// outside the largest remaining piece. Sorted by that count, largest
+    /// first, then in row-major order.
+    pub fn find_bottlenecks(&self) -> Vec<(Position, usize)> {
+        let mut bottlenecks = self.articulation_cuts();
+        bottlenecks.sort_by_key(|&(_, cut)| std::cmp::Reverse(cut));
+        bottlenecks
+    }
+
+    /// Articulation points in row-major order, each with the cells it cuts off
+    ///
+    /// Tracks DFS subtree sizes: a child subtree whose low link does not
+    /// reach above its parent is separated from the region when the parent
+    /// is removed, and everything else in the region stays together.
+    fn articulation_cuts(&self) -> Vec<(Position, usize)> {
+        const UNVISITED: usize = usize::MAX;
+        let count = self.cells.len();
+        let mut discovery = vec![UNVISITED; count];
+        let mut low = vec![0; count];
+        let mut subtree = vec![1; count];
+        // Per cell: number, total size and largest size of separated child subtrees
+        let mut separated = vec![(0usize, 0usize, 0usize); count];
+        let mut cuts = vec![None; count];
+        let mut timer = 0;
+
+        let empty_neighbors = |index: usize| {
+            let (x, y) = ((index % self.width) as i32, (index / self.width) as i32);
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .filter(move |&(dx, dy)| self.get_signed(x + dx, y + dy) == Some(CellState::Empty))
+                .map(move |(dx, dy)| (y + dy) as usize * self.width + (x + dx) as usize)
+        };
+
+        for root in 0..count {
+            if self.cells[root] != CellState::Empty || discovery[root] != UNVISITED {
+                continue;
+            }
+
+            discovery[root] = timer;
+            low[root] = timer;
+            timer += 1;
+            let mut region = vec![root];
+            // (cell, parent, neighbors not yet explored)
+            let mut stack = vec![(root, UNVISITED, empty_neighbors(root))];
+
+            while let Some((cell, parent, neighbors)) = stack.last_mut() {
+                let (cell, parent) = (*cell, *parent);
+                match neighbors.next() {
+                    Some(next) if discovery[next] == UNVISITED => {
+                        discovery[next] = timer;
+                        low[next] = timer;
+                        timer += 1;
+                        region.push(next);
+                        stack.push((next, cell, empty_neighbors(next)));
+                    }
+                    Some(next) => {
+                        if next != parent {
+                            low[cell] = low[cell].min(discovery[next]);
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        if parent != UNVISITED {
+                            low[parent] = low[parent].min(low[cell]);
+                            subtree[parent] += subtree[cell];
+                            // Every child of the root is separated from the others
+                            if low[cell] >= discovery[parent] {
+                                let (n, total, largest) = &mut separated[parent];
+                                *n += 1;
+                                *total += subtree[cell];
+                                *largest = (*largest).max(subtree[cell]);
+                            }
+                        }
+                    }
+                }
+            }
+
+            let size = subtree[root];
+            for &cell in &region {
+                let (n, total, largest) = separated[cell];
+                let is_articulation = if cell == root { n > 1 } else { n > 0 };
+                if is_articulation {
+                    let rest = size - 1 - total;
+                    cuts[cell] = Some(size - 1 - largest.max(rest));
+                }
+            }
+        }
+
+        (0..count)
+            .filter_map(|index| cuts[index].map(|cut| (Position::new(index % self.width, index / self.width), cut)))
+            .collect()
+    }
+
+    /// Stamp `shape` with its top-left corner at `pos`, all or nothing
+    ///
+    /// Every filled cell is checked before any is written, so an error
+    /// leaves the grid untouched. Empty cells under the shape become
+    /// `state`; occupied ones (such as the contact cell) are kept.
+    pub fn apply_shape_at(&mut self, pos: Position, shape: &Shape, state: CellState) -> Result<(), PlacementError> {
+        let cells = shape
+            .get_filled_positions()
+            .into_iter()
+            .map(|cell| {
+                let target = Position::new(pos.x.checked_add(cell.x)?, pos.y.checked_add(cell.y)?);
+                self.is_valid(target).then_some(target)
+            })
+            .collect::<Option<Vec<Position>>>()
+            .ok_or(PlacementError::OutOfBounds)?;
+        if cells.is_empty() {
+            return Err(PlacementError::EmptyShape);
+        }
+
+        for target in cells {
+            if self.get(target) == Some(CellState::Empty) {
+                self.set(target, state);
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a placement onto the grid for `player`
+    ///
+    /// The player's previous last-piece cells revert to normal territory and
+    /// the new piece is marked as their last piece, as the game engine does.
+    /// The piece is written with `apply_shape_at`, so a placement that does
+    /// not fit the board changes nothing.
+    ///
+    /// Returns the change to the grid's Zobrist hash, so callers can update
+    /// a cached hash with a single XOR.
+    pub fn apply_placement(&mut self, placement: &Placement, player: u8) -> u64 {
+        let Some((territory, last)) = CellState::player_states(player) else {
+            return 0;
+        };
+
+        let previous_last: Vec<usize> = (0..self.cells.len()).filter(|&i| self.cells[i] == last).collect();
+        let fresh: Vec<Position> = placement
+            .get_absolute_positions()
+            .into_iter()
+            .filter(|&pos| self.get(pos) == Some(CellState::Empty))
+            .collect();
+        if self.apply_shape_at(placement.position, &placement.shape, last).is_err() {
+            return 0;
+        }
+
+        let mut delta = 0;
+        for index in previous_last {
+            self.cells[index] = territory;
+            delta ^= zobrist_key(index, last) ^ zobrist_key(index, territory);
+        }
+        for pos in fresh {
+            delta ^= zobrist_key(pos.y * self.width + pos.x, last);
+        }
+        delta
+    }
+
+    /// Zobrist hash of the cell contents, computed from scratch
+    pub fn zobrist_hash(&self) -> u64 {
+        self.cells
+            .iter()
+            .enumerate()
+            .fold(0, |hash, (index, &state)| hash ^ zobrist_key(index, state))
+    }
+
+    /// Convert to a bitboard representation
+    pub fn as_bitboard(&self) -> BitGrid {
+        BitGrid::from_grid(self)
+    }
+
+    /// Rewrite the grid from `player`'s point of view
+    ///
+    /// `player`'s cells become Player 1 cells and every other player's
+    /// cells become Player 2 cells, preserving last-piece markers.
+    pub fn normalize_player_perspective(&self, player: u8) -> Grid {
+        let cells = self
+            .cells
+            .iter()
+            .map(|&state| match state.player_num() {
+                None => CellState::Empty,
+                Some(owner) => {
+                    let (territory, last) = if owner == player {
+                        (CellState::Player1, CellState::Player1Last)
+                    } else {
+                        (CellState::Player2, CellState::Player2Last)
+                    };
+                    if state.is_last_placed() { last } else { territory }
+                }
+            })
+            .collect();
+
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells,
+        }
+    }
+
+    /// Debug rendering with a size header and row numbers
+    pub fn to_debug_string(&self) -> String {
+        let mut out = format!("=== Grid: {} x {} ===\n", self.width, self.height);
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            out.push_str(&format!("{:03} ", y));
+            for cell in row {
+                out.push_str(&cell.to_string());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Binary grayscale PGM (P5) image, one pixel per cell
+    ///
+    /// See `CellState::gray_level` for the palette.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut image = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        image.extend(self.cells.iter().map(CellState::gray_level));
+        image
+    }
+
+    /// Binary color PPM (P6) image, one pixel per cell
+    ///
+    /// See `CellState::rgb` for the palette.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut image = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        image.extend(self.cells.iter().flat_map(CellState::rgb));
+        image
+    }
+
+    /// Print the grid for debugging
+    pub fn print(&self) {
+        eprint!("{}", self.to_debug_string());
+    }
+}
+
+/// Compact text format: one char per cell, rows separated by newlines
+///
+/// Round-trips through `str::parse::<Grid>()`.
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `Grid` that keeps its empty-cell count up to date
+///
+/// Reads go to the wrapped grid through `Deref`. Writes must use
+/// `TrackedGrid::set` or `TrackedGrid::apply_placement`, which adjust the
+/// count in O(1) per changed cell; there is no `DerefMut`, so the count
+/// cannot drift.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedGrid {
+    grid: Grid,
+    empty_count: usize,
+}
+
+impl TrackedGrid {
+    /// Wrap `grid`, counting its empty cells once
+    pub fn new(grid: Grid) -> Self {
+        let empty_count = grid.count_empty();
+        TrackedGrid { grid, empty_count }
+    }
+
+    /// Number of empty cells
+    pub fn empty_count(&self) -> usize {
+        self.empty_count
+    }
+
+    /// The wrapped grid
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Unwrap into the plain grid
+    pub fn into_inner(self) -> Grid {
+        self.grid
+    }
+
+    /// Set cell state at position (see `Grid::set`)
+    pub fn set(&mut self, pos: Position, state: CellState) -> bool {
+        let Some(previous) = self.grid.get(pos) else {
+            return false;
+        };
+        if previous == CellState::Empty {
+            self.empty_count -= 1;
+        }
+        if state == CellState::Empty {
+            self.empty_count += 1;
+        }
+        self.grid.set(pos, state)
+    }
+
+    /// Stamp `placement` for `player` (see `Grid::apply_placement`)
+    pub fn apply_placement(&mut self, placement: &Placement, player: u8) -> u64 {
+        // Only empty cells under the piece change between empty and taken,
+        // and nothing changes if part of the piece is off the board
+        let cells = placement.get_absolute_positions();
+        let filled = cells
+            .iter()
+            .filter(|&&pos| self.grid.get(pos) == Some(CellState::Empty))
+            .count();
+        if CellState::player_states(player).is_some() && cells.iter().all(|&pos| self.grid.is_valid(pos)) {
+            self.empty_count -= filled;
+        }
+        self.grid.apply_placement(placement, player)
+    }
+}
+
+impl std::ops::Deref for TrackedGrid {
+    type Target = Grid;
+
+    fn deref(&self) -> &Grid {
+        &self.grid
+    }
+}
+
+impl From<Grid> for TrackedGrid {
+    fn from(grid: Grid) -> Self {
+        TrackedGrid::new(grid)
+    }
+}
+
+/// Error parsing a `Grid` or `Shape` from its compact text format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Input has no rows
+    Empty,
+    /// A row's length differs from the first row's
+    RaggedRow { row: usize, expected: usize, found: usize },
+    /// A character that is not a valid cell
+    InvalidChar { row: usize, col: usize, ch: char },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Text is empty"),
+            ParseError::RaggedRow { row, expected, found } => {
+                write!(f, "Row {} has {} cells, expected {}", row, found, expected)
+            }
+            ParseError::InvalidChar { row, col, ch } => {
+                write!(f, "Invalid cell '{}' at row {}, column {}", ch, row, col)
+            }
+        }
+    }
+}
+
+/// Parse rectangular text into rows of cells
+///
+/// Blank lines and trailing whitespace are ignored; `cell` maps each
+/// character, returning `None` for invalid ones. Returns the width and the
+/// rows.
+fn parse_rows<T>(s: &str, cell: impl Fn(char) -> Option<T>) -> Result<(usize, Vec<Vec<T>>), ParseError> {
+    let rows: Vec<&str> = s.lines().map(str::trim_end).filter(|l| !l.is_empty()).collect();
+    let width = rows.first().ok_or(ParseError::Empty)?.chars().count();
+
+    let mut parsed = Vec::with_capacity(rows.len());
+    for (y, row) in rows.iter().enumerate() {
+        let found = row.chars().count();
+        if found != width {
+            return Err(ParseError::RaggedRow { row: y, expected: width, found });
+        }
+        let cells = row
+            .chars()
+            .enumerate()
+            .map(|(x, ch)| cell(ch).ok_or(ParseError::InvalidChar { row: y, col: x, ch }))
+            .collect::<Result<Vec<T>, _>>()?;
+        parsed.push(cells);
+    }
+    Ok((width, parsed))
+}
+
+impl std::str::FromStr for Grid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, rows) = parse_rows(s, |ch| {
+            let state = CellState::from(ch);
+            (state != CellState::Empty || ch == '.').then_some(state)
+        })?;
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+}
+
+/// Bitboard view of a `Grid`
+///
+/// Each player (1-4) gets one bit per cell, last-piece cells included.
+/// Rows are padded to whole `u64` words so a shifted piece row can be
+/// tested against the board with a couple of AND operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    pub width: usize,
+    pub height: usize,
+    words_per_row: usize,
+    players: [Vec<u64>; 4],
+    /// Union of all players' bitboards
+    occupied: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Build the bitboards for a grid
+    pub fn from_grid(grid: &Grid) -> Self {
+        let words_per_row = grid.width.div_ceil(64).max(1);
+        let mut players: [Vec<u64>; 4] = Default::default();
+        for board in players.iter_mut() {
+            *board = vec![0; words_per_row * grid.height];
+        }
+
+        for (pos, state) in grid.iter_positions() {
+            if let Some(owner) = state.player_num() {
+                let index = pos.y * words_per_row + pos.x / 64;
+                players[(owner - 1) as usize][index] |= 1 << (pos.x % 64);
+            }
+        }
+        let occupied = (0..words_per_row * grid.height)
+            .map(|i| players.iter().fold(0, |acc, board| acc | board[i]))
+            .collect();
+
+        BitGrid {
+            width: grid.width,
+            height: grid.height,
+            words_per_row,
+            players,
+            occupied,
+        }
+    }
+
+    /// Check whether `player` owns the cell at `pos`
+    pub fn is_owned_by(&self, pos: Position, player: u8) -> bool {
+        if !(1..=4).contains(&player) || pos.x >= self.width || pos.y >= self.height {
+            return false;
+        }
+        let word = self.players[(player - 1) as usize][pos.y * self.words_per_row + pos.x / 64];
+        word & (1 << (pos.x % 64)) != 0
+    }
+
+    /// Count how many of `mask`'s cells at `pos` overlap `player`'s territory
+    ///
+    /// Returns `None` if the piece leaves the board or overlaps any other
+    /// player's territory.
+    pub fn territory_overlap(&self, mask: &ShapeMask, pos: Position, player: u8) -> Option<usize> {
+        if !(1..=4).contains(&player)
+            || pos.x + mask.max_x >= self.width
+            || pos.y + mask.max_y >= self.height
+        {
+            return None;
+        }
+
+        let own = &self.players[(player - 1) as usize];
+        let word = pos.x / 64;
+        let offset = pos.x % 64;
+        let mut touches = 0;
+        for (dy, &row) in mask.rows.iter().enumerate() {
+            if row == 0 {
+                continue;
+            }
+            let base = (pos.y + dy) * self.words_per_row + word;
+            let lo = row << offset;
+            let hi = if offset == 0 { 0 } else { row >> (64 - offset) };
+
+            let mut occupied_hits = (self.occupied[base] & lo).count_ones();
+            let mut own_hits = (own[base] & lo).count_ones();
+            if hi != 0 {
+                occupied_hits += (self.occupied[base + 1] & hi).count_ones();
+                own_hits += (own[base + 1] & hi).count_ones();
+            }
+            if occupied_hits != own_hits {
+                return None;
+            }
+            touches += own_hits as usize;
+        }
+
+        Some(touches)
+    }
+}
+
+/// A piece shape as one `u64` bit mask per row, for use with `BitGrid`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeMask {
+    pub rows: Vec<u64>,
+    /// Largest x offset of a filled cell
+    pub max_x: usize,
+    /// Largest y offset of a filled cell
+    pub max_y: usize,
+    /// Number of filled cells
+    pub cells: usize,
+}
+
+impl ShapeMask {
+    /// Build a mask from a shape, or `None` if it is empty or wider than 64
+    pub fn from_shape(shape: &Shape) -> Option<Self> {
+        let filled = shape.get_filled_positions();
+        if filled.is_empty() || shape.width > 64 {
+            return None;
+        }
+
+        let mut rows = vec![0u64; shape.height];
+        for pos in &filled {
+            rows[pos.y] |= 1 << pos.x;
+        }
+
+        Some(ShapeMask {
+            rows,
+            max_x: filled.iter().map(|p| p.x).max().unwrap_or(0),
+            max_y: filled.iter().map(|p| p.y).max().unwrap_or(0),
+            cells: filled.len(),
+        })
+    }
+}
+
+/// Mirror and rotation symmetries of a shape's filled pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// No mirror or half-turn symmetry
+    None,
+    /// Unchanged by `flip_horizontal` (left-right mirror) only
+    Horizontal,
+    /// Unchanged by `flip_vertical` (top-bottom mirror) only
+    Vertical,
+    /// Unchanged by `rotate_180` but by neither mirror
+    Rotational180,
+    /// Unchanged by both mirrors, and therefore by `rotate_180`
+    Full,
+}
+
+/// Represents a piece shape
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<Vec<bool>>, // true = filled, false = empty
+}
+
+impl Shape {
+    /// Create a new shape from raw character data
+    pub fn from_chars(width: usize, height: usize, raw: Vec<Vec<char>>) -> Self {
+        let cells = raw
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|c| c != '.')
+                    .collect()
+            })
+            .collect();
+
+        Shape {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Get all filled cell positions relative to top-left (0, 0)
+    pub fn get_filled_positions(&self) -> Vec<Position> {
+        let mut positions = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.cells[y][x] {
+                    positions.push(Position::new(x, y));
+                }
+            }
+        }
+        positions
+    }
+
+    /// Check if the shape has any filled cells
+    pub fn is_empty(&self) -> bool {
+        self.get_filled_positions().is_empty()
+    }
+
+    /// Get bounding box of the filled cells
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let positions = self.get_filled_positions();
+        if positions.is_empty() {
+            return None;
+        }
+
+        let min_x = positions.iter().map(|p| p.x).min().unwrap();
+        let max_x = positions.iter().map(|p| p.x).max().unwrap();
+        let min_y = positions.iter().map(|p| p.y).min().unwrap();
+        let max_y = positions.iter().map(|p| p.y).max().unwrap();
+
+        Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+    }
+
+    /// Number of filled cells
+    pub fn area(&self) -> usize {
+        self.cells.iter().flatten().filter(|&&filled| filled).count()
+    }
+
+    /// Number of filled-cell edges that face an empty cell or the shape boundary
+    pub fn perimeter(&self) -> usize {
+        let filled = |x: i32, y: i32| {
+            x >= 0
+                && y >= 0
+                && (y as usize) < self.height
+                && (x as usize) < self.width
+                && self.cells[y as usize][x as usize]
+        };
+
+        self.get_filled_positions()
+            .iter()
+            .map(|p| {
+                let (x, y) = (p.x as i32, p.y as i32);
+                [(0, -1), (0, 1), (-1, 0), (1, 0)]
+                    .iter()
+                    .filter(|(dx, dy)| !filled(x + dx, y + dy))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Isoperimetric ratio `4π·area / perimeter²`
+    ///
+    /// Higher is more compact: a single cell or 2x2 square scores about
+    /// 0.79, a straight 4-cell bar 0.5. An empty shape scores 0.
+    pub fn compactness(&self) -> f32 {
+        let perimeter = self.perimeter();
+        if perimeter == 0 {
+            return 0.0;
+        }
+        4.0 * std::f32::consts::PI * self.area() as f32 / (perimeter * perimeter) as f32
+    }
+
+    /// Empty cells enclosed by the piece, relative to top-left (0, 0)
+    ///
+    /// Flood-fills the empty cells of the filled cells' bounding box from its
+    /// edge; empty cells the fill cannot reach are holes.
+    pub fn interior_hole_positions(&self) -> Vec<Position> {
+        let Some((min_x, min_y, width, height)) = self.bounding_box() else {
+            return Vec::new();
+        };
+        let empty = |pos: Position| !self.cells[min_y + pos.y][min_x + pos.x];
+        let local = || (0..height).flat_map(|y| (0..width).map(move |x| Position::new(x, y)));
+
+        let mut outside = vec![vec![false; width]; height];
+        let mut queue: VecDeque<Position> = local()
+            .filter(|&p| (p.x == 0 || p.y == 0 || p.x + 1 == width || p.y + 1 == height) && empty(p))
+            .collect();
+        for p in &queue {
+            outside[p.y][p.x] = true;
+        }
+
+        while let Some(pos) = queue.pop_front() {
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let Some(n) = pos.checked_add(dx, dy) else {
+                    continue;
+                };
+                if n.x < width && n.y < height && !outside[n.y][n.x] && empty(n) {
+                    outside[n.y][n.x] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        local()
+            .filter(|&p| empty(p) && !outside[p.y][p.x])
+            .map(|p| Position::new(min_x + p.x, min_y + p.y))
+            .collect()
+    }
+
+    /// Number of empty cells fully enclosed by the piece
+    pub fn count_interior_holes(&self) -> usize {
+        self.interior_hole_positions().len()
+    }
+
+    /// Translate the filled cells to the top-left and trim the bounding box
+    ///
+    /// An empty shape normalizes to a 0x0 shape.
+    pub fn normalize(&self) -> Shape {
+        let Some((min_x, min_y, width, height)) = self.bounding_box() else {
+            return Shape {
+                width: 0,
+                height: 0,
+                cells: Vec::new(),
+            };
+        };
+        let cells = self.cells[min_y..min_y + height]
+            .iter()
+            .map(|row| row[min_x..min_x + width].to_vec())
+            .collect();
+
+        Shape {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Sorted normalized filled positions, usable as a `HashMap` key
+    ///
+    /// Shapes with the same filled pattern share a key regardless of framing.
+    pub fn canonical_key(&self) -> Vec<(usize, usize)> {
+        let mut key: Vec<(usize, usize)> = self
+            .normalize()
+            .get_filled_positions()
+            .into_iter()
+            .map(|p| (p.x, p.y))
+            .collect();
+        key.sort_unstable();
+        key
+    }
+
+    /// Rotate the shape 90 degrees clockwise
+    pub fn rotate_90(&self) -> Shape {
+        let cells = (0..self.width)
+            .map(|x| (0..self.height).rev().map(|y| self.cells[y][x]).collect())
+            .collect();
+
+        Shape {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotate the shape 180 degrees
+    pub fn rotate_180(&self) -> Shape {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// Rotate the shape 270 degrees clockwise (90 counter-clockwise)
+    pub fn rotate_270(&self) -> Shape {
+        self.rotate_180().rotate_90()
+    }
+
+    /// Mirror the shape left to right
+    pub fn flip_horizontal(&self) -> Shape {
+        let cells = self
+            .cells
+            .iter()
+            .map(|row| row.iter().rev().copied().collect())
+            .collect();
+
+        Shape {
+            width: self.width,
+            height: self.height,
+            cells,
+        }
+    }
+
+    /// Mirror the shape top to bottom
+    pub fn flip_vertical(&self) -> Shape {
+        Shape {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().rev().cloned().collect(),
+        }
+    }
+
+    /// Classify the shape's symmetry by comparing its filled pattern with
+    /// its mirror images and half-turn
+    pub fn symmetry_class(&self) -> Symmetry {
+        let key = self.canonical_key();
+        let horizontal = self.flip_horizontal().canonical_key() == key;
+        let vertical = self.flip_vertical().canonical_key() == key;
+
+        match (horizontal, vertical) {
+            (true, true) => Symmetry::Full,
+            (true, false) => Symmetry::Horizontal,
+            (false, true) => Symmetry::Vertical,
+            (false, false) if self.rotate_180().canonical_key() == key => Symmetry::Rotational180,
+            (false, false) => Symmetry::None,
+        }
+    }
+
+    /// Print the shape for debugging
+    pub fn print(&self) {
+        eprintln!("=== Shape: {} x {} ===", self.width, self.height);
+        eprintln!("{}", self);
+    }
+}
+
+/// Compact text format: `#` for filled, `.` for empty, rows separated by newlines
+///
+/// Round-trips through `str::parse::<Shape>()`.
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells.iter().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &filled in row {
+                write!(f, "{}", if filled { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Shape {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, cells) = parse_rows(s, |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+
+        Ok(Shape {
+            width,
+            height: cells.len(),
+            cells,
+        })
+    }
+}
+
+/// Stage of the game, based on how full the board is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePhase {
+    /// Less than 25% of the board occupied
+    Early,
+    /// 25% to 60% of the board occupied
+    Mid,
+    /// More than 60% of the board occupied
+    Late,
+}
+
+impl fmt::Display for GamePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GamePhase::Early => "Early",
+            GamePhase::Mid => "Mid",
+            GamePhase::Late => "Late",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Structured snapshot of a game state for debug output
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameDebugInfo {
+    pub player: u8,
+    pub my_territory: usize,
+    pub opponent_territory: usize,
+    pub turn_number: usize,
+    pub grid_repr: String,
+    pub piece_repr: String,
+    pub phase: GamePhase,
+    pub is_winning: bool,
+}
+
+impl fmt::Display for GameDebugInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n=== Game State ===")?;
+        writeln!(
+            f,
+            "Player: {} | Turn: {} | Phase: {}",
+            self.player, self.turn_number, self.phase
+        )?;
+        writeln!(
+            f,
+            "My Territory: {} | Opponent Territory: {}{}",
+            self.my_territory,
+            self.opponent_territory,
+            if self.is_winning { " (winning)" } else { "" }
+        )?;
+        write!(f, "{}", self.grid_repr)?;
+        writeln!(f)?;
+        write!(f, "{}", self.piece_repr)
+    }
+}
+
+/// Cells that changed between two game states
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardDiff {
+    /// Each changed cell with its state before and after, in row-major order
+    pub changed_cells: Vec<(Position, CellState, CellState)>,
+}
+
+impl BoardDiff {
+    /// Whether no cell changed
+    pub fn is_empty(&self) -> bool {
+        self.changed_cells.is_empty()
+    }
+
+    /// Positions that went from empty to owned by `player`
+    pub fn placed_by(&self, player: u8) -> Vec<Position> {
+        self.changed_cells
+            .iter()
+            .filter(|(_, from, to)| *from == CellState::Empty && to.is_player(player))
+            .map(|&(pos, _, _)| pos)
+            .collect()
+    }
+}
+
+/// Summary of one player's territory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Territory {
+    /// Every cell the player owns, in row-major order
+    pub cells: Vec<Position>,
+    /// Owned cells with at least one empty neighbor (see `Grid::territory_border`)
+    pub border: Vec<Position>,
+    /// Owned-cell edges facing a cell the player does not own or the board edge
+    pub perimeter: usize,
+    /// Number of 4-connected islands
+    pub connected_components: usize,
+    /// Mean `(x, y)` of the owned cells, `(0.0, 0.0)` if there are none
+    pub centroid: (f32, f32),
+    /// `(min_x, min_y, width, height)` of the owned cells, all 0 if there are none
+    pub bounding_box: (usize, usize, usize, usize),
+}
+
+impl Territory {
+    /// Analyse `player_num`'s territory on `grid`
+    pub fn for_player(grid: &Grid, player_num: u8) -> Territory {
+        let cells = grid.get_player_positions(player_num);
+        let border = grid.territory_border(player_num);
+        let connected_components = grid.connected_components(player_num).len();
+        let perimeter = cells
+            .iter()
+            .map(|&pos| {
+                let owned = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+                    .into_iter()
+                    .filter_map(|(dx, dy)| pos.checked_add(dx, dy))
+                    .filter(|&n| grid.get(n).is_some_and(|c| c.is_player(player_num)))
+                    .count();
+                4 - owned
+            })
+            .sum();
+
+        let (centroid, bounding_box) = if cells.is_empty() {
+            ((0.0, 0.0), (0, 0, 0, 0))
+        } else {
+            let count = cells.len() as f32;
+            let sum_x: usize = cells.iter().map(|p| p.x).sum();
+            let sum_y: usize = cells.iter().map(|p| p.y).sum();
+
+            let min_x = cells.iter().map(|p| p.x).min().unwrap();
+            let max_x = cells.iter().map(|p| p.x).max().unwrap();
+            // Row-major order puts the top row first and the bottom row last
+            let min_y = cells[0].y;
+            let max_y = cells[cells.len() - 1].y;
+
+            (
+                (sum_x as f32 / count, sum_y as f32 / count),
+                (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1),
+            )
+        };
+
+        Territory {
+            cells,
+            border,
+            perimeter,
+            connected_components,
+            centroid,
+            bounding_box,
+        }
+    }
+
+    /// Number of owned cells
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the player owns no cells
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+/// Number of recent turns `GameState::print` shows
+pub const HISTORY_PRINT_TURNS: usize = 5;
+
+/// Turns `GameState::is_stalled` looks back over
+pub const STALL_WINDOW: usize = 3;
+
+/// Most cells per player over `STALL_WINDOW` turns that still counts as a stall
+pub const STALL_MAX_GROWTH: isize = 2;
+
+/// Metrics recorded for one past turn
+///
+/// Territory sizes are stored per player number rather than as ours and
+/// the opponent's, so an entry reads the same whichever side is to move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnHistory {
+    pub turn: u32,
+    /// Player who moved this turn
+    pub player: u8,
+    /// Territory sizes of players 1 and 2 after the turn
+    pub territory: [usize; 2],
+    /// The mover's placement, `None` if it could not move or is unknown
+    pub placement: Option<Placement>,
+    /// Cells the mover claimed
+    pub cells_gained: usize,
+}
+
+impl TurnHistory {
+    /// Record `player`'s turn from the board as it stands after the move
+    pub fn after_turn(turn: u32, player: u8, grid: &Grid, placement: Option<Placement>) -> Self {
+        TurnHistory {
+            turn,
+            player,
+            territory: [grid.count_territory(1), grid.count_territory(2)],
+            cells_gained: placement.as_ref().map_or(0, |p| p.cells_added),
+            placement,
+        }
+    }
+
+    /// Territory size of `player` after the turn (0 for other players)
+    pub fn territory_of(&self, player: u8) -> usize {
+        match player {
+            1 | 2 => self.territory[(player - 1) as usize],
+            _ => 0,
+        }
+    }
+
+    /// Same entry with players 1 and 2 exchanged, as `GameState::swap_perspective` does
+    pub fn swapped(&self) -> TurnHistory {
+        TurnHistory {
+            player: GameState::opponent_of(self.player),
+            territory: [self.territory[1], self.territory[0]],
+            ..self.clone()
+        }
+    }
+}
+
+impl fmt::Display for TurnHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "turn {} (p{}): {} vs {} (+{})",
+            self.turn, self.player, self.territory[0], self.territory[1], self.cells_gained
+        )?;
+        if let Some(placement) = &self.placement {
+            write!(f, " at ({}, {})", placement.position.x, placement.position.y)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents the complete game state
+#[derive(Debug, Clone)]
+pub struct GameState {
+    player_number: u8,
+    /// Board; private so every edit goes through a setter that drops the
+    /// cached hash and territories
+    grid: TrackedGrid,
+    pub current_piece: Shape,
+    /// Turns played so far (0 when unknown, e.g. a single engine input)
+    pub turn_number: usize,
+    /// Past turns, oldest first (empty when unknown); shared between
+    /// search nodes
+    history: Arc<Vec<TurnHistory>>,
+    /// Cached Zobrist hash of the grid and player to move
+    zobrist: u64,
+    /// Lazily computed territory of the player to move
+    my_territory: OnceLock<Territory>,
+    /// Lazily computed territory of the opponent
+    opponent_territory: OnceLock<Territory>,
+}
+
+impl GameState {
+    /// Create a new game state
+    pub fn new(player_number: u8, grid: Grid, current_piece: Shape) -> Self {
+        let zobrist = grid.zobrist_hash() ^ zobrist_player_key(player_number);
+        GameState {
+            player_number,
+            grid: TrackedGrid::new(grid),
+            current_piece,
+            turn_number: 0,
+            history: Arc::default(),
+            zobrist,
+            my_territory: OnceLock::new(),
+            opponent_territory: OnceLock::new(),
+        }
+    }
+
+    /// Start building a game state on an empty board of the given size
+    pub fn builder_from_size(width: usize, height: usize) -> GameStateBuilder {
+        GameStateBuilder::new(width, height)
+    }
+
+    /// Player to move
+    pub fn player_number(&self) -> u8 {
+        self.player_number
+    }
+
+    /// Current board
+    pub fn grid(&self) -> &TrackedGrid {
+        &self.grid
+    }
+
+    /// The board, consuming the state
+    pub fn into_grid(self) -> Grid {
+        self.grid.into_inner()
+    }
+
+    /// Replace the board, dropping every cached value
+    pub fn set_grid(&mut self, grid: Grid) {
+        self.grid = TrackedGrid::new(grid);
+        self.refresh_caches();
+    }
+
+    /// Set one cell, dropping every cached value
+    ///
+    /// Returns `false` if `pos` is off the board.
+    pub fn set_cell(&mut self, pos: Position, state: CellState) -> bool {
+        let changed = self.grid.set(pos, state);
+        self.refresh_caches();
+        changed
+    }
+
+    /// Change the player to move, dropping every cached value
+    pub fn set_player_number(&mut self, player_number: u8) {
+        self.player_number = player_number;
+        self.refresh_caches();
+    }
+
+    /// Same state with `history` attached
+    pub fn with_history(mut self, history: Vec<TurnHistory>) -> GameState {
+        self.history = Arc::new(history);
+        self
+    }
+
+    /// Past turns, oldest first (empty when unknown)
+    pub fn history(&self) -> &[TurnHistory] {
+        &self.history
+    }
+
+    /// Territory change over the last `turns` recorded turns
+    ///
+    /// Returns `(ours, opponent's)` for the player to move, comparing the
+    /// latest history entry with the one `turns` entries earlier (or the
+    /// oldest). `(0, 0)` with fewer than two entries.
+    pub fn territory_delta(&self, turns: usize) -> (isize, isize) {
+        let Some(latest) = self.history.last() else {
+            return (0, 0);
+        };
+        let earlier = &self.history[self.history.len().saturating_sub(turns + 1)];
+        let delta = |player: u8| latest.territory_of(player) as isize - earlier.territory_of(player) as isize;
+
+        (delta(self.player_number), delta(self.opponent_number()))
+    }
+
+    /// Whether both players have grown slowly over the last `STALL_WINDOW` turns
+    ///
+    /// `false` until the history covers the whole window.
+    pub fn is_stalled(&self) -> bool {
+        if self.history.len() <= STALL_WINDOW {
+            return false;
+        }
+        let (mine, theirs) = self.territory_delta(STALL_WINDOW);
+        mine <= STALL_MAX_GROWTH && theirs <= STALL_MAX_GROWTH
+    }
+
+    /// Check whether the current player's territory is one connected island
+    pub fn is_territory_connected(&self) -> bool {
+        self.my_territory().connected_components <= 1
+    }
+
+    /// Get all positions belonging to the current player
+    pub fn get_my_positions(&self) -> Vec<Position> {
+        self.my_territory().cells.clone()
+    }
+
+    /// Current player's territory, computed on first use and cached
+    ///
+    /// The grid setters drop the cache.
+    pub fn my_territory(&self) -> &Territory {
+        self.my_territory
+            .get_or_init(|| Territory::for_player(&self.grid, self.player_number))
+    }
+
+    /// Opponent's territory, computed on first use and cached
+    ///
+    /// The grid setters drop the cache.
+    pub fn opponent_territory(&self) -> &Territory {
+        self.opponent_territory
+            .get_or_init(|| Territory::for_player(&self.grid, self.opponent_number()))
+    }
+
+    /// Euclidean distance between both players' territory centroids
+    ///
+    /// Small values mean the battle fronts are close. Returns `f32::INFINITY`
+    /// if either player owns no cells.
+    pub fn territory_centroid_distance(&self) -> f32 {
+        let (mine, theirs) = (self.my_territory(), self.opponent_territory());
+        if mine.size() == 0 || theirs.size() == 0 {
+            return f32::INFINITY;
+        }
+        let ((x1, y1), (x2, y2)) = (mine.centroid, theirs.centroid);
+        ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
+    }
+
+    /// Largest empty region we can reach minus the largest the opponent can
+    ///
+    /// Positive when we have access to more open space; decisive in the
+    /// late game, when the board has split into separate pockets. See
+    /// `Grid::largest_reachable_empty_regions`.
+    pub fn empty_region_advantage(&self) -> i32 {
+        let (player1, player2) = self.grid.largest_reachable_empty_regions();
+        let (mine, theirs) = if self.player_number == 1 { (player1, player2) } else { (player2, player1) };
+        mine as i32 - theirs as i32
+    }
+
+    /// Normalized entropy of the empty-region sizes, in `[0, 1]`
+    ///
+    /// 0 when the empty cells form at most one region, 1 when every empty
+    /// cell is isolated. The entropy is divided by its maximum, `ln` of the
+    /// number of empty cells. See `Grid::region_sizes`.
+    pub fn board_fragmentation(&self) -> f32 {
+        let sizes = self.grid.region_sizes();
+        let total: usize = sizes.iter().sum();
+        if sizes.len() <= 1 {
+            return 0.0;
+        }
+
+        let entropy: f32 = sizes
+            .iter()
+            .map(|&size| {
+                let p = size as f32 / total as f32;
+                -p * p.ln()
+            })
+            .sum();
+        entropy / (total as f32).ln()
+    }
+
+    /// Opponent player number in a two-player game
+    pub fn opponent_number(&self) -> u8 {
+        GameState::opponent_of(self.player_number)
+    }
+
+    /// Opponent of `player` in a two-player game
+    pub fn opponent_of(player: u8) -> u8 {
+        if player == 1 { 2 } else { 1 }
+    }
+
+    /// Same board with the opponent to move
+    ///
+    /// Unlike `swap_perspective`, the cells are untouched and only the
+    /// player number changes, so `my_territory` becomes the opponent's.
+    /// The turn number is kept.
+    pub fn for_opponent(&self) -> GameState {
+        let player_number = self.opponent_number();
+        GameState {
+            player_number,
+            grid: self.grid.clone(),
+            current_piece: self.current_piece.clone(),
+            turn_number: self.turn_number,
+            history: Arc::clone(&self.history),
+            zobrist: self.zobrist ^ zobrist_player_key(self.player_number) ^ zobrist_player_key(player_number),
+            my_territory: self.opponent_territory.clone(),
+            opponent_territory: self.my_territory.clone(),
+        }
+    }
+
+    /// All player numbers from `all_players` other than the current player
+    pub fn other_players(&self, all_players: &[u8]) -> Vec<u8> {
+        all_players
+            .iter()
+            .copied()
+            .filter(|&p| p != self.player_number)
+            .collect()
+    }
+
+    /// Get all positions belonging to the opponent
+    pub fn get_opponent_positions(&self) -> Vec<Position> {
+        self.opponent_territory().cells.clone()
+    }
+
+    /// Get current territory size for current player
+    pub fn get_my_territory_size(&self) -> usize {
+        self.my_territory().size()
+    }
+
+    /// Get opponent territory size
+    pub fn get_opponent_territory_size(&self) -> usize {
+        self.opponent_territory().size()
+    }
+
+    /// State after the current player makes `placement`
+    ///
+    /// The returned state has the opponent to move, keeps the current piece
+    /// (the next piece is unknown) and advances the turn number.
+    pub fn apply_placement(&self, placement: &Placement) -> GameState {
+        let mut grid = self.grid.clone();
+        let delta = grid.apply_placement(placement, self.player_number);
+        let player_number = self.opponent_number();
+
+        GameState {
+            player_number,
+            grid,
+            current_piece: self.current_piece.clone(),
+            turn_number: self.turn_number + 1,
+            history: Arc::clone(&self.history),
+            zobrist: self.zobrist
+                ^ delta
+                ^ zobrist_player_key(self.player_number)
+                ^ zobrist_player_key(player_number),
+            my_territory: OnceLock::new(),
+            opponent_territory: OnceLock::new(),
+        }
+    }
+
+    /// State after the opponent makes `placement`
+    ///
+    /// Stamps the placement with the opponent's cell states and keeps the
+    /// current player to move, so lookahead can play out an opponent reply
+    /// and evaluate from our side. Advances the turn number.
+    pub fn apply_opponent_placement(&self, placement: &Placement) -> GameState {
+        let mut grid = self.grid.clone();
+        let delta = grid.apply_placement(placement, self.opponent_number());
+
+        GameState {
+            player_number: self.player_number,
+            grid,
+            current_piece: self.current_piece.clone(),
+            turn_number: self.turn_number + 1,
+            history: Arc::clone(&self.history),
+            zobrist: self.zobrist ^ delta,
+            my_territory: OnceLock::new(),
+            opponent_territory: OnceLock::new(),
+        }
+    }
+
+    /// Same state with player 1 and player 2 cells exchanged
+    ///
+    /// The player number is kept, so the current player now owns what was
+    /// the opponent's territory: the result is the opponent's view of the
+    /// board, and any strategy run on it plays the opponent's move.
+    /// Last-piece markers are preserved; other players' cells are unchanged.
+    pub fn swap_perspective(&self) -> GameState {
+        let cells = self
+            .grid
+            .cells
+            .iter()
+            .map(|&state| match state {
+                CellState::Player1 => CellState::Player2,
+                CellState::Player2 => CellState::Player1,
+                CellState::Player1Last => CellState::Player2Last,
+                CellState::Player2Last => CellState::Player1Last,
+                other => other,
+            })
+            .collect();
+        let grid = Grid {
+            width: self.grid.width,
+            height: self.grid.height,
+            cells,
+        };
+
+        let mut swapped = GameState::new(self.player_number, grid, self.current_piece.clone());
+        swapped.turn_number = self.turn_number;
+        swapped.history = Arc::new(self.history.iter().map(TurnHistory::swapped).collect());
+        swapped
+    }
+
+    /// Every cell whose state differs between `before` and `after`
+    ///
+    /// Both states are expected to share the same board size; cells outside
+    /// `after`'s board are compared as empty.
+    pub fn diff(before: &GameState, after: &GameState) -> BoardDiff {
+        let changed_cells = before
+            .grid
+            .iter_positions()
+            .filter_map(|(pos, from)| {
+                let to = after.grid.get(pos).unwrap_or(CellState::Empty);
+                (from != to).then_some((pos, from, to))
+            })
+            .collect();
+
+        BoardDiff { changed_cells }
+    }
+
+    /// Zobrist hash of the board and the player to move
+    ///
+    /// Computed when the state is created and updated incrementally by
+    /// `apply_placement`; the setters recompute it.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist
+    }
+
+    /// Recompute the cached Zobrist hash from scratch
+    pub fn refresh_zobrist(&mut self) {
+        self.zobrist = self.grid.zobrist_hash() ^ zobrist_player_key(self.player_number);
+    }
+
+    /// Bring every cached value back in line with `grid` and `player_number`
+    ///
+    /// Recomputes the Zobrist hash and drops the cached territories.
+    fn refresh_caches(&mut self) {
+        self.refresh_zobrist();
+        self.my_territory = OnceLock::new();
+        self.opponent_territory = OnceLock::new();
+    }
+
+    /// Classify the game phase by occupied share of the board
+    pub fn current_phase(&self) -> GamePhase {
+        let area = self.grid.cells.len().max(1);
+        let occupied = self.grid.cells.len() - self.grid.empty_count();
+        let percent = occupied * 100 / area;
+
+        if percent < 25 {
+            GamePhase::Early
+        } else if percent <= 60 {
+            GamePhase::Mid
+        } else {
+            GamePhase::Late
+        }
+    }
+
+    /// Collect debug information about this state
+    pub fn debug_info(&self) -> GameDebugInfo {
+        let my_territory = self.get_my_territory_size();
+        let opponent_territory = self.get_opponent_territory_size();
+
+        GameDebugInfo {
+            player: self.player_number,
+            my_territory,
+            opponent_territory,
+            turn_number: self.turn_number,
+            grid_repr: self.grid.to_debug_string(),
+            piece_repr: format!(
+                "=== Shape: {} x {} ===\n{}\n",
+                self.current_piece.width, self.current_piece.height, self.current_piece
+            ),
+            phase: self.current_phase(),
+            is_winning: my_territory > opponent_territory,
+        }
+    }
+
+    /// Print game state for debugging
+    pub fn print(&self) {
+        eprintln!("{}", self.debug_info());
+        let skip = self.history.len().saturating_sub(HISTORY_PRINT_TURNS);
+        for turn in &self.history[skip..] {
+            eprintln!("  {}", turn);
+        }
+    }
+}
+
+/// Builder for `GameState` values, mainly used to set up test boards
+///
+/// Starts from an empty grid with player 1 to move and a single-cell piece.
+#[derive(Debug, Clone)]
+pub struct GameStateBuilder {
+    player_number: u8,
+    grid: Grid,
+    piece: Shape,
+}
+
+impl GameStateBuilder {
+    /// Create a builder for an empty `width` x `height` board
+    pub fn new(width: usize, height: usize) -> Self {
+        GameStateBuilder {
+            player_number: 1,
+            grid: Grid::from_chars(width, height, vec![vec!['.'; width]; height]),
+            piece: Shape::from_chars(1, 1, vec![vec!['#']]),
+        }
+    }
+
+    /// Set the player whose turn it is
+    pub fn with_player(mut self, player_number: u8) -> Self {
+        self.player_number = player_number;
+        self
+    }
+
+    /// Mark the given positions as territory of `player` (1 or 2)
+    ///
+    /// Out-of-bounds positions are ignored.
+    pub fn with_territory(mut self, player: u8, positions: &[Position]) -> Self {
+        let state = match player {
+            1 => CellState::Player1,
+            2 => CellState::Player2,
+            _ => return self,
+        };
+        for &pos in positions {
+            self.grid.set(pos, state);
+        }
+        self
+    }
+
+    /// Set the piece to be placed this turn
+    pub fn with_piece(mut self, shape: Shape) -> Self {
+        self.piece = shape;
+        self
+    }
+
+    /// Place player 1 in the top-left corner and player 2 in the bottom-right
+    pub fn symmetric_start(self) -> Self {
+        let far_corner = Position::new(
+            self.grid.width.saturating_sub(1),
+            self.grid.height.saturating_sub(1),
+        );
+        self.with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &[far_corner])
+    }
+
+    /// Finish building the game state
+    pub fn build(self) -> GameState {
+        GameState::new(self.player_number, self.grid, self.piece)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_state_from_char() {
+        assert_eq!(CellState::from('.'), CellState::Empty);
+        assert_eq!(CellState::from('@'), CellState::Player1);
+        assert_eq!(CellState::from('$'), CellState::Player2);
+        assert_eq!(CellState::from('a'), CellState::Player1Last);
+        assert_eq!(CellState::from('s'), CellState::Player2Last);
+    }
+
+    #[test]
+    fn test_cell_state_multi_player_chars() {
+        assert_eq!(CellState::from('&'), CellState::Player3);
+        assert_eq!(CellState::from('%'), CellState::Player4);
+        assert_eq!(CellState::from('d').player_num(), Some(3));
+        assert_eq!(CellState::from('f').player_num(), Some(4));
+        assert!(CellState::Player3Last.is_player(3) && CellState::Player3Last.is_last_placed());
+        assert!(!CellState::Player3.is_player(1) && !CellState::Empty.is_player(0));
+        assert_eq!(CellState::from('?'), CellState::Empty);
+        assert_eq!(CellState::Player3.to_string(), "&");
+    }
+
+    #[test]
+    fn test_position() {
+        let p = Position::new(5, 10);
+        assert_eq!(p.x, 5);
+        assert_eq!(p.y, 10);
+    }
+
+    #[test]
+    fn test_grid_creation() {
+        let raw = vec![
+            vec!['.', '@', '.'],
+            vec!['.', '.', '.'],
+            vec!['.', '$', '.'],
+        ];
+        let grid = Grid::from_chars(3, 3, raw);
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 3);
+    }
+
+    #[test]
+    fn test_grid_get_set() {
+        let raw = vec![vec!['.'; 3]; 3];
+        let mut grid = Grid::from_chars(3, 3, raw);
+
+        let pos = Position::new(1, 1);
+        grid.set(pos, CellState::Player1);
+        assert_eq!(grid.get(pos), Some(CellState::Player1));
+    }
+
+    #[test]
+    fn test_grid_bounds() {
+        let raw = vec![vec!['.'; 3]; 3];
+        let grid = Grid::from_chars(3, 3, raw);
+
+        assert!(grid.is_valid(Position::new(0, 0)));
+        assert!(grid.is_valid(Position::new(2, 2)));
+        assert!(!grid.is_valid(Position::new(3, 3)));
+    }
+
+    #[test]
+    fn test_shape_from_chars() {
+        let raw = vec![vec!['.', '#'], vec!['#', '.']];
+        let shape = Shape::from_chars(2, 2, raw);
+        let filled = shape.get_filled_positions();
+        assert_eq!(filled.len(), 2);
+    }
+
+    #[test]
+    fn test_shape_bounding_box() {
+        let raw = vec![
+            vec!['.', '#', '.'],
+            vec!['#', '#', '.'],
+            vec!['.', '.', '.'],
+        ];
+        let shape = Shape::from_chars(3, 3, raw);
+        let bbox = shape.bounding_box().unwrap();
+        assert_eq!(bbox, (0, 0, 2, 2)); // (min_x, min_y, width, height)
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let raw = vec![
+            vec!['@', '@', '.', '.', '@'],
+            vec!['.', '.', '.', '.', 'a'],
+            vec!['.', '$', '.', '.', '.'],
+        ];
+        let grid = Grid::from_chars(5, 3, raw);
+
+        let components = grid.connected_components(1);
+        assert_eq!(components.len(), 2);
+        let mut sizes: Vec<usize> = components.iter().map(|c| c.len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![2, 2]);
+
+        assert_eq!(grid.connected_components(2).len(), 1);
+    }
+
+    #[test]
+    fn test_builder_matches_from_chars() {
+        let raw = vec![
+            vec!['.', '.', '.', '.', '.'],
+            vec!['.', '@', '@', '.', '.'],
+            vec!['.', '@', '.', '.', '.'],
+            vec!['.', '.', '.', '$', '$'],
+            vec!['.', '.', '.', '$', '.'],
+        ];
+        let expected = Grid::from_chars(5, 5, raw);
+
+        let built = GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(1, 2)])
+            .with_territory(2, &[Position::new(3, 3), Position::new(4, 3), Position::new(3, 4)])
+            .build();
+
+        assert_eq!(*built.grid, expected);
+        assert_eq!(built.player_number, 1);
+        assert_eq!(built.current_piece, Shape::from_chars(1, 1, vec![vec!['#']]));
+    }
+
+    #[test]
+    fn test_builder_with_piece_and_player() {
+        let shape = Shape::from_chars(2, 1, vec![vec!['#', '#']]);
+        let state = GameState::builder_from_size(4, 3)
+            .with_player(2)
+            .with_piece(shape.clone())
+            .build();
+
+        assert_eq!(state.player_number, 2);
+        assert_eq!(state.current_piece, shape);
+        assert_eq!(state.grid.get_empty_positions().len(), 12);
+    }
+
+    #[test]
+    fn test_builder_symmetric_start() {
+        let state = GameState::builder_from_size(6, 4).symmetric_start().build();
+
+        assert_eq!(state.grid.get(Position::new(0, 0)), Some(CellState::Player1));
+        assert_eq!(state.grid.get(Position::new(5, 3)), Some(CellState::Player2));
+        assert_eq!(state.get_my_territory_size(), 1);
+        assert_eq!(state.get_opponent_territory_size(), 1);
+    }
+
+    #[test]
+    fn test_grid_flat_row_major_layout() {
+        let raw = vec![
+            vec!['.', '@', '.'],
+            vec!['$', '.', 's'],
+        ];
+        let grid = Grid::from_chars(3, 2, raw);
+
+        assert_eq!(grid.cells.len(), 6);
+        assert_eq!(grid.cells[1], CellState::Player1);
+        assert_eq!(grid.cells[3], CellState::Player2);
+        assert_eq!(grid.get(Position::new(2, 1)), Some(CellState::Player2Last));
+        assert_eq!(grid.get(Position::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_grid_iter_positions() {
+        let raw = vec![
+            vec!['.', '@'],
+            vec!['$', '.'],
+        ];
+        let grid = Grid::from_chars(2, 2, raw);
+        let cells: Vec<_> = grid.iter_positions().collect();
+
+        assert_eq!(cells.len(), 4);
+        assert_eq!(cells[1], (Position::new(1, 0), CellState::Player1));
+        assert_eq!(cells[2], (Position::new(0, 1), CellState::Player2));
+    }
+
+    #[test]
+    fn test_opponent_and_other_players() {
+        let state = GameState::builder_from_size(4, 4).with_player(3).build();
+
+        assert_eq!(state.other_players(&[1, 2, 3, 4]), vec![1, 2, 4]);
+        assert_eq!(GameState::builder_from_size(4, 4).with_player(2).build().opponent_number(), 1);
+    }
+
+    #[test]
+    fn test_normalize_player_perspective() {
+        let raw = vec![
+            vec!['&', 'd', '@'],
+            vec!['%', '.', 's'],
+        ];
+        let grid = Grid::from_chars(3, 2, raw).normalize_player_perspective(3);
+
+        assert_eq!(grid.get(Position::new(0, 0)), Some(CellState::Player1));
+        assert_eq!(grid.get(Position::new(1, 0)), Some(CellState::Player1Last));
+        assert_eq!(grid.get(Position::new(2, 0)), Some(CellState::Player2));
+        assert_eq!(grid.get(Position::new(0, 1)), Some(CellState::Player2));
+        assert_eq!(grid.get(Position::new(1, 1)), Some(CellState::Empty));
+        assert_eq!(grid.get(Position::new(2, 1)), Some(CellState::Player2Last));
+    }
+
+    #[test]
+    fn test_position_checked_add() {
+        let pos = Position::new(1, 0);
+
+        assert_eq!(pos.checked_add(1, 2), Some(Position::new(2, 2)));
+        assert_eq!(pos.checked_add(-1, 0), Some(Position::new(0, 0)));
+        assert_eq!(pos.checked_add(-2, 0), None);
+        assert_eq!(pos.checked_add(0, -1), None);
+        assert_eq!(Position::new(usize::MAX, 0).checked_add(1, 0), None);
+    }
+
+    #[test]
+    fn test_grid_get_signed() {
+        let grid = Grid::from_chars(3, 2, vec![vec!['.', '@', '.'], vec!['$', '.', '.']]);
+
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, -1), None);
+        assert_eq!(grid.get_signed(3, 0), None);
+        assert_eq!(grid.get_signed(0, 2), None);
+        assert_eq!(grid.get_signed(1, 0), Some(CellState::Player1));
+        assert!(grid.is_valid_signed(2, 1));
+        assert!(!grid.is_valid_signed(-1, -1));
+    }
+
+    #[test]
+    fn test_debug_info_territory_counts() {
+        let state = GameState::builder_from_size(4, 4)
+            .with_territory(1, &[Position::new(0, 0), Position::new(1, 0)])
+            .with_territory(2, &[Position::new(3, 3)])
+            .build();
+        let info = state.debug_info();
+
+        assert_eq!(info.player, 1);
+        assert_eq!(info.my_territory, 2);
+        assert_eq!(info.opponent_territory, 1);
+        assert_eq!(info.turn_number, 0);
+        assert_eq!(info.phase, GamePhase::Early);
+        assert!(info.is_winning);
+    }
+
+    #[test]
+    fn test_debug_info_display_contains_grid() {
+        let state = GameState::builder_from_size(3, 2).symmetric_start().build();
+        let info = state.debug_info();
+        let output = info.to_string();
+
+        assert!(output.contains(&info.grid_repr));
+        assert!(output.contains("000 @.."));
+        assert!(output.contains("001 ..$"));
+        assert!(output.contains("=== Shape: 1 x 1 ==="));
+    }
+
+    #[test]
+    fn test_current_phase_thresholds() {
+        let rows = |n: usize| -> Vec<Position> {
+            (0..n).flat_map(|y| (0..10).map(move |x| Position::new(x, y))).collect()
+        };
+
+        let early = GameState::builder_from_size(10, 10).with_territory(1, &rows(1)).build();
+        let mid = GameState::builder_from_size(10, 10).with_territory(1, &rows(4)).build();
+        let late = GameState::builder_from_size(10, 10).with_territory(1, &rows(7)).build();
+
+        assert_eq!(early.current_phase(), GamePhase::Early);
+        assert_eq!(mid.current_phase(), GamePhase::Mid);
+        assert_eq!(late.current_phase(), GamePhase::Late);
+    }
+
+    #[test]
+    fn test_grid_apply_placement_marks_last_piece() {
+        let mut grid = GameState::builder_from_size(4, 4).symmetric_start().build().grid;
+        let placement = Placement {
+            position: Position::new(0, 0),
+            shape: Shape::from_chars(2, 1, vec![vec!['#', '#']]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        grid.apply_placement(&placement, 1);
+
+        assert_eq!(grid.get(Position::new(0, 0)), Some(CellState::Player1));
+        assert_eq!(grid.get(Position::new(1, 0)), Some(CellState::Player1Last));
+        assert_eq!(grid.count_territory(1), 2);
+    }
+
+    #[test]
+    fn test_apply_shape_at_is_atomic() {
+        let mut grid: Grid = "@...\n....".parse().unwrap();
+        let square = Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '#']]);
+
+        let overflow = grid.apply_shape_at(Position::new(3, 0), &square, CellState::Player1Last);
+        assert_eq!(overflow, Err(PlacementError::OutOfBounds));
+        assert_eq!(grid.to_string(), "@...\n....");
+        let empty = Shape::from_chars(1, 1, vec![vec!['.']]);
+        let nothing = grid.apply_shape_at(Position::new(0, 0), &empty, CellState::Player1);
+        assert_eq!(nothing, Err(PlacementError::EmptyShape));
+
+        assert_eq!(grid.apply_shape_at(Position::new(0, 0), &square, CellState::Player1Last), Ok(()));
+        assert_eq!(grid.to_string(), "@a..\naa..");
+
+        // A placement hanging off the board leaves the old last piece alone
+        let overhang = Placement {
+            position: Position::new(3, 0),
+            shape: square,
+            cells_added: 1,
+            territory_touches: 0,
+        };
+        assert_eq!(grid.apply_placement(&overhang, 1), 0);
+        assert_eq!(grid.to_string(), "@a..\naa..");
+    }
+
+    #[test]
+    fn test_game_state_apply_placement() {
+        let state = GameState::builder_from_size(4, 4).symmetric_start().build();
+        let placement = Placement {
+            position: Position::new(0, 0),
+            shape: Shape::from_chars(2, 1, vec![vec!['#', '#']]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        let next = state.apply_placement(&placement);
+
+        assert_eq!(next.player_number, 2);
+        assert_eq!(next.turn_number, 1);
+        assert_eq!(next.grid.count_territory(1), 2);
+        // The original state is unchanged
+        assert_eq!(state.grid.count_territory(1), 1);
+    }
+
+    #[test]
+    fn test_bitgrid_ownership() {
         let raw = vec![
-            vec!['.', '#', '.'],
-            vec!['#', '#', '.'],
-            vec!['.', '.', '.'],
+            vec!['@', '.', '$'],
+            vec!['.', 'a', '&'],
         ];
-        let shape = Shape::from_chars(3, 3, raw);
-        let bbox = shape.bounding_box().unwrap();
-        assert_eq!(bbox, (0, 0, 2, 2)); // (min_x, min_y, width, height)
+        let bits = Grid::from_chars(3, 2, raw).as_bitboard();
+
+        assert!(bits.is_owned_by(Position::new(0, 0), 1));
+        assert!(bits.is_owned_by(Position::new(1, 1), 1));
+        assert!(bits.is_owned_by(Position::new(2, 0), 2));
+        assert!(bits.is_owned_by(Position::new(2, 1), 3));
+        assert!(!bits.is_owned_by(Position::new(1, 0), 1));
+        assert!(!bits.is_owned_by(Position::new(3, 0), 1));
+    }
+
+    #[test]
+    fn test_bitgrid_territory_overlap_wide_board() {
+        // 70 columns forces pieces to straddle the first word boundary
+        let state = GameState::builder_from_size(70, 2)
+            .with_territory(1, &[Position::new(64, 0)])
+            .with_territory(2, &[Position::new(66, 0)])
+            .build();
+        let bits = state.grid.as_bitboard();
+        let mask = ShapeMask::from_shape(&Shape::from_chars(2, 1, vec![vec!['#', '#']])).unwrap();
+
+        assert_eq!(bits.territory_overlap(&mask, Position::new(63, 0), 1), Some(1));
+        assert_eq!(bits.territory_overlap(&mask, Position::new(61, 0), 1), Some(0));
+        assert_eq!(bits.territory_overlap(&mask, Position::new(65, 0), 1), None);
+        assert_eq!(bits.territory_overlap(&mask, Position::new(69, 0), 1), None);
+    }
+
+    #[test]
+    fn test_zobrist_identical_states_match() {
+        let a = GameState::builder_from_size(8, 6).symmetric_start().build();
+        let b = GameState::builder_from_size(8, 6).symmetric_start().build();
+
+        assert_eq!(a.zobrist_hash(), b.zobrist_hash());
+        let other_to_move = GameState::builder_from_size(8, 6).symmetric_start().with_player(2).build();
+        assert_ne!(a.zobrist_hash(), other_to_move.zobrist_hash());
+    }
+
+    #[test]
+    fn test_zobrist_incremental_matches_full() {
+        let state = GameState::builder_from_size(6, 4)
+            .symmetric_start()
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let first = crate::placement::find_all_valid_placements(&state)[0].clone();
+        let mut next = state.apply_placement(&first);
+        let incremental = next.zobrist_hash();
+
+        next.refresh_zobrist();
+        assert_eq!(incremental, next.zobrist_hash());
+        assert_ne!(incremental, state.zobrist_hash());
+    }
+
+    #[test]
+    fn test_zobrist_distinct_states_rarely_collide() {
+        // Every single-cell board on a 12x12 grid for two players
+        let mut hashes = HashSet::new();
+        for player in 1..=2 {
+            for y in 0..12 {
+                for x in 0..12 {
+                    let state = GameState::builder_from_size(12, 12)
+                        .with_territory(player, &[Position::new(x, y)])
+                        .build();
+                    hashes.insert(state.zobrist_hash());
+                }
+            }
+        }
+
+        assert_eq!(hashes.len(), 2 * 12 * 12);
+    }
+
+    #[test]
+    fn test_distance_map_routes_around_opponent_wall() {
+        // Player 2 wall in column 2 with a gap only in the bottom row
+        let wall: Vec<Position> = (0..4).map(|y| Position::new(2, y)).collect();
+        let grid = GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &wall)
+            .build()
+            .grid;
+        let distances = grid.distance_map(1);
+
+        assert_eq!(distances[0][0], Some(0));
+        assert_eq!(distances[0][1], Some(1));
+        // Manhattan distance to (3, 0) is 3, but the path detours via row 4
+        assert_eq!(distances[0][3], Some(11));
+        assert_eq!(distances[4][2], Some(6));
+    }
+
+    #[test]
+    fn test_shape_rotations() {
+        // L piece:  #.
+        //           ##
+        let shape = Shape::from_chars(2, 2, vec![vec!['#', '.'], vec!['#', '#']]);
+
+        let r90 = shape.rotate_90();
+        assert_eq!(r90.cells, vec![vec![true, true], vec![true, false]]);
+        assert_eq!(shape.rotate_180().cells, vec![vec![true, true], vec![false, true]]);
+        assert_eq!(shape.rotate_270().cells, vec![vec![false, true], vec![true, true]]);
+        assert_eq!(r90.rotate_90().rotate_90().rotate_90(), shape);
+    }
+
+    #[test]
+    fn test_shape_rotate_non_square() {
+        let bar = Shape::from_chars(3, 1, vec![vec!['#', '#', '.']]);
+        let rotated = bar.rotate_90();
+
+        assert_eq!((rotated.width, rotated.height), (1, 3));
+        assert_eq!(rotated.cells, vec![vec![true], vec![true], vec![false]]);
+        assert_eq!(bar.rotate_270().cells, vec![vec![false], vec![true], vec![true]]);
+    }
+
+    #[test]
+    fn test_shape_flips() {
+        let shape = Shape::from_chars(3, 2, vec![vec!['#', '.', '.'], vec!['#', '#', '.']]);
+
+        assert_eq!(shape.flip_horizontal().cells[0], vec![false, false, true]);
+        assert_eq!(shape.flip_vertical().cells[0], vec![true, true, false]);
+        assert_eq!(shape.flip_horizontal().flip_horizontal(), shape);
+    }
+
+    #[test]
+    fn test_shape_normalize() {
+        let framed = Shape::from_chars(4, 3, vec![
+            vec!['.', '.', '.', '.'],
+            vec!['.', '#', '#', '.'],
+            vec!['.', '#', '.', '.'],
+        ]);
+        let tight = Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]);
+
+        assert_eq!(framed.normalize(), tight);
+        assert_eq!(framed.canonical_key(), vec![(0, 0), (0, 1), (1, 0)]);
+        assert_eq!(framed.canonical_key(), tight.canonical_key());
+        assert_eq!(Shape::from_chars(1, 1, vec![vec!['.']]).normalize().width, 0);
+    }
+
+    #[test]
+    fn test_game_state_apply_placement_for_player_two() {
+        let state = GameState::builder_from_size(4, 4).symmetric_start().with_player(2).build();
+        let placement = Placement {
+            position: Position::new(2, 3),
+            shape: Shape::from_chars(2, 1, vec![vec!['#', '#']]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        let next = state.apply_placement(&placement);
+
+        // The overlap cell stays plain territory, the new cell is the last piece
+        assert_eq!(next.grid.get(Position::new(3, 3)), Some(CellState::Player2));
+        assert_eq!(next.grid.get(Position::new(2, 3)), Some(CellState::Player2Last));
+        assert_eq!(state.get_my_territory_size(), 1);
+        assert_eq!(next.grid.count_territory(2), 2);
+        assert_eq!(next.grid.count_territory(1), 1);
+        assert_eq!(next.player_number, 1);
+    }
+
+    #[test]
+    fn test_is_territory_connected() {
+        let connected = GameState::builder_from_size(4, 4)
+            .with_territory(1, &[Position::new(0, 0), Position::new(1, 0)])
+            .build();
+        let split = GameState::builder_from_size(4, 4)
+            .with_territory(1, &[Position::new(0, 0), Position::new(2, 0)])
+            .build();
+
+        assert!(connected.is_territory_connected());
+        assert!(!split.is_territory_connected());
+    }
+
+    #[test]
+    fn test_grid_string_round_trip() {
+        let text = "@..a\n.&.%\n$..s";
+        let grid: Grid = text.parse().unwrap();
+
+        assert_eq!((grid.width, grid.height), (4, 3));
+        assert_eq!(grid.get(Position::new(3, 0)), Some(CellState::Player1Last));
+        assert_eq!(grid.to_string(), text);
+
+        let built = GameState::builder_from_size(5, 4).symmetric_start().build().grid;
+        assert_eq!(built.to_string().parse::<Grid>(), Ok(built.into_inner()));
+    }
+
+    #[test]
+    fn test_grid_from_str_errors() {
+        assert_eq!("".parse::<Grid>(), Err(ParseError::Empty));
+        assert_eq!(
+            "...\n..".parse::<Grid>(),
+            Err(ParseError::RaggedRow { row: 1, expected: 3, found: 2 })
+        );
+        assert_eq!(
+            "..\n.x".parse::<Grid>(),
+            Err(ParseError::InvalidChar { row: 1, col: 1, ch: 'x' })
+        );
+    }
+
+    #[test]
+    fn test_grid_transformations() {
+        let grid: Grid = "@..\n.s$".parse().unwrap();
+
+        assert_eq!(grid.flip_horizontal().to_string(), "..@\n$s.");
+        assert_eq!(grid.flip_vertical().to_string(), ".s$\n@..");
+        assert_eq!(grid.rotate_90().to_string(), ".@\ns.\n$.");
+        let full_turn = grid.rotate_90().rotate_90().rotate_90().rotate_90();
+        assert_eq!(full_turn, grid);
+        assert_eq!(grid.rotate_90().rotate_90(), grid.flip_horizontal().flip_vertical());
+    }
+
+    #[test]
+    fn test_shape_text_round_trip() {
+        let text = ".#.\n###";
+        let shape: Shape = text.parse().unwrap();
+
+        assert_eq!(shape, Shape::from_chars(3, 2, vec![vec!['.', '#', '.'], vec!['#', '#', '#']]));
+        assert_eq!(shape.to_string(), text);
+        assert_eq!("\n##\n#.\n".parse::<Shape>().unwrap().to_string(), "##\n#.");
+    }
+
+    #[test]
+    fn test_shape_from_str_errors() {
+        assert_eq!("".parse::<Shape>(), Err(ParseError::Empty));
+        assert_eq!(
+            "##\n#".parse::<Shape>(),
+            Err(ParseError::RaggedRow { row: 1, expected: 2, found: 1 })
+        );
+        assert_eq!(
+            ".#\n*.".parse::<Shape>(),
+            Err(ParseError::InvalidChar { row: 1, col: 0, ch: '*' })
+        );
+    }
+
+    #[test]
+    fn test_diff_after_placement_lists_placement_cells() {
+        let state = GameState::builder_from_size(4, 4).symmetric_start().build();
+        let placement = Placement {
+            position: Position::new(0, 0),
+            shape: Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]),
+            cells_added: 2,
+            territory_touches: 1,
+        };
+
+        let next = state.apply_placement(&placement);
+        let diff = GameState::diff(&state, &next);
+
+        assert_eq!(
+            diff.changed_cells,
+            vec![
+                (Position::new(1, 0), CellState::Empty, CellState::Player1Last),
+                (Position::new(0, 1), CellState::Empty, CellState::Player1Last),
+            ]
+        );
+        assert_eq!(diff.placed_by(1), vec![Position::new(1, 0), Position::new(0, 1)]);
+        assert!(diff.placed_by(2).is_empty());
+        assert!(GameState::diff(&state, &state).is_empty());
+    }
+
+    #[test]
+    fn test_shape_area_perimeter_compactness() {
+        let square = Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '#']]);
+        let bar = Shape::from_chars(4, 1, vec![vec!['#'; 4]]);
+        let corner = Shape::from_chars(2, 2, vec![vec!['#', '.'], vec!['#', '#']]);
+
+        assert_eq!((square.area(), square.perimeter()), (4, 8));
+        assert_eq!((bar.area(), bar.perimeter()), (4, 10));
+        assert_eq!((corner.area(), corner.perimeter()), (3, 8));
+        assert!(square.compactness() > bar.compactness());
+        assert!((bar.compactness() - 0.5027).abs() < 1e-3);
+
+        let empty = Shape::from_chars(1, 1, vec![vec!['.']]);
+        assert_eq!(empty.compactness(), 0.0);
+    }
+
+    #[test]
+    fn test_territory_border() {
+        let block: Vec<Position> = (0..3)
+            .flat_map(|y| (0..3).map(move |x| Position::new(x, y)))
+            .collect();
+        let grid = GameState::builder_from_size(4, 4)
+            .with_territory(1, &block)
+            .with_territory(2, &[Position::new(3, 1)])
+            .build()
+            .grid;
+
+        let border = grid.territory_border(1);
+
+        // The board edge is not empty and (2, 1) is walled in by player 2
+        assert_eq!(
+            border,
+            vec![
+                Position::new(2, 0),
+                Position::new(0, 2),
+                Position::new(1, 2),
+                Position::new(2, 2),
+            ]
+        );
+        assert_eq!(grid.territory_border(2), vec![Position::new(3, 1)]);
+    }
+
+    #[test]
+    fn test_territory_for_player() {
+        let grid = GameState::builder_from_size(6, 5)
+            .with_territory(1, &[Position::new(1, 1), Position::new(2, 1), Position::new(4, 3)])
+            .build()
+            .grid;
+
+        let territory = Territory::for_player(&grid, 1);
+
+        assert_eq!(territory.size(), 3);
+        assert_eq!(territory.border.len(), 3);
+        assert_eq!(territory.connected_components, 2);
+        assert_eq!(territory.centroid, (7.0 / 3.0, 5.0 / 3.0));
+        assert_eq!(territory.bounding_box, (1, 1, 4, 3));
+
+        let none = Territory::for_player(&grid, 2);
+        assert!(none.is_empty());
+        assert_eq!(none.bounding_box, (0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_game_state_territory_cache() {
+        let mut state = GameState::builder_from_size(4, 4).symmetric_start().build();
+
+        assert_eq!(state.my_territory().cells, vec![Position::new(0, 0)]);
+        assert_eq!(state.opponent_territory().cells, vec![Position::new(3, 3)]);
+
+        // Grid edits through the setters drop the cached values
+        state.set_cell(Position::new(1, 0), CellState::Player1);
+        assert_eq!(state.get_my_territory_size(), 2);
+        assert_eq!(state.zobrist_hash(), state.grid.zobrist_hash() ^ zobrist_player_key(1));
+    }
+
+    #[test]
+    fn test_reachable_empty_cells() {
+        // Player 1 wall in column 2 splits the board; player 2 sits at (4, 1)
+        let wall: Vec<Position> = (0..3).map(|y| Position::new(2, y)).collect();
+        let grid = GameState::builder_from_size(5, 3)
+            .with_territory(1, &wall)
+            .with_territory(2, &[Position::new(4, 1)])
+            .build()
+            .grid;
+        let start = [Position::new(0, 0)];
+
+        let left = grid.reachable_empty_cells(&start, None);
+        assert_eq!(left.len(), 6);
+        assert!(left.contains(&Position::new(0, 0)));
+
+        // Walking through the wall reaches the right side too
+        let through = grid.reachable_empty_cells(&start, Some(1));
+        assert_eq!(through.len(), 11);
+        assert!(!through.contains(&Position::new(4, 1)));
+
+        assert!(grid.reachable_empty_cells_limited(&start, None, 0).len() <= 1);
+        assert!(grid.reachable_empty_cells_limited(&start, None, 2).len() < left.len());
+    }
+
+    #[test]
+    fn test_apply_opponent_placement() {
+        let state = GameState::builder_from_size(4, 4).symmetric_start().build();
+        let placement = Placement {
+            position: Position::new(2, 3),
+            shape: Shape::from_chars(2, 1, vec![vec!['#', '#']]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+
+        let next = state.apply_opponent_placement(&placement);
+
+        assert_eq!(next.player_number, 1);
+        assert_eq!(next.turn_number, 1);
+        assert_eq!(next.grid.get(Position::new(2, 3)), Some(CellState::Player2Last));
+        assert_eq!(next.get_opponent_territory_size(), 2);
+        assert_eq!(next.get_my_territory_size(), 1);
+
+        let mut refreshed = next.clone();
+        refreshed.refresh_zobrist();
+        assert_eq!(next.zobrist_hash(), refreshed.zobrist_hash());
+    }
+
+    #[test]
+    fn test_swap_perspective() {
+        let state = GameState::builder_from_size(3, 1)
+            .with_territory(1, &[Position::new(0, 0)])
+            .build();
+        let mut grid = state.grid.grid().clone();
+        grid.set(Position::new(2, 0), CellState::Player2Last);
+        let state = GameState::new(1, grid, state.current_piece.clone());
+
+        let swapped = state.swap_perspective();
+
+        assert_eq!(swapped.player_number, 1);
+        assert_eq!(swapped.grid.to_string(), "$.a");
+        assert_eq!(swapped.get_my_positions(), state.get_opponent_positions());
+        assert_eq!(swapped.swap_perspective().grid, state.grid);
+    }
+
+    #[test]
+    fn test_for_opponent() {
+        let state = GameState::builder_from_size(4, 1)
+            .with_territory(1, &[Position::new(0, 0)])
+            .with_territory(2, &[Position::new(3, 0)])
+            .build();
+        // Fill the territory caches before they get swapped
+        assert_eq!(state.get_my_positions(), vec![Position::new(0, 0)]);
+
+        let flipped = state.for_opponent();
+
+        assert_eq!((flipped.player_number, flipped.opponent_number()), (2, 1));
+        assert_eq!(flipped.grid, state.grid);
+        assert_eq!(flipped.get_my_positions(), vec![Position::new(3, 0)]);
+        assert_eq!(flipped.get_opponent_positions(), state.get_my_positions());
+        let fresh = GameState::new(2, state.grid.grid().clone(), state.current_piece.clone());
+        assert_eq!(flipped.zobrist_hash(), fresh.zobrist_hash());
+        assert_eq!(flipped.for_opponent().zobrist_hash(), state.zobrist_hash());
+        assert_eq!(GameState::opponent_of(2), 1);
+    }
+
+    #[test]
+    fn test_shape_count_interior_holes() {
+        let ring = Shape::from_chars(3, 3, vec![
+            vec!['#', '#', '#'],
+            vec!['#', '.', '#'],
+            vec!['#', '#', '#'],
+        ]);
+        let open_ring = Shape::from_chars(3, 3, vec![
+            vec!['#', '#', '#'],
+            vec!['#', '.', '.'],
+            vec!['#', '#', '#'],
+        ]);
+        // Padding around the piece does not count as a hole
+        let padded_ring = Shape::from_chars(5, 4, vec![
+            vec!['.', '.', '.', '.', '.'],
+            vec!['.', '#', '#', '#', '.'],
+            vec!['.', '#', '.', '#', '.'],
+            vec!['.', '#', '#', '#', '.'],
+        ]);
+
+        assert_eq!(ring.count_interior_holes(), 1);
+        assert_eq!(ring.interior_hole_positions(), vec![Position::new(1, 1)]);
+        assert_eq!(open_ring.count_interior_holes(), 0);
+        assert_eq!(padded_ring.interior_hole_positions(), vec![Position::new(2, 2)]);
+        assert_eq!(Shape::from_chars(1, 1, vec![vec!['.']]).count_interior_holes(), 0);
+    }
+
+    #[test]
+    fn test_territory_centroid_distance() {
+        let game_state = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(0, 0), Position::new(2, 0)])
+            .with_territory(2, &[Position::new(4, 4)])
+            .build();
+
+        assert_eq!(game_state.my_territory().centroid, (1.0, 0.0));
+        assert_eq!(game_state.opponent_territory().centroid, (4.0, 4.0));
+        assert_eq!(game_state.territory_centroid_distance(), 5.0);
+
+        let alone = GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(2, 2)])
+            .build();
+        assert_eq!(alone.territory_centroid_distance(), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_territory_spread() {
+        let game_state = GameState::builder_from_size(10, 10)
+            .with_territory(1, &[Position::new(0, 0), Position::new(2, 0)])
+            .with_territory(2, &[Position::new(0, 2), Position::new(8, 2), Position::new(4, 8)])
+            .build();
+
+        assert_eq!(game_state.grid.territory_spread(1), 1.0);
+        // Centroid (4, 4): squared distances 20, 20 and 16
+        assert_eq!(game_state.grid.territory_spread(2), 56.0 / 3.0);
+        assert_eq!(game_state.grid.territory_spread(3), 0.0);
+    }
+
+    #[test]
+    fn test_convex_hull_of_territory() {
+        // An L: the inner corner (1, 1) and the cells along its arms are
+        // not hull vertices
+        let territory: Vec<Position> = (0..3)
+            .map(|y| Position::new(0, y))
+            .chain((1..3).map(|x| Position::new(x, 2)))
+            .collect();
+        let grid = GameState::builder_from_size(4, 4).with_territory(1, &territory).build().grid;
+
+        assert_eq!(
+            grid.convex_hull_of_territory(1),
+            vec![Position::new(0, 0), Position::new(2, 2), Position::new(0, 2)]
+        );
+        assert!(grid.convex_hull_of_territory(2).is_empty());
+    }
+
+    #[test]
+    fn test_voronoi_distance_pair_matches_distance_maps() {
+        let wall: Vec<Position> = (0..4).map(|y| Position::new(2, y)).collect();
+        let grid = GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(0, 0), Position::new(0, 1)])
+            .with_territory(2, &wall)
+            .build()
+            .grid;
+
+        let (player1, player2) = grid.voronoi_distance_pair();
+
+        assert_eq!(player1, grid.distance_map(1));
+        assert_eq!(player2, grid.distance_map(2));
+        assert_eq!(player1[0][3], Some(10));
+    }
+
+    #[test]
+    fn test_articulation_points() {
+        // Two 2x3 rooms whose only link is the doorway at (2, 2); the room
+        // cells next to the doorway are cut vertices as well
+        let grid: Grid = "\
+..$..
+..$..
+.....
+$$$$$"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            grid.articulation_points(),
+            vec![Position::new(1, 2), Position::new(2, 2), Position::new(3, 2)]
+        );
+
+        let corridor: Grid = "....".parse().unwrap();
+        assert_eq!(
+            corridor.articulation_points(),
+            vec![Position::new(1, 0), Position::new(2, 0)]
+        );
+
+        let open: Grid = "...\n...\n...".parse().unwrap();
+        assert!(open.articulation_points().is_empty());
+    }
+
+    #[test]
+    fn test_find_bottlenecks() {
+        // A 3x3 room with a two-cell passage out of it: the room's edge
+        // cell (2, 1) cuts off both passage cells, (3, 1) only the end
+        let grid: Grid = "\
+...$$
+.....
+...$$"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            grid.find_bottlenecks(),
+            vec![(Position::new(2, 1), 2), (Position::new(3, 1), 1)]
+        );
+
+        let corridor: Grid = "....".parse().unwrap();
+        assert_eq!(corridor.find_bottlenecks(), vec![(Position::new(1, 0), 1), (Position::new(2, 0), 1)]);
+        assert!("...\n...".parse::<Grid>().unwrap().find_bottlenecks().is_empty());
+    }
+
+    #[test]
+    fn test_turn_history_delta_and_stall() {
+        let turn = |turn: u32, mine: usize, theirs: usize| TurnHistory {
+            turn,
+            player: 1,
+            territory: [mine, theirs],
+            placement: None,
+            cells_gained: 0,
+        };
+        let game_state = GameState::builder_from_size(5, 5).build();
+        assert_eq!(game_state.territory_delta(3), (0, 0));
+        assert!(!game_state.is_stalled());
+
+        let growing = game_state.clone().with_history(vec![
+            turn(1, 1, 1),
+            turn(2, 4, 3),
+            turn(3, 8, 6),
+            turn(4, 12, 9),
+        ]);
+        assert_eq!(growing.territory_delta(1), (4, 3));
+        assert_eq!(growing.territory_delta(3), (11, 8));
+        assert_eq!(growing.territory_delta(10), (11, 8));
+        assert!(!growing.is_stalled());
+
+        let stalled = game_state.with_history(vec![
+            turn(5, 20, 15),
+            turn(6, 21, 15),
+            turn(7, 21, 16),
+            turn(8, 22, 17),
+        ]);
+        assert_eq!(stalled.territory_delta(STALL_WINDOW), (2, 2));
+        assert!(stalled.is_stalled());
+
+        // Deltas follow the player to move
+        assert_eq!(growing.for_opponent().territory_delta(3), (8, 11));
+        assert_eq!(growing.swap_perspective().territory_delta(3), (8, 11));
+        assert_eq!(stalled.history()[3].to_string(), "turn 8 (p1): 22 vs 17 (+0)");
+    }
+
+    #[test]
+    fn test_largest_empty_region_and_advantage() {
+        // Our wall shuts player 2 into a one-cell pocket at (1, 0)
+        let grid: Grid = "\
+$.@..
+@@@..
+....."
+            .parse()
+            .unwrap();
+        let mut game_state = GameState::new(1, grid, Shape::from_chars(1, 1, vec![vec!['#']]));
+
+        assert_eq!(game_state.grid.largest_empty_region(), 9);
+        assert_eq!(game_state.grid.largest_reachable_empty_regions(), (9, 1));
+        assert_eq!(game_state.empty_region_advantage(), 8);
+
+        game_state.player_number = 2;
+        assert_eq!(game_state.empty_region_advantage(), -8);
+        assert_eq!("@$".parse::<Grid>().unwrap().largest_empty_region(), 0);
+    }
+
+    #[test]
+    fn test_region_sizes_and_fragmentation() {
+        let piece = Shape::from_chars(1, 1, vec![vec!['#']]);
+        let split: Grid = "\
+..@..
+..@.$
+@@@@@
+.$.$."
+            .parse()
+            .unwrap();
+        assert_eq!(split.region_sizes(), vec![4, 3, 1, 1, 1]);
+
+        let split = GameState::new(1, split, piece.clone());
+        let open = GameState::new(1, "@....\n....$".parse().unwrap(), piece.clone());
+        let scattered = GameState::new(1, ".@.$.".parse().unwrap(), piece);
+
+        assert_eq!(open.board_fragmentation(), 0.0);
+        assert!((scattered.board_fragmentation() - 1.0).abs() < 1e-6);
+        assert!((0.0..1.0).contains(&split.board_fragmentation()) && split.board_fragmentation() > 0.0);
+        assert!("@$".parse::<Grid>().unwrap().region_sizes().is_empty());
+    }
+
+    #[test]
+    fn test_simulate_random_fill() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // (2, 0) is equidistant; player 3 walls off the bottom row
+        let grid: Grid = "\
+@...$
+&&&&&
+....."
+            .parse()
+            .unwrap();
+        let player1 = grid.get_player_positions(1);
+        let player2 = grid.get_player_positions(2);
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for _ in 0..10 {
+            let (count1, count2) = grid.simulate_random_fill(&player1, &player2, &mut rng);
+            assert!((count1, count2) == (3, 2) || (count1, count2) == (2, 3));
+        }
+        let (expected1, expected2) = grid.expected_final_territory(&player1, &player2, &mut rng);
+        assert_eq!(expected1 + expected2, 5.0);
+        assert!(expected1 > 2.0 && expected1 < 3.0);
+    }
+
+    #[test]
+    fn test_erode_and_dilate_territory() {
+        // A 2x2 block with a one-cell peninsula sticking out to the right
+        let grid: Grid = "\
+@@@..
+@@...
+....$"
+            .parse()
+            .unwrap();
+
+        let core = grid.erode_territory(1, 2);
+        assert_eq!(core.to_string(), "@@...\n@@...\n....$");
+        assert_eq!(grid.erode_territory(1, 0), grid);
+        assert_eq!(grid.erode_territory(2, 1).count_territory(2), 0);
+
+        let grown = grid.dilate_territory(1, 2);
+        assert_eq!(grown.to_string(), "@@@..\n@@@..\n....$");
+        assert_eq!(grid.dilate_territory(1, 1).count_territory(1), 5 + 4);
+        assert_eq!(grid.dilate_territory(5, 1), grid);
+    }
+
+    #[test]
+    fn test_tracked_grid_empty_count() {
+        let mut grid = TrackedGrid::new("@...\n...$".parse().unwrap());
+        assert_eq!(grid.empty_count(), 6);
+
+        grid.set(Position::new(1, 0), CellState::Player1);
+        grid.set(Position::new(0, 0), CellState::Empty);
+        grid.set(Position::new(3, 1), CellState::Player2Last);
+        assert!(!grid.set(Position::new(9, 9), CellState::Empty));
+        assert_eq!(grid.empty_count(), 6);
+
+        // The domino covers (1, 0), already ours, and claims (1, 1)
+        let placement = Placement {
+            position: Position::new(1, 0),
+            shape: Shape::from_chars(1, 2, vec![vec!['#'], vec!['#']]),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        grid.apply_placement(&placement, 1);
+        assert_eq!(grid.empty_count(), 5);
+        assert_eq!(grid.empty_count(), grid.count_empty());
+
+        let game_state = GameState::new(1, grid.into_inner(), Shape::from_chars(1, 1, vec![vec!['#']]));
+        let after = game_state.apply_placement(&Placement {
+            position: Position::new(2, 0),
+            shape: Shape::from_chars(2, 1, vec![vec!['#', '#']]),
+            cells_added: 2,
+            territory_touches: 0,
+        });
+        assert_eq!(after.grid.empty_count(), 3);
+        assert_eq!(after.grid.empty_count(), after.grid.count_empty());
+    }
+
+    #[test]
+    fn test_compute_threat_map() {
+        let grid: Grid = "\
+.....
+..@..
+....$"
+            .parse()
+            .unwrap();
+        let domino = Shape::from_chars(2, 1, vec![vec!['#', '#']]);
+
+        // Player 1's domino can only extend left or right of its cell
+        let threat = grid.compute_threat_map(1, &domino);
+        assert_eq!(threat[1], vec![0, 1, 0, 1, 0]);
+        assert!(threat[0].iter().chain(&threat[2]).all(|&t| t == 0));
+
+        let threat = grid.compute_threat_map(2, &domino);
+        assert_eq!(threat[2], vec![0, 0, 0, 1, 0]);
+        assert_eq!(threat[1][4], 0);
+    }
+
+    #[test]
+    fn test_shape_symmetry_class() {
+        let shape = |rows: &[&str]| {
+            Shape::from_chars(rows[0].len(), rows.len(), rows.iter().map(|r| r.chars().collect()).collect())
+        };
+
+        assert_eq!(shape(&["##", "##"]).symmetry_class(), Symmetry::Full);
+        assert_eq!(shape(&["###"]).symmetry_class(), Symmetry::Full);
+        assert_eq!(shape(&["###", ".#."]).symmetry_class(), Symmetry::Horizontal);
+        assert_eq!(shape(&["#.", "##", "#."]).symmetry_class(), Symmetry::Vertical);
+        assert_eq!(shape(&["##.", ".##"]).symmetry_class(), Symmetry::Rotational180);
+        assert_eq!(shape(&["#.", "##"]).symmetry_class(), Symmetry::None);
+        // Padding does not affect the class
+        assert_eq!(shape(&["...", "##.", ".##"]).symmetry_class(), Symmetry::Rotational180);
+    }
+
+    #[test]
+    fn test_grid_image_output() {
+        let grid: Grid = "@a.\n$s.".parse().unwrap();
+
+        let pgm = grid.to_pgm();
+        let header = b"P5\n3 2\n255\n";
+        assert_eq!(&pgm[..header.len()], header);
+        assert_eq!(&pgm[header.len()..], &[220, 255, 128, 0, 64, 128]);
+
+        let ppm = grid.to_ppm();
+        let header = b"P6\n3 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 3 * 6);
+        assert_eq!(&ppm[header.len()..header.len() + 3], &CellState::Player1.rgb());
     }
 }
@@ -0,0 +1,16 @@
+/// Filler AI library
+///
+/// Game state, placement search and AI strategies shared by the `filler`
+/// player binary and the `filler-sim` self-play harness.
+
+pub mod error;
+pub mod parser;
+pub mod output;
+pub mod game_io;
+pub mod game_state;
+pub mod placement;
+pub mod utils;
+pub mod ai;
+pub mod sim;
+pub mod tournament;
+pub mod replay;
@@ -1,83 +1,185 @@
-mod parser;
-mod output;
-mod game_state;
-mod placement;
-mod utils;
-mod ai;
+use filler::parser::{parse_game_input, parse_next_turn, GameInput};
+use filler::output::{Move, MoveLog};
+use filler::game_io::StdinStdout;
+use filler::game_state::{Grid, Shape, GameState, TurnHistory};
+use filler::placement::find_all_valid_placements;
+use filler::ai::select_move_default_with_cache;
+use filler::ai::optimization::TurnCache;
+use filler::ai::advanced_strategies::EvalWeights;
+use filler::ai::heuristics::{advanced_score_pure, advanced_score_with_breakdown, HeuristicWeights, ScoreMaps};
+use filler::placement::Placement;
+use std::cmp::Reverse;
 
-use parser::parse_game_input;
-use output::Move;
-use game_state::{Grid, Shape, GameState};
-use placement::find_all_valid_placements;
-use ai::select_move_default;
+/// Number of placements printed by `--debug-scores`
+const DEBUG_SCORES_TOP_N: usize = 5;
 
-fn main() {
-    eprintln!("Starting Filler AI...");
+/// Write the board as `<prefix>.pgm` and `<prefix>.ppm`
+fn write_debug_images(grid: &Grid, prefix: &str) {
+    for (extension, image) in [("pgm", grid.to_pgm()), ("ppm", grid.to_ppm())] {
+        let path = format!("{}.{}", prefix, extension);
+        if let Err(e) = std::fs::write(&path, image) {
+            eprintln!("Error writing debug image {}: {}", path, e);
+        }
+    }
+}
+
+/// Print the top placements by `advanced_score` with their score breakdowns
+fn print_score_breakdowns(placements: &[Placement], game_state: &GameState, weights: &HeuristicWeights) {
+    let maps = ScoreMaps::new(game_state);
+    let mut ranked: Vec<&Placement> = placements.iter().collect();
+    ranked.sort_by_cached_key(|p| {
+        let simulated = p.simulate(game_state.grid(), game_state.player_number());
+        Reverse(advanced_score_pure(p, &simulated, game_state, &maps, weights))
+    });
+
+    for placement in ranked.into_iter().take(DEBUG_SCORES_TOP_N) {
+        eprintln!(
+            "  ({}, {}): {}",
+            placement.position.x,
+            placement.position.y,
+            advanced_score_with_breakdown(placement, game_state, &maps, weights)
+        );
+    }
+}
+
+/// Record the opponent's turn, seen as the board change since our last move
+fn record_opponent_turn(history: &mut Vec<TurnHistory>, grid: &Grid, player_number: u8) {
+    let Some(previous) = history.last() else {
+        return;
+    };
+    let opponent = GameState::opponent_of(player_number);
+    let mut turn = TurnHistory::after_turn(previous.turn + 1, opponent, grid, None);
+    turn.cells_gained = turn.territory_of(opponent).saturating_sub(previous.territory_of(opponent));
+    history.push(turn);
+}
+
+/// Play one engine turn and append it to `history`
+///
+/// `weights` drive both move selection and `--debug-scores` output.
+/// `turn_cache` is cleared here and shared by everything scoring this turn.
+fn play_turn(
+    game_input: GameInput,
+    io: &mut StdinStdout,
+    history: &mut Vec<TurnHistory>,
+    move_log: Option<&mut MoveLog>,
+    weights: &HeuristicWeights,
+    turn_cache: &mut TurnCache,
+    debug_scores: bool,
+) {
+    turn_cache.clear();
+    eprintln!("Player: {}", game_input.player_number);
+    eprintln!("Anfield: {} x {}", game_input.anfield.width, game_input.anfield.height);
+    eprintln!("Piece: {} x {}", game_input.piece.width, game_input.piece.height);
     
-    match parse_game_input() {
-        Ok(game_input) => {
-            eprintln!("Player: {}", game_input.player_number);
-            eprintln!("Anfield: {} x {}", game_input.anfield.width, game_input.anfield.height);
-            eprintln!("Piece: {} x {}", game_input.piece.width, game_input.piece.height);
-            
-            // Convert parsed input to internal game state representation
-            let grid = Grid::from_chars(
-                game_input.anfield.width,
-                game_input.anfield.height,
-                game_input.anfield.grid,
-            );
+    // Convert parsed input to internal game state representation
+    let grid = Grid::from_chars(
+        game_input.anfield.width,
+        game_input.anfield.height,
+        game_input.anfield.grid,
+    );
+    
+    let shape = Shape::from_chars(
+        game_input.piece.width,
+        game_input.piece.height,
+        game_input.piece.shape,
+    );
+    
+    record_opponent_turn(history, &grid, game_input.player_number);
+    let game_state = GameState::new(game_input.player_number, grid, shape).with_history(history.clone());
+    let turn = history.last().map_or(0, |t| t.turn + 1);
+    
+    // Debug output
+    game_state.print();
+    if let Ok(prefix) = std::env::var("FILLER_DEBUG_IMG") {
+        write_debug_images(game_state.grid(), &prefix);
+    }
+    
+    // Find all valid placements
+    let valid_placements = find_all_valid_placements(&game_state);
+    
+    if valid_placements.is_empty() {
+        eprintln!("No valid placements available!");
+        if let Err(e) = Move::fallback().submit_to(io) {
+            eprintln!("Error submitting fallback move: {}", e);
+        }
+        history.push(TurnHistory::after_turn(turn, game_state.player_number(), game_state.grid(), None));
+        return;
+    }
+    
+    eprintln!("Found {} valid placements", valid_placements.len());
+    if debug_scores {
+        print_score_breakdowns(&valid_placements, &game_state, weights);
+    }
+    
+    // Use AI to select best placement
+    let eval_weights = EvalWeights::from_heuristics(weights);
+    match select_move_default_with_cache(&valid_placements, &game_state, &eval_weights, turn_cache) {
+        Some(placement) => {
+            let game_move = Move::new(placement.position.x, placement.position.y);
             
-            let shape = Shape::from_chars(
-                game_input.piece.width,
-                game_input.piece.height,
-                game_input.piece.shape,
+            eprintln!(
+                "AI selected placement at ({}, {}) - adds {} cells",
+                placement.position.x, placement.position.y, placement.cells_added
             );
             
-            let game_state = GameState::new(game_input.player_number, grid, shape);
-            
-            // Debug output
-            game_state.print();
-            
-            // Find all valid placements
-            let valid_placements = find_all_valid_placements(&game_state);
-            
-            if valid_placements.is_empty() {
-                eprintln!("No valid placements available!");
-                if let Err(e) = Move::fallback().submit() {
-                    eprintln!("Error submitting fallback move: {}", e);
-                }
-            } else {
-                eprintln!("Found {} valid placements", valid_placements.len());
-                
-                // Use AI to select best placement
-                match select_move_default(&valid_placements, &game_state) {
-                    Some(placement) => {
-                        let game_move = Move::new(placement.position.x, placement.position.y);
-                        
-                        eprintln!(
-                            "AI selected placement at ({}, {}) - adds {} cells",
-                            placement.position.x, placement.position.y, placement.cells_added
-                        );
-                        
-                        if let Err(e) = game_move.submit() {
-                            eprintln!("Error submitting move: {}", e);
-                        }
-                    }
-                    None => {
-                        eprintln!("AI failed to select placement, using fallback");
-                        if let Err(e) = Move::fallback().submit() {
-                            eprintln!("Error submitting fallback move: {}", e);
-                        }
-                    }
-                }
+            if let Err(e) = game_move.submit_with_log(io, game_state.player_number(), move_log) {
+                eprintln!("Error submitting move: {}", e);
             }
+            let after = game_state.apply_placement(&placement);
+            history.push(TurnHistory::after_turn(turn, game_state.player_number(), after.grid(), Some(placement)));
         }
-        Err(e) => {
-            eprintln!("Error parsing input: {}", e);
-            // Output fallback move when parsing fails
-            if let Err(e) = Move::fallback().submit() {
+        None => {
+            eprintln!("AI failed to select placement, using fallback");
+            if let Err(e) = Move::fallback().submit_to(io) {
                 eprintln!("Error submitting fallback move: {}", e);
             }
+            history.push(TurnHistory::after_turn(turn, game_state.player_number(), game_state.grid(), None));
+        }
+    }
+}
+
+fn main() {
+    eprintln!("Starting Filler AI...");
+    let debug_scores = std::env::args().any(|arg| arg == "--debug-scores");
+    // FILLER_WEIGHT_* overrides, read once for the whole game
+    let weights = HeuristicWeights::from_env();
+    
+    // Optional move log for post-game analysis
+    let mut move_log = std::env::var("FILLER_MOVE_LOG").ok().and_then(|path| {
+        MoveLog::new(&path)
+            .map_err(|e| eprintln!("Error creating move log {}: {}", path, e))
+            .ok()
+    });
+    
+    // The engine sends the player line once, then one board and piece per turn
+    let mut io = StdinStdout;
+    let mut history = Vec::new();
+    let mut turn_cache = TurnCache::new();
+    let mut next_turn = parse_game_input(&mut io).map(Some);
+    loop {
+        match next_turn {
+            Ok(Some(game_input)) => {
+                let player_number = game_input.player_number;
+                play_turn(
+                    game_input,
+                    &mut io,
+                    &mut history,
+                    move_log.as_mut(),
+                    &weights,
+                    &mut turn_cache,
+                    debug_scores,
+                );
+                next_turn = parse_next_turn(&mut io, player_number);
+            }
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("Error parsing input: {}", e);
+                // Output fallback move when parsing fails
+                if let Err(e) = Move::fallback().submit_to(&mut io) {
+                    eprintln!("Error submitting fallback move: {}", e);
+                }
+                break;
+            }
         }
     }
 }
@@ -2,7 +2,11 @@
 /// 
 /// Handles outputting moves in the format expected by the game engine: X Y\n
 
-use std::io::{self, Write};
+use crate::error::FillerError;
+use crate::game_io::GameIO;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
 
 /// Represents a move to be submitted to the game engine
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,12 +27,95 @@ impl Move {
     }
 
     /// Submit the move to stdout in the format expected by game engine
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn submit(&self) -> io::Result<()> {
         let stdout = io::stdout();
         let mut handle = stdout.lock();
         writeln!(handle, "{} {}", self.x, self.y)?;
         Ok(())
     }
+
+    /// Submit the move through `game_io`
+    pub fn submit_to(&self, game_io: &mut dyn GameIO) -> Result<(), FillerError> {
+        game_io.write_move(self.x, self.y)
+    }
+
+    /// Submit the move through `game_io`, recording it in the move log first
+    /// if one is given
+    pub fn submit_with_log(
+        &self,
+        game_io: &mut dyn GameIO,
+        player: u8,
+        log: Option<&mut MoveLog>,
+    ) -> Result<(), FillerError> {
+        if let Some(Err(e)) = log.map(|log| log.record(player, *self)) {
+            eprintln!("Error recording move: {}", e);
+        }
+        self.submit_to(game_io)
+    }
+}
+
+/// Record of all moves submitted during a game, mirrored to a file
+///
+/// Each line of the file has the form `"{player}: {x} {y}"`.
+#[derive(Debug, Clone)]
+pub struct MoveLog {
+    pub path: PathBuf,
+    pub entries: Vec<(u8, Move)>,
+}
+
+impl MoveLog {
+    /// Create a move log, creating (or truncating) the file at `path`
+    pub fn new(path: &str) -> Result<Self, io::Error> {
+        File::create(path)?;
+        Ok(MoveLog {
+            path: PathBuf::from(path),
+            entries: Vec::new(),
+        })
+    }
+
+    /// Record a move and append it to the log file
+    pub fn record(&mut self, player: u8, m: Move) -> io::Result<()> {
+        self.entries.push((player, m));
+        let mut file = OpenOptions::new().append(true).create(true).open(&self.path)?;
+        writeln!(file, "{}: {} {}", player, m.x, m.y)
+    }
+
+    /// Rewrite the log file from the in-memory entries
+    pub fn flush(&self) -> io::Result<()> {
+        let mut contents = String::new();
+        for (player, m) in &self.entries {
+            contents.push_str(&format!("{}: {} {}\n", player, m.x, m.y));
+        }
+        fs::write(&self.path, contents)
+    }
+
+    /// Read back all moves from a log file written by `MoveLog`
+    pub fn replay_from_file(path: &str) -> Result<Vec<(u8, Move)>, io::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut moves = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            moves.push(parse_log_line(&line).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("Invalid move log line: {}", line))
+            })?);
+        }
+
+        Ok(moves)
+    }
+}
+
+/// Parse a single `"{player}: {x} {y}"` log line
+fn parse_log_line(line: &str) -> Option<(u8, Move)> {
+    let (player, coords) = line.split_once(':')?;
+    let mut parts = coords.split_whitespace();
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    Some((player.trim().parse().ok()?, Move::new(x, y)))
 }
 
 #[cfg(test)]
@@ -55,4 +142,46 @@ mod tests {
         let m2 = Move::new(5, 5);
         assert_eq!(m1, m2);
     }
+
+    fn temp_log_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("filler_{}_{}.log", name, std::process::id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_move_log_round_trip() {
+        let path = temp_log_path("round_trip");
+        let moves = vec![
+            (1, Move::new(3, 4)),
+            (2, Move::new(10, 0)),
+            (1, Move::new(0, 7)),
+            (2, Move::new(12, 15)),
+            (1, Move::new(5, 5)),
+        ];
+
+        let mut log = MoveLog::new(&path).unwrap();
+        for &(player, m) in &moves {
+            log.record(player, m).unwrap();
+        }
+        log.flush().unwrap();
+
+        let replayed = MoveLog::replay_from_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(log.entries, moves);
+        assert_eq!(replayed, moves);
+    }
+
+    #[test]
+    fn test_move_log_rejects_malformed_line() {
+        let path = temp_log_path("malformed");
+        fs::write(&path, "1: 3 4\nnot a move\n").unwrap();
+
+        let result = MoveLog::replay_from_file(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(result.is_err());
+    }
 }
@@ -9,7 +9,9 @@
 /// Piece W H:
 /// [piece grid]
 
-use std::io::{self, BufRead};
+use crate::error::FillerError;
+use crate::game_io::{GameIO, ReaderIO};
+use std::io::BufRead;
 
 #[derive(Debug, Clone)]
 pub struct GameInput {
@@ -56,28 +58,22 @@ impl Piece {
     }
 }
 
-/// Parse a single game input from stdin
+/// Parse a single game input from the engine connection `io`
 /// 
 /// # Returns
 /// - `Ok(GameInput)` if parsing succeeds
-/// - `Err(String)` if parsing fails with error message
-pub fn parse_game_input() -> Result<GameInput, String> {
-    let stdin = io::stdin();
-    let mut reader = stdin.lock();
-    let mut line = String::new();
-
+/// - `Err(FillerError)` describing the read or format failure
+pub fn parse_game_input(io: &mut dyn GameIO) -> Result<GameInput, FillerError> {
     // Parse player identification line: $$$ exec p<number> : [<player_path>]
-    reader
-        .read_line(&mut line)
-        .map_err(|e| format!("Failed to read player line: {}", e))?;
+    let line = io.read_line()?;
     
     let player_number = parse_player_line(&line)?;
 
     // Parse Anfield section
-    let anfield = parse_anfield(&mut reader)?;
+    let anfield = parse_anfield(io)?;
 
     // Parse Piece section
-    let piece = parse_piece(&mut reader)?;
+    let piece = parse_piece(io)?;
 
     Ok(GameInput {
         player_number,
@@ -86,25 +82,100 @@ pub fn parse_game_input() -> Result<GameInput, String> {
     })
 }
 
+/// Parse the next turn of a running game
+///
+/// The engine sends the player line only before the first turn, so later
+/// turns are just the Anfield and Piece sections, attributed to
+/// `player_number`. Blank lines between turns are skipped.
+///
+/// # Returns
+/// - `Ok(Some(GameInput))` for the next turn
+/// - `Ok(None)` once the engine closes the connection
+/// - `Err(FillerError)` describing the read or format failure
+pub fn parse_next_turn(io: &mut dyn GameIO, player_number: u8) -> Result<Option<GameInput>, FillerError> {
+    let header = loop {
+        let line = io.read_line()?;
+        if line.is_empty() {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break line;
+        }
+    };
+
+    let anfield = parse_anfield_body(&header, io)?;
+    let piece = parse_piece(io)?;
+
+    Ok(Some(GameInput {
+        player_number,
+        anfield,
+        piece,
+    }))
+}
+
+/// Parse a single game input from any reader
+///
+/// Never panics: malformed or truncated input, invalid UTF-8 and absurd
+/// dimensions all surface as a `FillerError`. This is the entry point of
+/// the `parse_game_input` fuzz target.
+pub fn parse_game_input_from(reader: &mut dyn BufRead) -> Result<GameInput, FillerError> {
+    parse_game_input(&mut ReaderIO::new(reader))
+}
+
+/// Parse every turn of a full game log
+///
+/// Reads a log from the filler VM: one or more `$$$ exec` player lines,
+/// each followed by any number of Anfield/Piece turns. Blank lines and
+/// other VM output (such as `<got` move echoes) between turns are skipped.
+/// Each turn is attributed to the most recent `$$$ exec` line.
+///
+/// # Returns
+/// - `Ok(Vec<GameInput>)` with one entry per turn, in log order
+/// - `Err(FillerError)` if a turn is malformed or appears before any player line
+pub fn parse_full_game(reader: &mut dyn BufRead) -> Result<Vec<GameInput>, FillerError> {
+    let mut io = ReaderIO::new(reader);
+    let mut turns = Vec::new();
+    let mut player_number = None;
+
+    loop {
+        let line = io.read_line()?;
+        if line.is_empty() {
+            break;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.starts_with("$$$ exec") {
+            player_number = Some(parse_player_line(trimmed)?);
+        } else if trimmed.starts_with("Anfield") {
+            let player_number = player_number
+                .ok_or(FillerError::MissingPlayerLine { turn: turns.len() })?;
+            let anfield = parse_anfield_body(trimmed, &mut io)?;
+            let piece = parse_piece(&mut io)?;
+            turns.push(GameInput {
+                player_number,
+                anfield,
+                piece,
+            });
+        }
+    }
+
+    Ok(turns)
+}
+
 /// Extract player number from the first line
 /// Expected format: $$$ exec p<number> : [<player_path>]
-fn parse_player_line(line: &str) -> Result<u8, String> {
+fn parse_player_line(line: &str) -> Result<u8, FillerError> {
     let trimmed = line.trim();
+    let invalid = || FillerError::InvalidPlayerLine { raw: trimmed.to_string() };
     
     // Find 'p' character and extract number after it
-    if let Some(p_idx) = trimmed.find('p') {
-        let after_p = &trimmed[p_idx + 1..];
-        let number_str = after_p
-            .chars()
-            .take_while(|c| c.is_ascii_digit())
-            .collect::<String>();
-        
-        number_str
-            .parse::<u8>()
-            .map_err(|e| format!("Failed to parse player number: {}", e))
-    } else {
-        Err("Player line missing 'p' character".to_string())
-    }
+    let p_idx = trimmed.find('p').ok_or_else(invalid)?;
+    let number_str = trimmed[p_idx + 1..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    
+    number_str.parse::<u8>().map_err(|_| invalid())
 }
 
 /// Parse the Anfield section
@@ -113,31 +184,29 @@ fn parse_player_line(line: &str) -> Result<u8, String> {
 ///     [column indices]
 /// [row_num] [grid row]
 /// ...
-fn parse_anfield(reader: &mut dyn BufRead) -> Result<Anfield, String> {
-    let mut line = String::new();
-
+fn parse_anfield(io: &mut dyn GameIO) -> Result<Anfield, FillerError> {
     // Read "Anfield W H:" line
-    reader
-        .read_line(&mut line)
-        .map_err(|e| format!("Failed to read Anfield header: {}", e))?;
+    let line = io.read_line()?;
+
+    parse_anfield_body(&line, io)
+}
 
-    let (width, height) = parse_anfield_dimensions(&line)?;
+/// Parse the rest of the Anfield section once its header line has been read
+fn parse_anfield_body(header: &str, io: &mut dyn GameIO) -> Result<Anfield, FillerError> {
+    let (width, height) = parse_anfield_dimensions(header)?;
 
     // Read column indices line (we can skip it)
-    line.clear();
-    reader
-        .read_line(&mut line)
-        .map_err(|e| format!("Failed to read column indices: {}", e))?;
+    io.read_line()?;
 
     // Read grid rows
     let mut grid = Vec::new();
-    for _ in 0..height {
-        line.clear();
-        reader
-            .read_line(&mut line)
-            .map_err(|e| format!("Failed to read grid row: {}", e))?;
+    for y in 0..height {
+        let line = io.read_line()?;
 
-        let row = parse_grid_row(&line, width)?;
+        let row = parse_grid_row(&line, width).ok_or_else(|| FillerError::InvalidGridRow {
+            row: y,
+            raw: line.trim_end().to_string(),
+        })?;
         grid.push(row);
     }
 
@@ -145,74 +214,59 @@ fn parse_anfield(reader: &mut dyn BufRead) -> Result<Anfield, String> {
 }
 
 /// Parse anfield dimensions from "Anfield W H:" line
-fn parse_anfield_dimensions(line: &str) -> Result<(usize, usize), String> {
-    let trimmed = line.trim();
-    let parts: Vec<&str> = trimmed.split_whitespace().collect();
+fn parse_anfield_dimensions(line: &str) -> Result<(usize, usize), FillerError> {
+    parse_dimensions(line).ok_or_else(|| FillerError::InvalidAnfieldHeader {
+        raw: line.trim().to_string(),
+    })
+}
 
+/// Parse `W` and `H` from a `<Name> W H:` header line
+fn parse_dimensions(line: &str) -> Option<(usize, usize)> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
     if parts.len() < 3 {
-        return Err("Invalid Anfield header format".to_string());
+        return None;
     }
 
-    let width = parts[1]
-        .parse::<usize>()
-        .map_err(|e| format!("Failed to parse width: {}", e))?;
-
-    let height = parts[2]
-        .trim_end_matches(':')
-        .parse::<usize>()
-        .map_err(|e| format!("Failed to parse height: {}", e))?;
-
-    Ok((width, height))
+    let width = parts[1].parse::<usize>().ok()?;
+    let height = parts[2].trim_end_matches(':').parse::<usize>().ok()?;
+    Some((width, height))
 }
 
 /// Parse a single grid row
 /// Format: [row_num] [grid content]
-fn parse_grid_row(line: &str, width: usize) -> Result<Vec<char>, String> {
+///
+/// Returns `None` if the row has no content or the wrong width.
+fn parse_grid_row(line: &str, width: usize) -> Option<Vec<char>> {
     let trimmed = line.trim();
     
     // Find where the actual grid content starts (after row number and space)
-    let grid_start = trimmed
-        .find(' ')
-        .ok_or("Invalid grid row format")?
-        + 1;
+    let grid_start = trimmed.find(' ')? + 1;
 
     let grid_content = &trimmed[grid_start..];
     let row: Vec<char> = grid_content.chars().take(width).collect();
 
-    if row.len() != width {
-        return Err(format!(
-            "Grid row has {} chars, expected {}",
-            row.len(),
-            width
-        ));
-    }
-
-    Ok(row)
+    (row.len() == width).then_some(row)
 }
 
 /// Parse the Piece section
 /// Expected format:
 /// Piece W H:
 /// [piece grid]
-fn parse_piece(reader: &mut dyn BufRead) -> Result<Piece, String> {
-    let mut line = String::new();
-
+fn parse_piece(io: &mut dyn GameIO) -> Result<Piece, FillerError> {
     // Read "Piece W H:" line
-    reader
-        .read_line(&mut line)
-        .map_err(|e| format!("Failed to read Piece header: {}", e))?;
+    let line = io.read_line()?;
 
     let (width, height) = parse_piece_dimensions(&line)?;
 
     // Read piece shape rows
     let mut shape = Vec::new();
-    for _ in 0..height {
-        line.clear();
-        reader
-            .read_line(&mut line)
-            .map_err(|e| format!("Failed to read piece row: {}", e))?;
+    for y in 0..height {
+        let line = io.read_line()?;
 
-        let row = parse_piece_row(&line, width)?;
+        let row = parse_piece_row(&line, width).ok_or_else(|| FillerError::InvalidPieceRow {
+            row: y,
+            raw: line.trim_end().to_string(),
+        })?;
         shape.push(row);
     }
 
@@ -224,45 +278,27 @@ fn parse_piece(reader: &mut dyn BufRead) -> Result<Piece, String> {
 }
 
 /// Parse piece dimensions from "Piece W H:" line
-fn parse_piece_dimensions(line: &str) -> Result<(usize, usize), String> {
-    let trimmed = line.trim();
-    let parts: Vec<&str> = trimmed.split_whitespace().collect();
-
-    if parts.len() < 3 {
-        return Err("Invalid Piece header format".to_string());
-    }
-
-    let width = parts[1]
-        .parse::<usize>()
-        .map_err(|e| format!("Failed to parse piece width: {}", e))?;
-
-    let height = parts[2]
-        .trim_end_matches(':')
-        .parse::<usize>()
-        .map_err(|e| format!("Failed to parse piece height: {}", e))?;
-
-    Ok((width, height))
+fn parse_piece_dimensions(line: &str) -> Result<(usize, usize), FillerError> {
+    parse_dimensions(line).ok_or_else(|| FillerError::InvalidPieceDimensions {
+        raw: line.trim().to_string(),
+    })
 }
 
 /// Parse a single piece row
-fn parse_piece_row(line: &str, width: usize) -> Result<Vec<char>, String> {
+///
+/// Returns `None` if the row is narrower than `width`.
+fn parse_piece_row(line: &str, width: usize) -> Option<Vec<char>> {
     let trimmed = line.trim();
     let row: Vec<char> = trimmed.chars().take(width).collect();
 
-    if row.len() != width {
-        return Err(format!(
-            "Piece row has {} chars, expected {}",
-            row.len(),
-            width
-        ));
-    }
-
-    Ok(row)
+    (row.len() == width).then_some(row)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game_io::SliceIO;
+    use std::io;
 
     #[test]
     fn test_parse_player_line() {
@@ -273,6 +309,12 @@ mod tests {
         assert_eq!(parse_player_line(line2).unwrap(), 2);
     }
 
+    #[test]
+    fn test_parse_player_line_multi_player() {
+        assert_eq!(parse_player_line("$$$ exec p3 : [robots/h2_d2]").unwrap(), 3);
+        assert_eq!(parse_player_line("$$$ exec p4 : [robots/wall_e]").unwrap(), 4);
+    }
+
     #[test]
     fn test_parse_anfield_dimensions() {
         let line = "Anfield 20 15:";
@@ -305,4 +347,140 @@ mod tests {
         assert_eq!(row[1], 'O');
         assert_eq!(row[2], 'O');
     }
+
+    const TURN_P1: &str = "$$$ exec p1 : [robots/bender]
+Anfield 4 2:
+    0123
+000 @...
+001 ...$
+Piece 2 1:
+**
+";
+
+    const TURN_P2: &str = "$$$ exec p2 : [robots/terminator]
+Anfield 4 2:
+    0123
+000 @a..
+001 ...$
+Piece 1 2:
+*
+*
+";
+
+    #[test]
+    fn test_parse_full_game() {
+        let log = format!("{}\n<got (O): [0, 0]\n\n{}", TURN_P1, TURN_P2);
+        let turns = parse_full_game(&mut io::Cursor::new(log)).unwrap();
+
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].player_number, 1);
+        assert_eq!(turns[1].player_number, 2);
+        assert_eq!(turns[1].anfield.grid[0], vec!['@', 'a', '.', '.']);
+        assert_eq!((turns[1].piece.width, turns[1].piece.height), (1, 2));
+    }
+
+    #[test]
+    fn test_parse_full_game_errors() {
+        assert!(parse_full_game(&mut io::Cursor::new("")).unwrap().is_empty());
+
+        let headless = TURN_P1.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert!(parse_full_game(&mut io::Cursor::new(headless)).is_err());
+
+        let truncated = &TURN_P1[..TURN_P1.find("Piece").unwrap()];
+        assert!(parse_full_game(&mut io::Cursor::new(truncated)).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_are_structured() {
+        assert!(matches!(
+            parse_player_line("$$$ exec : [robots/bender]"),
+            Err(FillerError::InvalidPlayerLine { .. })
+        ));
+        assert!(matches!(
+            parse_anfield_dimensions("Anfield 20:"),
+            Err(FillerError::InvalidAnfieldHeader { .. })
+        ));
+        assert!(matches!(
+            parse_piece_dimensions("Piece x 1:"),
+            Err(FillerError::InvalidPieceDimensions { .. })
+        ));
+
+        let short_row = TURN_P1.replace("001 ...$", "001 ..");
+        match parse_full_game(&mut io::Cursor::new(short_row)) {
+            Err(FillerError::InvalidGridRow { row, raw }) => {
+                assert_eq!(row, 1);
+                assert_eq!(raw, "001 ..");
+            }
+            other => panic!("expected InvalidGridRow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_game_input_from() {
+        let input = parse_game_input_from(&mut io::Cursor::new(TURN_P1)).unwrap();
+        assert_eq!(input.player_number, 1);
+        assert_eq!(input.anfield.grid[1], vec!['.', '.', '.', '$']);
+        assert_eq!(input.piece.shape, vec![vec!['*', '*']]);
+
+        let invalid_utf8 = b"$$$ exec p1 : []\nAnfield 1 1:\n    0\n000 \xff\n".to_vec();
+        assert!(matches!(
+            parse_game_input_from(&mut io::Cursor::new(invalid_utf8)),
+            Err(FillerError::Io(_))
+        ));
+
+        let huge = "$$$ exec p1 : []\nAnfield 99999999999999 99999999999999:\n    0\n";
+        assert!(matches!(
+            parse_game_input_from(&mut io::Cursor::new(huge)),
+            Err(FillerError::InvalidGridRow { row: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_game_input_from_game_io() {
+        let mut io = SliceIO::new(TURN_P1);
+        let input = parse_game_input(&mut io).unwrap();
+
+        assert_eq!(input.player_number, 1);
+        assert_eq!(input.piece.shape, vec![vec!['*', '*']]);
+        assert!(matches!(parse_game_input(&mut io), Err(FillerError::InvalidPlayerLine { .. })));
+    }
+
+    #[test]
+    fn test_parse_next_turn() {
+        let body = &TURN_P2[TURN_P2.find("Anfield").unwrap()..];
+        let session = format!("{}{}\n", TURN_P1, body);
+        let mut io = SliceIO::new(&session);
+        let first = parse_game_input(&mut io).unwrap();
+
+        let next = parse_next_turn(&mut io, first.player_number).unwrap().unwrap();
+        assert_eq!(next.player_number, 1);
+        assert_eq!(next.anfield.grid[0], vec!['@', 'a', '.', '.']);
+        assert_eq!((next.piece.width, next.piece.height), (1, 2));
+        assert!(parse_next_turn(&mut io, 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_game_input_from_never_panics() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        // Cheap stand-in for the fuzz target: random byte flips, truncations
+        // and multibyte characters on top of a valid turn
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..2000 {
+            let mut bytes = TURN_P1.as_bytes().to_vec();
+            for _ in 0..(rng.next_u64() % 4) {
+                let index = (rng.next_u64() as usize) % bytes.len();
+                bytes[index] = match rng.next_u64() % 4 {
+                    0 => b' ',
+                    1 => b'\n',
+                    2 => 0xc3,
+                    _ => rng.next_u64() as u8,
+                };
+            }
+            bytes.truncate((rng.next_u64() as usize) % (bytes.len() + 1));
+
+            let _ = parse_game_input_from(&mut io::Cursor::new(bytes));
+        }
+    }
 }
@@ -3,7 +3,10 @@
 /// This module handles all logic related to validating piece placements,
 /// including boundary checking, collision detection, and territory overlap.
 
-use crate::game_state::{Position, Grid, Shape, CellState, GameState};
+use crate::game_state::{Position, Grid, Shape, ShapeMask, CellState, GameState, Symmetry};
+use crate::utils::bfs_distance_map;
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
 
 /// Represents a potential placement of a piece at a given position
 #[derive(Debug, Clone, PartialEq)]
@@ -27,6 +30,127 @@ impl Placement {
             .map(|p| Position::new(self.position.x + p.x, self.position.y + p.y))
             .collect()
     }
+
+    /// Bounding box of the occupied cells as `(min_x, min_y, width, height)`
+    ///
+    /// In board coordinates. An empty shape gives a zero-sized box at
+    /// `position`.
+    pub fn bounding_box(&self) -> (usize, usize, usize, usize) {
+        match self.shape.bounding_box() {
+            Some((x, y, width, height)) => (self.position.x + x, self.position.y + y, width, height),
+            None => (self.position.x, self.position.y, 0, 0),
+        }
+    }
+
+    /// Quality metrics of this placement on `game_state`
+    ///
+    /// Computed on demand rather than during the placement scan, since
+    /// only late-game selection reads them.
+    pub fn quality(&self, game_state: &GameState) -> PlacementQuality {
+        compute_quality(self, game_state)
+    }
+
+    /// Mean x and y of the occupied cells, in board coordinates
+    ///
+    /// Where a piece sits, as opposed to `position`, its top-left corner,
+    /// which is off-center for large pieces. An empty shape gives
+    /// `position`. Computed from the shape's cells on each call, without
+    /// allocating.
+    pub fn centroid(&self) -> (f32, f32) {
+        let (count, sum_x, sum_y) = self
+            .shape
+            .cells
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().filter(|&(_, &filled)| filled).map(move |(x, _)| (x, y)))
+            .fold((0usize, 0usize, 0usize), |(n, sx, sy), (x, y)| (n + 1, sx + x, sy + y));
+        if count == 0 {
+            return (self.position.x as f32, self.position.y as f32);
+        }
+        (
+            self.position.x as f32 + sum_x as f32 / count as f32,
+            self.position.y as f32 + sum_y as f32 / count as f32,
+        )
+    }
+
+    /// Euclidean distance from the centroid to the point `(x, y)`
+    pub fn distance_to_point(&self, x: f32, y: f32) -> f32 {
+        let (cx, cy) = self.centroid();
+        ((cx - x).powi(2) + (cy - y).powi(2)).sqrt()
+    }
+
+    /// Produce the grid that would result from `player` making this placement
+    ///
+    /// Placed cells are marked with `player`'s last-piece state, so
+    /// heuristics reading ownership off the result see them as the mover's.
+    /// Unlike `Grid::apply_placement`, the previous last piece is not
+    /// demoted. An invalid player number leaves the grid unchanged.
+    pub fn simulate(&self, grid: &Grid, player: u8) -> Grid {
+        let mut simulated = grid.clone();
+        if let Some((_, last)) = CellState::player_states(player) {
+            for pos in self.get_absolute_positions() {
+                simulated.set(pos, last);
+            }
+        }
+        simulated
+    }
+
+    /// Drop placements that cover exactly the same cells as an earlier one
+    ///
+    /// Only a symmetric piece can cover the same cells in two orientations,
+    /// so lists built from several orientations of a piece with
+    /// `Symmetry::None` (see `Shape::symmetry_class`) are returned as is.
+    /// Otherwise the first placement of each covered-cell set is kept, which
+    /// halves the list when a piece and its mirror image or half-turn were
+    /// both searched.
+    pub fn deduplicate_symmetric(placements: Vec<Placement>, shape_symmetry: Symmetry) -> Vec<Placement> {
+        if shape_symmetry == Symmetry::None {
+            return placements;
+        }
+
+        deduplicate_placements(placements)
+    }
+
+    /// Number of board cells covered by both `self` and `other`
+    pub fn coverage_overlap(&self, other: &Placement) -> usize {
+        let cells: HashSet<Position> = self.get_absolute_positions().into_iter().collect();
+        other
+            .get_absolute_positions()
+            .into_iter()
+            .filter(|pos| cells.contains(pos))
+            .count()
+    }
+
+    /// Change in the current player's Voronoi territory from this placement
+    ///
+    /// Voronoi territory is owned cells plus empty cells the player reaches
+    /// strictly before the opponent (see `Grid::voronoi_distance_pair`).
+    /// Unlike `cells_added`, this counts space cut off from the opponent.
+    /// Returns 0 if the placement would shrink the player's share.
+    pub fn simulate_territory_gain(&self, game_state: &GameState) -> usize {
+        let player = game_state.player_number();
+        let mut after = game_state.grid().grid().clone();
+        after.apply_placement(self, player);
+
+        voronoi_cells(&after, player).saturating_sub(voronoi_cells(game_state.grid(), player))
+    }
+}
+
+/// Cells `player` (1 or 2) owns or reaches strictly before the other player
+fn voronoi_cells(grid: &Grid, player: u8) -> usize {
+    let (player1, player2) = grid.voronoi_distance_pair();
+    let (mine, theirs) = if player == 1 { (player1, player2) } else { (player2, player1) };
+
+    grid.iter_positions()
+        .filter(|&(pos, state)| match state.player_num() {
+            Some(owner) => owner == player,
+            None => match (mine[pos.y][pos.x], theirs[pos.y][pos.x]) {
+                (Some(d), Some(other)) => d < other,
+                (Some(_), None) => true,
+                _ => false,
+            },
+        })
+        .count()
 }
 
 /// Result of a placement attempt
@@ -44,6 +168,8 @@ pub enum PlacementError {
     MultipleContacts,
     /// Piece shape is empty (no filled cells)
     EmptyShape,
+    /// Piece comes closer to the opponent than a `PlacementConstraint` allows
+    TooCloseToOpponent,
 }
 
 impl std::fmt::Display for PlacementError {
@@ -55,11 +181,31 @@ impl std::fmt::Display for PlacementError {
             PlacementError::NoTerritoryContact => "Piece doesn't touch existing territory",
             PlacementError::MultipleContacts => "Piece touches territory at multiple cells",
             PlacementError::EmptyShape => "Piece shape is empty",
+            PlacementError::TooCloseToOpponent => "Piece is too close to opponent territory",
         };
         write!(f, "{}", msg)
     }
 }
 
+/// Quality metrics describing a valid placement (see `Placement::quality`)
+///
+/// A valid placement covers exactly one own cell, so the two ratios only
+/// depend on the piece's cell count; compare placements of one piece by
+/// `edge_proximity`, `frontier_adjacency` and `growth_room`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacementQuality {
+    /// Filled cells on own territory / total filled cells
+    pub territory_coverage_ratio: f32,
+    /// Empty cells added / total filled cells
+    pub expansion_efficiency: f32,
+    /// Minimum distance from any filled cell to the grid boundary
+    pub edge_proximity: usize,
+    /// Number of filled cells adjacent to a frontier cell of own territory
+    pub frontier_adjacency: usize,
+    /// Distinct empty cells bordering the filled cells after the placement
+    pub growth_room: usize,
+}
+
 /// Check if a piece placement is valid
 pub fn validate_placement(
     game_state: &GameState,
@@ -75,26 +221,20 @@ pub fn validate_placement(
     
     // Check for collisions and territory contact
     let mut territory_touches = 0;
-    let player_num = game_state.player_number;
+    let player_num = game_state.player_number();
     
     for &pos in &absolute_positions {
-        match game_state.grid.get(pos) {
+        match game_state.grid().get(pos) {
             None => return Err(PlacementError::OutOfBounds),
             Some(cell) => {
-                match cell {
-                    CellState::Empty => {
+                match cell.player_num() {
+                    None => {
                         // Empty cells are OK
                     }
-                    CellState::Player1 | CellState::Player1Last if player_num == 1 => {
-                        territory_touches += 1;
-                    }
-                    CellState::Player2 | CellState::Player2Last if player_num == 2 => {
+                    Some(owner) if owner == player_num => {
                         territory_touches += 1;
                     }
-                    CellState::Player1 | CellState::Player1Last => {
-                        return Err(PlacementError::CollisionWithOpponent);
-                    }
-                    CellState::Player2 | CellState::Player2Last => {
+                    Some(_) => {
                         return Err(PlacementError::CollisionWithOpponent);
                     }
                 }
@@ -118,16 +258,186 @@ pub fn validate_placement(
     }
 }
 
+/// Extra rules a placement must meet on top of the game's own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlacementConstraint {
+    /// Smallest Manhattan distance allowed between a piece cell and an
+    /// opponent cell; `Some(2)` forbids touching the opponent
+    pub require_opponent_distance: Option<usize>,
+}
+
+impl PlacementConstraint {
+    /// Whether `placement` meets every constraint on `game_state`
+    ///
+    /// Only the cells within reach of each piece cell are checked, so this
+    /// is cheap enough to filter whole placement lists.
+    pub fn allows(&self, placement: &Placement, game_state: &GameState) -> bool {
+        let Some(distance) = self.require_opponent_distance else {
+            return true;
+        };
+        let opponent = game_state.opponent_number();
+        let reach = distance as i32 - 1;
+
+        placement.get_absolute_positions().into_iter().all(|pos| {
+            (-reach..=reach).all(|dy| {
+                let span = reach - dy.abs();
+                (-span..=span).all(|dx| {
+                    game_state
+                        .grid()
+                        .get_signed(pos.x as i32 + dx, pos.y as i32 + dy)
+                        .is_none_or(|cell| !cell.is_player(opponent))
+                })
+            })
+        })
+    }
+}
+
+/// `validate_placement` plus the rules in `constraints`
+///
+/// A placement the game accepts but `constraints` rule out fails with
+/// `PlacementError::TooCloseToOpponent`.
+pub fn validate_placement_constrained(
+    game_state: &GameState,
+    placement_pos: Position,
+    constraints: &PlacementConstraint,
+) -> Result<Placement, PlacementError> {
+    let placement = validate_placement(game_state, placement_pos)?;
+    if constraints.allows(&placement, game_state) {
+        Ok(placement)
+    } else {
+        Err(PlacementError::TooCloseToOpponent)
+    }
+}
+
+/// Compute quality metrics for `placement` on `game_state`
+fn compute_quality(placement: &Placement, game_state: &GameState) -> PlacementQuality {
+    let grid = game_state.grid();
+    let positions = placement.get_absolute_positions();
+    let total = positions.len() as f32;
+    let player_num = game_state.player_number();
+
+    let edge_proximity = positions
+        .iter()
+        .map(|p| p.x.min(p.y).min(grid.width - 1 - p.x).min(grid.height - 1 - p.y))
+        .min()
+        .unwrap_or(0);
+
+    // A frontier cell is an own cell with at least one empty neighbor
+    let is_frontier = |pos: Position| {
+        grid.get(pos).is_some_and(|c| c.is_player(player_num))
+            && get_neighbors(pos, grid.width, grid.height)
+                .into_iter()
+                .any(|n| grid.get(n) == Some(CellState::Empty))
+    };
+    let frontier_adjacency = positions
+        .iter()
+        .filter(|&&p| {
+            get_neighbors(p, grid.width, grid.height)
+                .into_iter()
+                .any(&is_frontier)
+        })
+        .count();
+
+    let mut open: Vec<Position> = positions
+        .iter()
+        .flat_map(|&p| get_neighbors(p, grid.width, grid.height))
+        .filter(|n| grid.get(*n) == Some(CellState::Empty) && !positions.contains(n))
+        .collect();
+    open.sort_unstable_by_key(|p| (p.y, p.x));
+    open.dedup();
+
+    PlacementQuality {
+        territory_coverage_ratio: placement.territory_touches as f32 / total,
+        expansion_efficiency: placement.cells_added as f32 / total,
+        edge_proximity,
+        frontier_adjacency,
+        growth_room: open.len(),
+    }
+}
+
+/// Lazy row-major scan for valid placements
+///
+/// Each `next` call validates positions until it finds the next valid
+/// placement, so callers that stop early skip the rest of the board.
+#[derive(Debug, Clone)]
+pub struct ValidPlacements<'a> {
+    game_state: &'a GameState,
+    /// Row-major index of the next position to try
+    index: usize,
+}
+
+impl Iterator for ValidPlacements<'_> {
+    type Item = Placement;
+
+    fn next(&mut self) -> Option<Placement> {
+        let grid = &self.game_state.grid();
+        while self.index < grid.width * grid.height {
+            let pos = Position::new(self.index % grid.width, self.index / grid.width);
+            self.index += 1;
+            if let Ok(placement) = validate_placement(self.game_state, pos) {
+                return Some(placement);
+            }
+        }
+        None
+    }
+}
+
+/// Iterate valid placements in row-major order without collecting them
+pub fn valid_placements_iter<'a>(game_state: &'a GameState) -> impl Iterator<Item = Placement> + 'a {
+    ValidPlacements {
+        game_state,
+        index: 0,
+    }
+}
+
 /// Find all valid placements for a piece at a given position
 pub fn find_all_valid_placements(game_state: &GameState) -> Vec<Placement> {
+    valid_placements_iter(game_state).collect()
+}
+
+/// Drop placements that cover exactly the same cells as an earlier one
+///
+/// Two placements are duplicates when their `coverage_overlap` is their
+/// whole area, which happens when shapes with different padding land on
+/// the same cells from different `position`s. The first of each
+/// covered-cell set is kept, in order.
+pub fn deduplicate_placements(placements: Vec<Placement>) -> Vec<Placement> {
+    let mut seen: HashSet<Vec<Position>> = HashSet::new();
+    placements
+        .into_iter()
+        .filter(|placement| {
+            let mut cells = placement.get_absolute_positions();
+            cells.sort_unstable_by_key(|p| (p.y, p.x));
+            seen.insert(cells)
+        })
+        .collect()
+}
+
+/// Find all valid placements using the bitboard representation
+///
+/// Produces the same placements as `find_all_valid_placements`, but checks
+/// collisions a row at a time with `BitGrid` instead of cell by cell.
+pub fn find_all_valid_placements_bitboard(game_state: &GameState) -> Vec<Placement> {
+    let shape = &game_state.current_piece;
+    let Some(mask) = ShapeMask::from_shape(shape) else {
+        return find_all_valid_placements(game_state);
+    };
+    let bits = game_state.grid().as_bitboard();
     let mut valid_placements = Vec::new();
 
-    // Try all possible positions in the grid
-    for y in 0..game_state.grid.height {
-        for x in 0..game_state.grid.width {
+    // Positions past these limits would push a filled cell off the board
+    let max_y = game_state.grid().height.saturating_sub(mask.max_y);
+    let max_x = game_state.grid().width.saturating_sub(mask.max_x);
+    for y in 0..max_y {
+        for x in 0..max_x {
             let pos = Position::new(x, y);
-            if let Ok(placement) = validate_placement(game_state, pos) {
-                valid_placements.push(placement);
+            if bits.territory_overlap(&mask, pos, game_state.player_number()) == Some(1) {
+                valid_placements.push(Placement {
+                    position: pos,
+                    shape: shape.clone(),
+                    cells_added: mask.cells - 1,
+                    territory_touches: 1,
+                });
             }
         }
     }
@@ -135,31 +445,127 @@ pub fn find_all_valid_placements(game_state: &GameState) -> Vec<Placement> {
     valid_placements
 }
 
+/// Find valid placements for every distinct rotation of the current piece
+///
+/// With `allow_rotations` false this is `find_all_valid_placements`.
+/// Rotations that produce the same filled pattern (e.g. all four turns of
+/// a square) are searched only once.
+pub fn find_all_valid_placements_with_rotations(
+    game_state: &GameState,
+    allow_rotations: bool,
+) -> Vec<Placement> {
+    if !allow_rotations {
+        return find_all_valid_placements(game_state);
+    }
+
+    let piece = &game_state.current_piece;
+    let rotations = [piece.clone(), piece.rotate_90(), piece.rotate_180(), piece.rotate_270()];
+    let mut seen: Vec<Vec<(usize, usize)>> = Vec::new();
+    let mut placements = Vec::new();
+
+    for shape in rotations {
+        let key = shape.canonical_key();
+        if seen.contains(&key) {
+            continue;
+        }
+        seen.push(key);
+
+        let mut rotated_state = game_state.clone();
+        rotated_state.current_piece = shape;
+        placements.extend(find_all_valid_placements(&rotated_state));
+    }
+
+    placements
+}
+
+/// Order in which `find_all_valid_placements_ordered` returns placements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementOrdering {
+    /// Row-by-row scan order (same as `find_all_valid_placements`)
+    RowMajor,
+    /// Closest to the opponent's territory first (by BFS distance)
+    ByFrontierDistance,
+    /// Most cells added first
+    ByCellsAdded,
+}
+
+impl PlacementOrdering {
+    /// Stably sort `items` by the placement `placement_of` picks from each
+    ///
+    /// `RowMajor` leaves the slice as it is, so row-major input stays
+    /// row-major.
+    pub fn sort<T>(
+        self,
+        items: &mut [T],
+        game_state: &GameState,
+        placement_of: impl Fn(&T) -> &Placement,
+    ) {
+        match self {
+            PlacementOrdering::RowMajor => {}
+            PlacementOrdering::ByFrontierDistance => {
+                let sources = &game_state.opponent_territory().cells;
+                let distances = bfs_distance_map(game_state.grid(), sources);
+                items.sort_by_key(|item| {
+                    placement_of(item)
+                        .get_absolute_positions()
+                        .iter()
+                        .filter_map(|pos| distances[pos.y][pos.x])
+                        .min()
+                        .unwrap_or(usize::MAX)
+                });
+            }
+            PlacementOrdering::ByCellsAdded => {
+                items.sort_by_key(|item| Reverse(placement_of(item).cells_added));
+            }
+        }
+    }
+}
+
+/// Find all valid placements, returned in the requested order
+///
+/// Useful for pre-ordering candidates before searches that stop early.
+/// Sorting is stable, so ties keep their row-major order.
+pub fn find_all_valid_placements_ordered(
+    game_state: &GameState,
+    ordering: PlacementOrdering,
+) -> Vec<Placement> {
+    let mut placements = find_all_valid_placements(game_state);
+    ordering.sort(&mut placements, game_state, |p| p);
+    placements
+}
+
 /// Find valid placements that touch specific territory positions
 /// This is useful for greedy expansion
+///
+/// Only positions on the current player's territory border are searched:
+/// a piece overlapping an interior cell has nowhere empty to expand into.
+/// For each border cell, the piece is anchored with each of its filled
+/// cells on that cell. Results are unique by position.
 pub fn find_placements_touching_territory(
     game_state: &GameState,
     territory_positions: &[Position],
 ) -> Vec<Placement> {
+    let border: HashSet<Position> = game_state
+        .grid()
+        .territory_border(game_state.player_number())
+        .into_iter()
+        .collect();
+    let offsets = game_state.current_piece.get_filled_positions();
+
+    let mut seen = HashSet::new();
     let mut valid_placements = Vec::new();
 
-    // For each territory position, try placements that would touch it
-    for &territory_pos in territory_positions {
-        // Get neighbors of the territory position
-        let neighbors = get_neighbors(territory_pos, game_state.grid.width, game_state.grid.height);
-        
-        // For each neighbor, try placing the piece such that it touches the territory
-        for neighbor_pos in neighbors {
-            if let Ok(placement) = validate_placement(game_state, neighbor_pos) {
-                // Check if this placement actually touches the territory position we want
-                if placement.get_absolute_positions().contains(&territory_pos) {
-                    // Avoid duplicates
-                    if !valid_placements.iter().any(|p: &Placement| {
-                        p.position == placement.position
-                    }) {
-                        valid_placements.push(placement);
-                    }
-                }
+    for &territory_pos in territory_positions.iter().filter(|p| border.contains(p)) {
+        for offset in &offsets {
+            if offset.x > territory_pos.x || offset.y > territory_pos.y {
+                continue;
+            }
+            let anchor = Position::new(territory_pos.x - offset.x, territory_pos.y - offset.y);
+            if !seen.insert(anchor) {
+                continue;
+            }
+            if let Ok(placement) = validate_placement(game_state, anchor) {
+                valid_placements.push(placement);
             }
         }
     }
@@ -167,6 +573,95 @@ pub fn find_placements_touching_territory(
     valid_placements
 }
 
+/// Find valid placements within `max_distance` of the opponent
+///
+/// A placement qualifies if any of its cells is within `max_distance`
+/// (Manhattan) of an opponent cell. Used to narrow blocking candidates.
+pub fn find_placements_near_opponent(game_state: &GameState, max_distance: usize) -> Vec<Placement> {
+    filter_near_opponent(&find_all_valid_placements(game_state), game_state, max_distance)
+}
+
+/// Keep the placements within `max_distance` (Manhattan) of the opponent
+///
+/// Placements whose bounding box misses the opponent's bounding box grown
+/// by `max_distance` on every side cannot qualify and are rejected before
+/// the per-cell distance check.
+pub fn filter_near_opponent(
+    placements: &[Placement],
+    game_state: &GameState,
+    max_distance: usize,
+) -> Vec<Placement> {
+    let grid = &game_state.grid();
+    let opponent = game_state.get_opponent_positions();
+    let Some(reach) = expanded_bounds(&opponent, max_distance) else {
+        return Vec::new();
+    };
+    let distances = manhattan_distance_map(grid, &opponent);
+
+    placements
+        .iter()
+        .filter(|p| boxes_intersect(p.bounding_box(), reach))
+        .filter(|p| {
+            p.get_absolute_positions()
+                .iter()
+                .filter(|pos| grid.is_valid(**pos))
+                .any(|pos| distances[pos.y * grid.width + pos.x] <= max_distance)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Bounding box of `positions` grown by `margin` on every side
+///
+/// Returned as `(min_x, min_y, max_x, max_y)`, inclusive and clamped at 0
+/// (but not at the board edge). `None` for no positions.
+fn expanded_bounds(positions: &[Position], margin: usize) -> Option<(usize, usize, usize, usize)> {
+    let min_x = positions.iter().map(|p| p.x).min()?;
+    let min_y = positions.iter().map(|p| p.y).min()?;
+    let max_x = positions.iter().map(|p| p.x).max()?;
+    let max_y = positions.iter().map(|p| p.y).max()?;
+    Some((
+        min_x.saturating_sub(margin),
+        min_y.saturating_sub(margin),
+        max_x.saturating_add(margin),
+        max_y.saturating_add(margin),
+    ))
+}
+
+/// Whether a `(min_x, min_y, width, height)` box overlaps inclusive `bounds`
+fn boxes_intersect(
+    (x, y, width, height): (usize, usize, usize, usize),
+    (min_x, min_y, max_x, max_y): (usize, usize, usize, usize),
+) -> bool {
+    width > 0 && height > 0 && x <= max_x && y <= max_y && x + width > min_x && y + height > min_y
+}
+
+/// Manhattan distance from every cell to the nearest source, row-major
+///
+/// A BFS that ignores cell contents, so distances are plain Manhattan
+/// distances. Cells are `usize::MAX` when there are no sources.
+fn manhattan_distance_map(grid: &Grid, sources: &[Position]) -> Vec<usize> {
+    let mut distances = vec![usize::MAX; grid.width * grid.height];
+    let mut queue = VecDeque::new();
+    for &pos in sources {
+        distances[pos.y * grid.width + pos.x] = 0;
+        queue.push_back(pos);
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        let next = distances[pos.y * grid.width + pos.x] + 1;
+        for neighbor in get_neighbors(pos, grid.width, grid.height) {
+            let index = neighbor.y * grid.width + neighbor.x;
+            if distances[index] == usize::MAX {
+                distances[index] = next;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
 /// Get absolute grid positions from relative piece positions
 fn get_absolute_positions(base_pos: Position, shape: &Shape) -> Result<Vec<Position>, PlacementError> {
     shape
@@ -210,25 +705,16 @@ mod tests {
 
     /// Helper to create a test game state
     fn create_test_game_state() -> GameState {
-        use crate::game_state::{Grid, Shape};
-        
-        let grid_raw = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '@', '.', '.', '.'],
-            vec!['.', '.', '.', '$', '.'],
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-        ];
-        
         let piece_raw = vec![
             vec!['.', '#', '#'],
             vec!['#', '.', '.'],
         ];
         
-        let grid = Grid::from_chars(5, 5, grid_raw);
-        let shape = Shape::from_chars(3, 2, piece_raw);
-        
-        GameState::new(1, grid, shape)
+        GameState::builder_from_size(5, 5)
+            .with_territory(1, &[Position::new(1, 1)])
+            .with_territory(2, &[Position::new(3, 2)])
+            .with_piece(Shape::from_chars(3, 2, piece_raw))
+            .build()
     }
 
     #[test]
@@ -270,6 +756,32 @@ mod tests {
         assert_eq!(neighbors.len(), 4);
     }
 
+    #[test]
+    fn test_validate_placement_constrained() {
+        // A domino from our cell at (1, 1) can lie toward the opponent at
+        // (4, 1) or away from it
+        let game_state = GameState::builder_from_size(6, 3)
+            .with_territory(1, &[Position::new(1, 1)])
+            .with_territory(2, &[Position::new(4, 1)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let keep_off = |distance| PlacementConstraint { require_opponent_distance: Some(distance) };
+
+        let toward = Position::new(1, 1);
+        assert!(validate_placement_constrained(&game_state, toward, &PlacementConstraint::default()).is_ok());
+        assert!(validate_placement_constrained(&game_state, toward, &keep_off(2)).is_ok());
+        assert_eq!(
+            validate_placement_constrained(&game_state, toward, &keep_off(3)),
+            Err(PlacementError::TooCloseToOpponent)
+        );
+        assert!(validate_placement_constrained(&game_state, Position::new(0, 1), &keep_off(3)).is_ok());
+        // The game's own rules are still checked first
+        assert_eq!(
+            validate_placement_constrained(&game_state, Position::new(3, 1), &keep_off(1)),
+            Err(PlacementError::CollisionWithOpponent)
+        );
+    }
+
     #[test]
     fn test_placement_struct() {
         let placement = Placement {
@@ -320,11 +832,367 @@ mod tests {
         let empty_shape_raw = vec![vec!['.', '.'], vec!['.', '.']];
         let empty_shape = Shape::from_chars(2, 2, empty_shape_raw);
         
-        let grid_raw = vec![vec!['.'; 5]; 5];
-        let grid = Grid::from_chars(5, 5, grid_raw);
-        let game_state = GameState::new(1, grid, empty_shape);
+        let game_state = GameState::builder_from_size(5, 5)
+            .with_piece(empty_shape)
+            .build();
         
         let result = validate_placement(&game_state, Position::new(0, 0));
         assert_eq!(result, Err(PlacementError::EmptyShape));
     }
+
+    /// Game state with several valid placements around a single cell
+    fn create_ordering_game_state() -> GameState {
+        GameState::builder_from_size(6, 5)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_territory(2, &[Position::new(5, 4)])
+            .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]))
+            .build()
+    }
+
+    #[test]
+    fn test_ordered_row_major_matches_unordered() {
+        let game_state = create_ordering_game_state();
+        assert_eq!(
+            find_all_valid_placements_ordered(&game_state, PlacementOrdering::RowMajor),
+            find_all_valid_placements(&game_state)
+        );
+    }
+
+    #[test]
+    fn test_ordered_by_cells_added() {
+        let game_state = create_ordering_game_state();
+        let ordered = find_all_valid_placements_ordered(&game_state, PlacementOrdering::ByCellsAdded);
+
+        assert!(ordered.len() > 1);
+        let max_added = ordered.iter().map(|p| p.cells_added).max().unwrap();
+        assert_eq!(ordered[0].cells_added, max_added);
+        assert!(ordered.windows(2).all(|w| w[0].cells_added >= w[1].cells_added));
+    }
+
+    #[test]
+    fn test_ordered_by_frontier_distance() {
+        let game_state = create_ordering_game_state();
+        let ordered =
+            find_all_valid_placements_ordered(&game_state, PlacementOrdering::ByFrontierDistance);
+        let opponent = Position::new(5, 4);
+
+        let closest = |p: &Placement| {
+            p.get_absolute_positions()
+                .iter()
+                .map(|&pos| crate::utils::manhattan_distance(pos, opponent))
+                .min()
+                .unwrap()
+        };
+        assert_eq!(ordered.len(), find_all_valid_placements(&game_state).len());
+        assert!(closest(&ordered[0]) <= closest(ordered.last().unwrap()));
+    }
+
+    #[test]
+    fn test_validate_placement_quality() {
+        let game_state = create_ordering_game_state();
+
+        // L piece at (2, 2) covers (2, 2), (3, 2) and (2, 3)
+        let placement = validate_placement(&game_state, Position::new(2, 2)).unwrap();
+        let quality = placement.quality(&game_state);
+
+        assert_eq!(placement.cells_added, 2);
+        assert!((quality.territory_coverage_ratio - 1.0 / 3.0).abs() < 1e-6);
+        assert!((quality.expansion_efficiency - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(quality.edge_proximity, 1);
+        assert_eq!(quality.frontier_adjacency, 2);
+        assert_eq!(quality.growth_room, 7);
+    }
+
+    #[test]
+    fn test_validate_placement_quality_offset_contact() {
+        let game_state = create_ordering_game_state();
+
+        // L piece at (1, 2) covers (1, 2), (2, 2) and (1, 3); only (1, 2)
+        // borders the frontier cell (2, 2)
+        let quality = validate_placement(&game_state, Position::new(1, 2)).unwrap().quality(&game_state);
+
+        assert_eq!(quality.edge_proximity, 1);
+        assert_eq!(quality.frontier_adjacency, 1);
+    }
+
+    #[test]
+    fn test_bitboard_placements_match_cell_scan() {
+        let game_state = create_ordering_game_state();
+        assert_eq!(
+            find_all_valid_placements_bitboard(&game_state),
+            find_all_valid_placements(&game_state)
+        );
+
+        let game_state = create_test_game_state();
+        assert_eq!(
+            find_all_valid_placements_bitboard(&game_state),
+            find_all_valid_placements(&game_state)
+        );
+    }
+
+    #[test]
+    fn test_bitboard_placements_match_on_mid_game_board() {
+        // 20x20 board with a mid-game spread of territory for both players;
+        // `benchmark::benchmark_bitboard_placements` times the two searches
+        let mine: Vec<Position> = (0..20).flat_map(|y| (0..8).map(move |x| Position::new(x, y))).collect();
+        let theirs: Vec<Position> = (0..20).flat_map(|y| (13..20).map(move |x| Position::new(x, y))).collect();
+        let game_state = GameState::builder_from_size(20, 20)
+            .with_territory(1, &mine)
+            .with_territory(2, &theirs)
+            .with_piece(Shape::from_chars(3, 2, vec![vec!['#', '#', '#'], vec!['.', '#', '.']]))
+            .build();
+
+        let expected = find_all_valid_placements(&game_state);
+        assert!(!expected.is_empty());
+        assert_eq!(find_all_valid_placements_bitboard(&game_state), expected);
+    }
+
+    #[test]
+    fn test_placements_with_rotations() {
+        let game_state = create_ordering_game_state();
+        let fixed = find_all_valid_placements(&game_state);
+
+        assert_eq!(find_all_valid_placements_with_rotations(&game_state, false), fixed);
+
+        // Each of the four L orientations gives 3 contacts with the single cell
+        let rotated = find_all_valid_placements_with_rotations(&game_state, true);
+        assert_eq!(fixed.len(), 3);
+        assert_eq!(rotated.len(), 12);
+    }
+
+    #[test]
+    fn test_placements_with_rotations_dedups_symmetric_pieces() {
+        let game_state = GameState::builder_from_size(6, 5)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '#']]))
+            .build();
+
+        assert_eq!(
+            find_all_valid_placements_with_rotations(&game_state, true),
+            find_all_valid_placements(&game_state)
+        );
+    }
+
+    #[test]
+    fn test_touching_territory_matches_full_scan() {
+        let block: Vec<Position> = (1..4)
+            .flat_map(|y| (1..4).map(move |x| Position::new(x, y)))
+            .collect();
+        let game_state = GameState::builder_from_size(6, 6)
+            .with_territory(1, &block)
+            .with_territory(2, &[Position::new(5, 5)])
+            .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]))
+            .build();
+
+        let mut touching =
+            find_placements_touching_territory(&game_state, &game_state.get_my_positions());
+        let mut expected = find_all_valid_placements(&game_state);
+        touching.sort_by_key(|p| (p.position.y, p.position.x));
+        expected.sort_by_key(|p| (p.position.y, p.position.x));
+
+        assert_eq!(touching, expected);
+
+        // The interior cell (2, 2) alone yields nothing
+        assert!(find_placements_touching_territory(&game_state, &[Position::new(2, 2)]).is_empty());
+    }
+
+    #[test]
+    fn test_find_placements_near_opponent() {
+        let game_state = GameState::builder_from_size(10, 3)
+            .with_territory(1, &[Position::new(1, 1)])
+            .with_territory(2, &[Position::new(8, 1)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+
+        let all = find_all_valid_placements(&game_state);
+        let near = find_placements_near_opponent(&game_state, 6);
+
+        // Only the placement covering (2, 1) comes within 6 of (8, 1)
+        assert_eq!(all.len(), 2);
+        assert_eq!(near.len(), 1);
+        assert_eq!(near[0].position, Position::new(1, 1));
+        assert_eq!(find_placements_near_opponent(&game_state, 100).len(), all.len());
+        assert!(find_placements_near_opponent(&game_state, 5).is_empty());
+    }
+
+    #[test]
+    fn test_placement_centroid() {
+        let corner = Shape::from_chars(3, 3, vec![
+            vec!['.', '.', '.'],
+            vec!['.', '#', '#'],
+            vec!['.', '#', '.'],
+        ]);
+        let placement = Placement {
+            position: Position::new(4, 2),
+            shape: corner,
+            cells_added: 2,
+            territory_touches: 1,
+        };
+        let empty = Placement {
+            shape: Shape::from_chars(2, 1, vec![vec!['.', '.']]),
+            ..placement.clone()
+        };
+
+        // Cells (5, 3), (6, 3) and (5, 4)
+        let (x, y) = placement.centroid();
+        assert!((x - 16.0 / 3.0).abs() < 1e-5 && (y - 10.0 / 3.0).abs() < 1e-5);
+        assert_eq!(empty.centroid(), (4.0, 2.0));
+        assert_eq!(empty.distance_to_point(7.0, 6.0), 5.0);
+    }
+
+    #[test]
+    fn test_placement_bounding_box() {
+        let framed = Shape::from_chars(3, 3, vec![
+            vec!['.', '.', '.'],
+            vec!['.', '#', '#'],
+            vec!['.', '#', '.'],
+        ]);
+        let placement = Placement {
+            position: Position::new(4, 2),
+            shape: framed,
+            cells_added: 2,
+            territory_touches: 1,
+        };
+        let empty = Placement {
+            shape: Shape::from_chars(2, 1, vec![vec!['.', '.']]),
+            ..placement.clone()
+        };
+
+        assert_eq!(placement.bounding_box(), (5, 3, 2, 2));
+        assert_eq!(empty.bounding_box(), (4, 2, 0, 0));
+        assert!(boxes_intersect(placement.bounding_box(), (6, 4, 9, 9)));
+        assert!(!boxes_intersect(placement.bounding_box(), (7, 0, 9, 9)));
+        assert!(!boxes_intersect(empty.bounding_box(), (0, 0, 9, 9)));
+        assert_eq!(expanded_bounds(&[Position::new(1, 5), Position::new(3, 2)], 2), Some((0, 0, 5, 7)));
+        assert_eq!(expanded_bounds(&[], 2), None);
+    }
+
+    #[test]
+    fn test_valid_placements_iter_matches_collected() {
+        let game_state = create_ordering_game_state();
+        let lazy: Vec<Placement> = valid_placements_iter(&game_state).collect();
+
+        assert_eq!(lazy, find_all_valid_placements(&game_state));
+        assert_eq!(valid_placements_iter(&game_state).next(), lazy.first().cloned());
+    }
+
+    #[test]
+    fn test_simulate_territory_gain_counts_cut_off_space() {
+        // 1-row corridor: a 2-cell bar from (1, 0) claims (2, 0) and moves the
+        // midpoint toward the opponent
+        let game_state = GameState::builder_from_size(7, 1)
+            .with_territory(1, &[Position::new(1, 0)])
+            .with_territory(2, &[Position::new(5, 0)])
+            .with_piece(Shape::from_chars(2, 1, vec![vec!['#', '#']]))
+            .build();
+        let forward = validate_placement(&game_state, Position::new(1, 0)).unwrap();
+        let backward = validate_placement(&game_state, Position::new(0, 0)).unwrap();
+
+        // Before: owns (1,0), reaches (0,0) and (2,0) first; (3,0) is a tie
+        // After forward: owns (1,0),(2,0), reaches (0,0),(3,0) first
+        assert_eq!(forward.cells_added, 1);
+        assert_eq!(forward.simulate_territory_gain(&game_state), 1);
+        assert_eq!(backward.cells_added, 1);
+        assert_eq!(backward.simulate_territory_gain(&game_state), 0);
+    }
+
+    #[test]
+    fn test_deduplicate_symmetric_placements() {
+        let game_state = GameState::builder_from_size(6, 6)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_piece(Shape::from_chars(3, 2, vec![vec!['#', '#', '.'], vec!['.', '#', '#']]))
+            .build();
+        let piece = game_state.current_piece.clone();
+        let mut turned = game_state.clone();
+        turned.current_piece = piece.rotate_180();
+
+        // The S piece and its half-turn cover the same cells from each anchor
+        let mut both = find_all_valid_placements(&game_state);
+        let single = both.len();
+        both.extend(find_all_valid_placements(&turned));
+
+        let deduped = Placement::deduplicate_symmetric(both.clone(), piece.symmetry_class());
+        assert_eq!(piece.symmetry_class(), Symmetry::Rotational180);
+        assert_eq!(deduped.len(), single);
+        assert_eq!(Placement::deduplicate_symmetric(both.clone(), Symmetry::None), both);
+    }
+
+    #[test]
+    fn test_coverage_overlap_and_deduplicate_placements() {
+        let placement = |x, y, rows: Vec<Vec<char>>| Placement {
+            position: Position::new(x, y),
+            shape: Shape::from_chars(rows[0].len(), rows.len(), rows),
+            cells_added: 1,
+            territory_touches: 1,
+        };
+        // The same domino on (2, 1)-(3, 1), once with a padding row on top
+        let bare = placement(2, 1, vec![vec!['#', '#']]);
+        let padded = placement(2, 0, vec![vec!['.', '.'], vec!['#', '#']]);
+        let shifted = placement(3, 1, vec![vec!['#', '#']]);
+
+        assert_eq!(bare.coverage_overlap(&padded), 2);
+        assert_eq!(bare.coverage_overlap(&shifted), 1);
+        assert_eq!(shifted.coverage_overlap(&placement(0, 0, vec![vec!['#']])), 0);
+
+        let deduped = deduplicate_placements(vec![bare.clone(), shifted.clone(), padded]);
+        assert_eq!(deduped, vec![bare, shifted]);
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        prop_compose! {
+            /// Board of up to 8x8 with a random mix of empty, own and opponent cells
+            fn arb_grid()(width in 1usize..=8, height in 1usize..=8)
+                (cells in prop::collection::vec(prop::sample::select(vec!['.', '.', '.', '@', '$']), width * height),
+                 width in Just(width), height in Just(height)) -> Grid {
+                let raw = cells.chunks(width).map(|row| row.to_vec()).collect();
+                Grid::from_chars(width, height, raw)
+            }
+        }
+
+        prop_compose! {
+            /// Shape of up to 4x4 with at least one filled cell
+            fn arb_shape()(width in 1usize..=4, height in 1usize..=4)
+                (cells in prop::collection::vec(any::<bool>(), width * height),
+                 width in Just(width), height in Just(height)) -> Shape {
+                let mut raw: Vec<Vec<char>> = cells
+                    .chunks(width)
+                    .map(|row| row.iter().map(|&filled| if filled { '*' } else { '.' }).collect())
+                    .collect();
+                raw[0][0] = '*';
+                Shape::from_chars(width, height, raw)
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn valid_placements_touch_once_and_stay_in_bounds(grid in arb_grid(), shape in arb_shape()) {
+                let game_state = GameState::new(1, grid, shape);
+                for placement in find_all_valid_placements(&game_state) {
+                    prop_assert_eq!(placement.territory_touches, 1);
+                    for pos in placement.get_absolute_positions() {
+                        prop_assert!(pos.x < game_state.grid().width && pos.y < game_state.grid().height);
+                    }
+                }
+            }
+
+            #[test]
+            fn validate_placement_is_pure(grid in arb_grid(), shape in arb_shape(), x in 0usize..8, y in 0usize..8) {
+                let game_state = GameState::new(1, grid, shape);
+                let first = validate_placement(&game_state, Position::new(x, y));
+                let second = validate_placement(&game_state, Position::new(x, y));
+                prop_assert_eq!(first, second);
+            }
+
+            #[test]
+            fn cells_added_is_area_minus_contact(grid in arb_grid(), shape in arb_shape()) {
+                let game_state = GameState::new(1, grid, shape);
+                for placement in find_all_valid_placements(&game_state) {
+                    prop_assert_eq!(placement.cells_added + 1, placement.shape.area());
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,196 @@
+/// Replay analysis module
+///
+/// Works over recorded game rounds (the board a player saw and the move
+/// they made) to compute per-turn territory changes and to check how often
+/// each AI strategy would have made the same move.
+
+use crate::ai::{select_move, AIStrategy};
+use crate::game_state::{GameState, Position};
+use crate::output::Move;
+use crate::placement::{find_all_valid_placements, validate_placement};
+
+/// One recorded turn: the state the player saw and the move they made
+#[derive(Debug, Clone)]
+pub struct Round {
+    pub game_state: GameState,
+    pub actual_move: Move,
+}
+
+impl Round {
+    /// Create a new round
+    pub fn new(game_state: GameState, actual_move: Move) -> Self {
+        Round {
+            game_state,
+            actual_move,
+        }
+    }
+}
+
+/// Territory change caused by one round's move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerritoryDelta {
+    pub turn: usize,
+    pub player: u8,
+    /// Empty cells claimed by the move (0 if the move was invalid)
+    pub cells_added: usize,
+    pub territory_before: usize,
+    pub territory_after: usize,
+}
+
+/// Retrospective analysis over a sequence of rounds
+#[derive(Debug, Clone, Default)]
+pub struct ReplayAnalyzer {
+    pub rounds: Vec<Round>,
+}
+
+impl ReplayAnalyzer {
+    /// Create an analyzer over the given rounds
+    pub fn new(rounds: Vec<Round>) -> Self {
+        ReplayAnalyzer { rounds }
+    }
+
+    /// Territory gained by the moving player on each turn
+    pub fn territory_deltas(&self) -> Vec<TerritoryDelta> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .map(|(turn, round)| {
+                let state = &round.game_state;
+                let pos = Position::new(round.actual_move.x, round.actual_move.y);
+                let cells_added = validate_placement(state, pos)
+                    .map(|placement| placement.cells_added)
+                    .unwrap_or(0);
+                let territory_before = state.get_my_territory_size();
+
+                TerritoryDelta {
+                    turn,
+                    player: state.player_number(),
+                    cells_added,
+                    territory_before,
+                    territory_after: territory_before + cells_added,
+                }
+            })
+            .collect()
+    }
+
+    /// Agreement of `strategy` with the actual move on each turn
+    ///
+    /// Each entry is 1.0 if the strategy would have chosen the actual move
+    /// (or, with no valid placement, would also have had nothing to play),
+    /// and 0.0 otherwise.
+    pub fn score_with_strategy(&self, strategy: AIStrategy) -> Vec<f32> {
+        self.rounds
+            .iter()
+            .map(|round| {
+                let placements = find_all_valid_placements(&round.game_state);
+                let preferred = select_move(&placements, &round.game_state, strategy)
+                    .map(|p| Move::new(p.position.x, p.position.y));
+                let agreed = match preferred {
+                    Some(m) => m == round.actual_move,
+                    None => placements.is_empty(),
+                };
+                if agreed { 1.0 } else { 0.0 }
+            })
+            .collect()
+    }
+
+    /// Strategy that most often agreed with the actual moves
+    ///
+    /// Returns the strategy and its agreement rate in `[0, 1]`; ties keep
+    /// the first strategy in `AIStrategy::all()` order.
+    pub fn best_strategy_match(&self) -> (AIStrategy, f64) {
+        let mut best = (AIStrategy::all()[0], f64::NEG_INFINITY);
+        for strategy in AIStrategy::all() {
+            let rate = self.agreement_rate(strategy);
+            if rate > best.1 {
+                best = (strategy, rate);
+            }
+        }
+        best
+    }
+
+    /// Fraction of rounds where `strategy` agreed with the actual move
+    fn agreement_rate(&self, strategy: AIStrategy) -> f64 {
+        if self.rounds.is_empty() {
+            return 0.0;
+        }
+        let agreed: f32 = self.score_with_strategy(strategy).iter().sum();
+        agreed as f64 / self.rounds.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::Shape;
+
+    /// Single-cell territory at (2, 2) with an L piece on a 6x5 board
+    fn create_replay_game_state() -> GameState {
+        GameState::builder_from_size(6, 5)
+            .with_territory(1, &[Position::new(2, 2)])
+            .with_territory(2, &[Position::new(5, 4)])
+            .with_piece(Shape::from_chars(2, 2, vec![vec!['#', '#'], vec!['#', '.']]))
+            .build()
+    }
+
+    fn preferred_move(state: &GameState, strategy: AIStrategy) -> Move {
+        let placements = find_all_valid_placements(state);
+        let placement = select_move(&placements, state, strategy).unwrap();
+        Move::new(placement.position.x, placement.position.y)
+    }
+
+    #[test]
+    fn test_territory_deltas() {
+        let state = create_replay_game_state();
+        let analyzer = ReplayAnalyzer::new(vec![
+            Round::new(state.clone(), Move::new(2, 2)),
+            Round::new(state, Move::new(0, 0)),
+        ]);
+
+        let deltas = analyzer.territory_deltas();
+
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].cells_added, 2);
+        assert_eq!(deltas[0].territory_before, 1);
+        assert_eq!(deltas[0].territory_after, 3);
+        // (0, 0) does not touch the territory
+        assert_eq!(deltas[1].cells_added, 0);
+        assert_eq!(deltas[1].turn, 1);
+    }
+
+    #[test]
+    fn test_score_with_strategy_agreement() {
+        let state = create_replay_game_state();
+        let greedy = preferred_move(&state, AIStrategy::GreedyExpansion);
+        let other = find_all_valid_placements(&state)
+            .into_iter()
+            .map(|p| Move::new(p.position.x, p.position.y))
+            .find(|&m| m != greedy)
+            .unwrap();
+
+        let analyzer = ReplayAnalyzer::new(vec![
+            Round::new(state.clone(), greedy),
+            Round::new(state, other),
+        ]);
+
+        assert_eq!(analyzer.score_with_strategy(AIStrategy::GreedyExpansion), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_best_strategy_match() {
+        let state = create_replay_game_state();
+        let actual = preferred_move(&state, AIStrategy::AdvancedBalanced);
+        let analyzer = ReplayAnalyzer::new(vec![Round::new(state, actual); 2]);
+
+        let (strategy, rate) = analyzer.best_strategy_match();
+
+        assert_eq!(rate, 1.0);
+        assert_eq!(analyzer.score_with_strategy(strategy), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_best_strategy_match_empty() {
+        let (_, rate) = ReplayAnalyzer::default().best_strategy_match();
+        assert_eq!(rate, 0.0);
+    }
+}
@@ -0,0 +1,478 @@
+/// Self-play simulation module
+///
+/// Runs complete games between two AI strategies on a generated board,
+/// alternating turns and feeding each player randomly generated pieces.
+
+use crate::ai::{select_move, AIStrategy};
+use crate::game_state::{GameState, Grid, Position, Shape, TurnHistory};
+use crate::placement::{find_all_valid_placements, Placement};
+
+/// Safety cap on the number of turns in a simulated game
+pub const DEFAULT_MAX_TURNS: usize = 10_000;
+
+/// Outcome of a simulated game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimResult {
+    /// Winning player (1 or 2), or 0 for a tie
+    pub winner: u8,
+    /// Number of turns played by both players
+    pub turn_count: u32,
+    /// Final territory of (player 1, player 2)
+    pub final_territory: (usize, usize),
+}
+
+/// Deterministic random piece generator
+///
+/// Uses a small xorshift generator so simulations are reproducible
+/// from a seed.
+#[derive(Debug, Clone)]
+pub struct PieceGenerator {
+    state: u64,
+}
+
+impl PieceGenerator {
+    /// Create a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        // xorshift must never hold a zero state
+        PieceGenerator {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Next raw 64-bit random value
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Random value in `0..bound`
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound.max(1) as u64) as usize
+    }
+
+    /// Generate a random connected piece of 2 to 5 cells
+    ///
+    /// The piece grows one orthogonal neighbor at a time, like the
+    /// polyomino pieces the game engine hands out.
+    pub fn next_piece(&mut self) -> Shape {
+        let size = 2 + self.next_below(4);
+        let mut cells: Vec<(i32, i32)> = vec![(0, 0)];
+
+        while cells.len() < size {
+            let (x, y) = cells[self.next_below(cells.len())];
+            let (dx, dy) = [(1, 0), (-1, 0), (0, 1), (0, -1)][self.next_below(4)];
+            let candidate = (x + dx, y + dy);
+            if !cells.contains(&candidate) {
+                cells.push(candidate);
+            }
+        }
+
+        let min_x = cells.iter().map(|c| c.0).min().unwrap_or(0);
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        let width = (cells.iter().map(|c| c.0).max().unwrap_or(0) - min_x + 1) as usize;
+        let height = (cells.iter().map(|c| c.1).max().unwrap_or(0) - min_y + 1) as usize;
+
+        let mut raw = vec![vec!['.'; width]; height];
+        for (x, y) in cells {
+            raw[(y - min_y) as usize][(x - min_x) as usize] = '*';
+        }
+
+        Shape::from_chars(width, height, raw)
+    }
+}
+
+/// Board size, starting cells, and piece source for a simulated game
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    pub width: usize,
+    pub height: usize,
+    pub p1_start: Position,
+    pub p2_start: Position,
+    pub piece_generator: PieceGenerator,
+}
+
+impl GameConfig {
+    /// Players start mid-height, a quarter of the way in from each side
+    pub fn classic(width: usize, height: usize) -> Self {
+        GameConfig {
+            width,
+            height,
+            p1_start: Position::new(width / 4, height / 2),
+            p2_start: Position::new(3 * width / 4, height / 2),
+            piece_generator: PieceGenerator::new(0),
+        }
+    }
+
+    /// Player 1 starts somewhere in the left half, player 2 in the right half
+    pub fn random_start(width: usize, height: usize, seed: u64) -> Self {
+        let mut piece_generator = PieceGenerator::new(seed);
+        let half = width / 2;
+        let p1_start = Position::new(
+            piece_generator.next_below(half),
+            piece_generator.next_below(height),
+        );
+        let p2_start = Position::new(
+            half + piece_generator.next_below(width - half),
+            piece_generator.next_below(height),
+        );
+
+        GameConfig {
+            width,
+            height,
+            p1_start,
+            p2_start,
+            piece_generator,
+        }
+    }
+}
+
+/// Move choice for a simulated turn: `(player, placements, game_state)`
+pub type MoveSelector<'a> = dyn FnMut(u8, &[Placement], &GameState) -> Option<Placement> + 'a;
+
+/// A self-play game between two strategies
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub player1: AIStrategy,
+    pub player2: AIStrategy,
+    pub grid: Grid,
+    pub pieces: PieceGenerator,
+    pub max_turns: usize,
+    active: [bool; 2],
+    current_player: u8,
+    turn_count: usize,
+    history: Vec<TurnHistory>,
+}
+
+impl Simulation {
+    /// Create a simulation on a 20 x 15 board with fixed starting cells
+    pub fn new(player1: AIStrategy, player2: AIStrategy) -> Self {
+        Self::with_board(player1, player2, 20, 15, 0)
+    }
+
+    /// Create a simulation on a `width` x `height` board with fixed starting cells
+    ///
+    /// Player 1 starts near the top-left corner, player 2 near the bottom-right.
+    pub fn with_board(
+        player1: AIStrategy,
+        player2: AIStrategy,
+        width: usize,
+        height: usize,
+        seed: u64,
+    ) -> Self {
+        let config = GameConfig {
+            width,
+            height,
+            p1_start: Position::new(width / 4, height / 4),
+            p2_start: Position::new(width - 1 - width / 4, height - 1 - height / 4),
+            piece_generator: PieceGenerator::new(seed),
+        };
+        Self::new_with_config(player1, player2, config)
+    }
+
+    /// Create a simulation from an explicit game configuration
+    pub fn new_with_config(player1: AIStrategy, player2: AIStrategy, config: GameConfig) -> Self {
+        let grid = GameState::builder_from_size(config.width, config.height)
+            .with_territory(1, &[config.p1_start])
+            .with_territory(2, &[config.p2_start])
+            .build()
+            .into_grid();
+
+        Self::with_grid(player1, player2, grid, config.piece_generator)
+    }
+
+    /// Create a simulation starting from an arbitrary board
+    ///
+    /// Useful with `board_variants` to play the same start in several
+    /// orientations.
+    pub fn with_grid(player1: AIStrategy, player2: AIStrategy, grid: Grid, pieces: PieceGenerator) -> Self {
+        Simulation {
+            player1,
+            player2,
+            grid,
+            pieces,
+            max_turns: DEFAULT_MAX_TURNS,
+            active: [true, true],
+            current_player: 1,
+            turn_count: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Number of turns played so far by both players
+    pub fn turn_count(&self) -> usize {
+        self.turn_count
+    }
+
+    /// Every turn played so far, oldest first
+    pub fn history(&self) -> &[TurnHistory] {
+        &self.history
+    }
+
+    /// Whether the game has ended
+    pub fn is_finished(&self) -> bool {
+        !(self.active[0] || self.active[1]) || self.turn_count >= self.max_turns
+    }
+
+    /// Play the next turn, returning false once the game is over
+    ///
+    /// A player who cannot place their piece is out; the other keeps
+    /// playing until they are stuck too or the turn cap is reached.
+    pub fn step(&mut self) -> bool {
+        let (player1, player2) = (self.player1, self.player2);
+        self.step_with(&mut |player, placements, game_state| {
+            let strategy = if player == 1 { player1 } else { player2 };
+            select_move(placements, game_state, strategy)
+        })
+    }
+
+    /// `step` with moves chosen by `select` instead of the strategies
+    ///
+    /// `select` receives the player to move, their valid placements and
+    /// the game state they see.
+    pub fn step_with(&mut self, select: &mut MoveSelector<'_>) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        let player = self.current_player;
+        let index = (player - 1) as usize;
+        if !self.active[index] {
+            self.current_player = GameState::opponent_of(player);
+            return self.step_with(select);
+        }
+
+        let piece = self.pieces.next_piece();
+        if !self.play_turn(player, piece, select) {
+            self.active[index] = false;
+        }
+        self.turn_count += 1;
+        self.current_player = GameState::opponent_of(player);
+        true
+    }
+
+    /// Play the game to completion
+    pub fn run(&mut self) -> SimResult {
+        while self.step() {}
+        self.result()
+    }
+
+    /// Play the game to completion with moves chosen by `select`
+    pub fn run_with(&mut self, select: &mut MoveSelector<'_>) -> SimResult {
+        while self.step_with(select) {}
+        self.result()
+    }
+
+    /// Outcome of the game as it stands
+    fn result(&self) -> SimResult {
+        let final_territory = (self.grid.count_territory(1), self.grid.count_territory(2));
+        let winner = match final_territory.0.cmp(&final_territory.1) {
+            std::cmp::Ordering::Greater => 1,
+            std::cmp::Ordering::Less => 2,
+            std::cmp::Ordering::Equal => 0,
+        };
+
+        SimResult {
+            winner,
+            turn_count: self.turn_count as u32,
+            final_territory,
+        }
+    }
+
+    /// Play a single turn, returning false if the player had no valid placement
+    ///
+    /// The turn is added to the history either way.
+    fn play_turn(&mut self, player: u8, piece: Shape, select: &mut MoveSelector<'_>) -> bool {
+        let mut game_state =
+            GameState::new(player, self.grid.clone(), piece).with_history(self.history.clone());
+        game_state.turn_number = self.turn_count;
+        let placements = find_all_valid_placements(&game_state);
+
+        let placement = select(player, &placements, &game_state);
+        if let Some(placement) = &placement {
+            self.grid.apply_placement(placement, player);
+        }
+        let placed = placement.is_some();
+        self.history
+            .push(TurnHistory::after_turn(self.turn_count as u32, player, &self.grid, placement));
+        placed
+    }
+}
+
+/// A starting board and its mirror images and quarter turn
+///
+/// Returns `grid`, `grid.flip_horizontal()`, `grid.flip_vertical()` and
+/// `grid.rotate_90()`, in that order. The players keep their own cells, so
+/// each variant is the same opening seen from a different corner.
+pub fn board_variants(grid: &Grid) -> Vec<Grid> {
+    vec![grid.clone(), grid.flip_horizontal(), grid.flip_vertical(), grid.rotate_90()]
+}
+
+/// Play one game between two strategies and return the outcome
+///
+/// Starting cells are as in `Simulation::with_board`; `seed` fixes the
+/// piece sequence, so the same arguments always give the same result.
+pub fn simulate_game(
+    player1_strategy: AIStrategy,
+    player2_strategy: AIStrategy,
+    board_width: usize,
+    board_height: usize,
+    seed: u64,
+) -> SimResult {
+    Simulation::with_board(player1_strategy, player2_strategy, board_width, board_height, seed).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piece_generator_deterministic() {
+        let mut a = PieceGenerator::new(42);
+        let mut b = PieceGenerator::new(42);
+
+        for _ in 0..20 {
+            assert_eq!(a.next_piece(), b.next_piece());
+        }
+    }
+
+    #[test]
+    fn test_piece_generator_piece_sizes() {
+        let mut generator = PieceGenerator::new(7);
+
+        for _ in 0..100 {
+            let piece = generator.next_piece();
+            let filled = piece.get_filled_positions().len();
+            assert!((2..=5).contains(&filled));
+            assert!(piece.width <= 5 && piece.height <= 5);
+        }
+    }
+
+    #[test]
+    fn test_simulation_runs_to_completion() {
+        let mut sim = Simulation::with_board(
+            AIStrategy::GreedyExpansion,
+            AIStrategy::Balanced,
+            10,
+            8,
+            1,
+        );
+        let result = sim.run();
+
+        assert!(result.turn_count > 0);
+        assert!((result.turn_count as usize) < DEFAULT_MAX_TURNS);
+        assert!(result.final_territory.0 > 1);
+        assert!(result.final_territory.1 > 1);
+        assert!(result.winner <= 2);
+    }
+
+    #[test]
+    fn test_classic_config_positions() {
+        let config = GameConfig::classic(20, 15);
+        assert_eq!(config.p1_start, Position::new(5, 7));
+        assert_eq!(config.p2_start, Position::new(15, 7));
+    }
+
+    #[test]
+    fn test_random_start_opposing_halves() {
+        for seed in 0..20 {
+            let config = GameConfig::random_start(20, 15, seed);
+            assert!(config.p1_start.x < 10);
+            assert!(config.p2_start.x >= 10 && config.p2_start.x < 20);
+            assert!(config.p1_start.y < 15 && config.p2_start.y < 15);
+        }
+    }
+
+    #[test]
+    fn test_classic_simulation_terminates() {
+        let mut sim = Simulation::new_with_config(
+            AIStrategy::GreedyExpansion,
+            AIStrategy::Balanced,
+            GameConfig::classic(20, 15),
+        );
+        let result = sim.run();
+
+        assert!(result.turn_count as usize <= 10_000);
+        assert!(sim.is_finished());
+    }
+
+    #[test]
+    fn test_classic_simulation_both_players_grow() {
+        let mut sim = Simulation::new_with_config(
+            AIStrategy::GreedyExpansion,
+            AIStrategy::Balanced,
+            GameConfig::classic(20, 15),
+        );
+
+        // Ten turns for each player
+        while sim.turn_count() < 20 && sim.step() {}
+
+        // Each player gained at least five cells on top of its starting cell
+        assert!(sim.grid.count_territory(1) > 5);
+        assert!(sim.grid.count_territory(2) > 5);
+    }
+
+    #[test]
+    fn test_simulation_records_history() {
+        let mut sim = Simulation::with_board(AIStrategy::GreedyExpansion, AIStrategy::Balanced, 10, 8, 1);
+        let mut seen = Vec::new();
+        let result = sim.run_with(&mut |player, placements, game_state| {
+            seen.push(game_state.history().len());
+            select_move(placements, game_state, if player == 1 { AIStrategy::GreedyExpansion } else { AIStrategy::Balanced })
+        });
+
+        let history = sim.history();
+        assert_eq!(history.len(), result.turn_count as usize);
+        assert_eq!(seen, (0..history.len()).collect::<Vec<_>>());
+        assert_eq!(history[0].player, 1);
+        assert_eq!(history[1].player, 2);
+        let last = history.last().unwrap();
+        assert_eq!((last.territory[0], last.territory[1]), result.final_territory);
+    }
+
+    #[test]
+    fn test_simulate_game_is_reproducible() {
+        let result = simulate_game(AIStrategy::GreedyExpansion, AIStrategy::Balanced, 10, 8, 3);
+
+        assert_eq!(
+            result,
+            simulate_game(AIStrategy::GreedyExpansion, AIStrategy::Balanced, 10, 8, 3)
+        );
+        assert_eq!(
+            result,
+            Simulation::with_board(AIStrategy::GreedyExpansion, AIStrategy::Balanced, 10, 8, 3).run()
+        );
+    }
+
+    #[test]
+    fn test_board_variants_play_from_every_orientation() {
+        let base = Simulation::with_board(AIStrategy::Default, AIStrategy::Default, 10, 8, 0).grid;
+        let variants = board_variants(&base);
+
+        assert_eq!(variants.len(), 4);
+        assert_eq!(variants[0], base);
+        assert_eq!((variants[3].width, variants[3].height), (8, 10));
+        for (i, variant) in variants.iter().enumerate() {
+            assert!(variants[..i].iter().all(|earlier| earlier != variant));
+            let pieces = PieceGenerator::new(1);
+            let result = Simulation::with_grid(AIStrategy::GreedyExpansion, AIStrategy::Balanced, variant.clone(), pieces).run();
+            assert!(result.final_territory.0 > 1 && result.final_territory.1 > 1);
+        }
+    }
+
+    #[test]
+    fn test_run_with_custom_selector() {
+        let expected = simulate_game(AIStrategy::GreedyExpansion, AIStrategy::Balanced, 10, 8, 5);
+        let mut sim = Simulation::with_board(AIStrategy::Default, AIStrategy::Default, 10, 8, 5);
+
+        let result = sim.run_with(&mut |player, placements, game_state| {
+            let strategy = if player == 1 { AIStrategy::GreedyExpansion } else { AIStrategy::Balanced };
+            select_move(placements, game_state, strategy)
+        });
+
+        assert_eq!(result, expected);
+    }
+}
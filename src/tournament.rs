@@ -0,0 +1,212 @@
+/// Round-robin tournament between AI strategies
+///
+/// Plays every pair of strategies against each other using the self-play
+/// simulator and collects a win/tie/loss matrix.
+
+use crate::ai::AIStrategy;
+use crate::sim::Simulation;
+
+/// Head-to-head results of a round-robin tournament
+///
+/// `wins[i][j]` is the number of games strategy `i` won against strategy `j`;
+/// `ties` and `losses` are indexed the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TournamentResults {
+    pub strategies: Vec<String>,
+    pub wins: Vec<Vec<usize>>,
+    pub ties: Vec<Vec<usize>>,
+    pub losses: Vec<Vec<usize>>,
+}
+
+impl TournamentResults {
+    /// Create an empty result matrix for the given strategy names
+    pub fn new(strategies: Vec<String>) -> Self {
+        let n = strategies.len();
+        TournamentResults {
+            strategies,
+            wins: vec![vec![0; n]; n],
+            ties: vec![vec![0; n]; n],
+            losses: vec![vec![0; n]; n],
+        }
+    }
+
+    /// Record the outcome of one game between strategies `i` and `j`
+    ///
+    /// `winner` is `Some(i)`, `Some(j)`, or `None` for a tie.
+    pub fn record(&mut self, i: usize, j: usize, winner: Option<usize>) {
+        match winner {
+            Some(w) if w == i => {
+                self.wins[i][j] += 1;
+                self.losses[j][i] += 1;
+            }
+            Some(_) => {
+                self.wins[j][i] += 1;
+                self.losses[i][j] += 1;
+            }
+            None => {
+                self.ties[i][j] += 1;
+                self.ties[j][i] += 1;
+            }
+        }
+    }
+
+    /// Total wins for strategy `i` across all opponents
+    pub fn total_wins(&self, i: usize) -> usize {
+        self.wins[i].iter().sum()
+    }
+
+    /// Name of the strategy with the most total wins (first on ties)
+    pub fn best_strategy(&self) -> String {
+        (0..self.strategies.len())
+            .rev()
+            .max_by_key(|&i| self.total_wins(i))
+            .map(|i| self.strategies[i].clone())
+            .unwrap_or_default()
+    }
+
+    /// Render the matrix as a markdown table of `W-T-L` cells (row vs column)
+    pub fn to_markdown_table(&self) -> String {
+        let mut table = String::from("| vs |");
+        for name in &self.strategies {
+            table.push_str(&format!(" {} |", name));
+        }
+        table.push_str(" Total wins |\n|---|");
+        for _ in &self.strategies {
+            table.push_str("---|");
+        }
+        table.push_str("---|\n");
+
+        for (i, name) in self.strategies.iter().enumerate() {
+            table.push_str(&format!("| {} |", name));
+            for j in 0..self.strategies.len() {
+                if i == j {
+                    table.push_str(" - |");
+                } else {
+                    table.push_str(&format!(
+                        " {}-{}-{} |",
+                        self.wins[i][j], self.ties[i][j], self.losses[i][j]
+                    ));
+                }
+            }
+            table.push_str(&format!(" {} |\n", self.total_wins(i)));
+        }
+
+        table
+    }
+}
+
+/// Round-robin tournament configuration
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    pub strategies: Vec<AIStrategy>,
+    pub width: usize,
+    pub height: usize,
+    pub games_per_pair: usize,
+    pub seed: u64,
+}
+
+impl Tournament {
+    /// Tournament between all `AIStrategy` variants
+    pub fn new(width: usize, height: usize, games_per_pair: usize, seed: u64) -> Self {
+        Tournament {
+            strategies: AIStrategy::all().to_vec(),
+            width,
+            height,
+            games_per_pair,
+            seed,
+        }
+    }
+
+    /// Play every pair of strategies `games_per_pair` times
+    ///
+    /// Seats alternate between games so neither strategy always moves first.
+    pub fn run(&self) -> TournamentResults {
+        let names = self.strategies.iter().map(|s| format!("{:?}", s)).collect();
+        let mut results = TournamentResults::new(names);
+        let mut game_seed = self.seed;
+
+        for i in 0..self.strategies.len() {
+            for j in (i + 1)..self.strategies.len() {
+                for game in 0..self.games_per_pair {
+                    let (first, second) = if game % 2 == 0 { (i, j) } else { (j, i) };
+                    let outcome = Simulation::with_board(
+                        self.strategies[first],
+                        self.strategies[second],
+                        self.width,
+                        self.height,
+                        game_seed,
+                    )
+                    .run();
+                    game_seed = game_seed.wrapping_add(1);
+
+                    let winner = match outcome.winner {
+                        1 => Some(first),
+                        2 => Some(second),
+                        _ => None,
+                    };
+                    results.record(i, j, winner);
+                }
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_small_tournament() -> TournamentResults {
+        Tournament::new(10, 8, 1, 3).run()
+    }
+
+    #[test]
+    fn test_tournament_plays_all_pairs() {
+        let results = run_small_tournament();
+        let n = results.strategies.len();
+        assert_eq!(n, 14);
+
+        let games: usize = (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .filter(|(i, j)| i < j)
+            .map(|(i, j)| results.wins[i][j] + results.ties[i][j] + results.losses[i][j])
+            .sum();
+        assert_eq!(games, 91);
+    }
+
+    #[test]
+    fn test_tournament_matrix_is_anti_symmetric() {
+        let results = run_small_tournament();
+        let n = results.strategies.len();
+
+        for i in 0..n {
+            assert_eq!(results.wins[i][i], 0);
+            assert_eq!(results.ties[i][i], 0);
+            assert_eq!(results.losses[i][i], 0);
+            for j in 0..n {
+                assert_eq!(results.wins[i][j], results.losses[j][i]);
+                assert_eq!(results.ties[i][j], results.ties[j][i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_best_strategy_is_a_participant() {
+        let results = run_small_tournament();
+        assert!(results.strategies.contains(&results.best_strategy()));
+    }
+
+    #[test]
+    fn test_record_and_markdown_table() {
+        let mut results = TournamentResults::new(vec!["A".to_string(), "B".to_string()]);
+        results.record(0, 1, Some(0));
+        results.record(0, 1, Some(0));
+        results.record(0, 1, None);
+
+        assert_eq!(results.best_strategy(), "A");
+        let table = results.to_markdown_table();
+        assert!(table.contains("| A | - | 2-1-0 | 2 |"));
+        assert!(table.contains("| B | 0-1-2 | - | 0 |"));
+    }
+}
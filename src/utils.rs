@@ -2,7 +2,8 @@
 /// 
 /// Common helper functions used across modules
 
-use crate::game_state::Position;
+use crate::game_state::{CellState, Grid, Position};
+use std::collections::VecDeque;
 
 /// Calculate Manhattan distance between two positions
 pub fn manhattan_distance(a: Position, b: Position) -> usize {
@@ -11,6 +12,13 @@ pub fn manhattan_distance(a: Position, b: Position) -> usize {
     dx + dy
 }
 
+/// Calculate Euclidean (straight-line) distance between two positions
+pub fn euclidean_distance(a: Position, b: Position) -> f32 {
+    let dx = a.x as f32 - b.x as f32;
+    let dy = a.y as f32 - b.y as f32;
+    (dx * dx + dy * dy).sqrt()
+}
+
 /// Calculate Chebyshev distance (max of absolute differences)
 pub fn chebyshev_distance(a: Position, b: Position) -> usize {
     let dx = (a.x as i32 - b.x as i32).abs() as usize;
@@ -18,6 +26,50 @@ pub fn chebyshev_distance(a: Position, b: Position) -> usize {
     dx.max(dy)
 }
 
+/// Convex hull of a set of positions by gift wrapping (Jarvis march)
+///
+/// Returns the hull vertices counterclockwise in `(x, y)` coordinates,
+/// starting from the leftmost point (lowest `y` on ties). Points lying on
+/// an edge are not vertices. Sets of one or two distinct points are
+/// returned as is.
+pub fn convex_hull(points: &[Position]) -> Vec<Position> {
+    let mut points = points.to_vec();
+    points.sort_unstable_by_key(|p| (p.x, p.y));
+    points.dedup();
+    if points.len() <= 2 {
+        return points;
+    }
+
+    let cross = |o: Position, a: Position, b: Position| {
+        let (ox, oy) = (o.x as i64, o.y as i64);
+        (a.x as i64 - ox) * (b.y as i64 - oy) - (a.y as i64 - oy) * (b.x as i64 - ox)
+    };
+    let distance = |a: Position, b: Position| {
+        (a.x as i64 - b.x as i64).pow(2) + (a.y as i64 - b.y as i64).pow(2)
+    };
+
+    let start = points[0];
+    let mut hull = vec![start];
+    let mut current = start;
+    loop {
+        // Wrap to the point with every other point on its left, the
+        // farthest one if several are collinear
+        let mut next = if points[0] == current { points[1] } else { points[0] };
+        for &candidate in &points {
+            let turn = cross(current, next, candidate);
+            if turn < 0 || (turn == 0 && distance(current, candidate) > distance(current, next)) {
+                next = candidate;
+            }
+        }
+        if next == start || hull.len() > points.len() {
+            break;
+        }
+        hull.push(next);
+        current = next;
+    }
+    hull
+}
+
 /// Check if two positions are adjacent (4-connected)
 pub fn are_adjacent_4(a: Position, b: Position) -> bool {
     manhattan_distance(a, b) == 1
@@ -28,6 +80,43 @@ pub fn are_adjacent_8(a: Position, b: Position) -> bool {
     chebyshev_distance(a, b) == 1
 }
 
+/// Compute BFS distances from a set of source positions
+///
+/// The search expands only through empty cells; occupied cells adjacent to
+/// the frontier still receive a distance but block further expansion.
+/// Unreachable cells are `None`. Indexed as `map[y][x]`.
+pub fn bfs_distance_map(grid: &Grid, sources: &[Position]) -> Vec<Vec<Option<usize>>> {
+    let mut distances = vec![vec![None; grid.width]; grid.height];
+    let mut queue = VecDeque::new();
+
+    for &pos in sources {
+        if grid.is_valid(pos) && distances[pos.y][pos.x].is_none() {
+            distances[pos.y][pos.x] = Some(0);
+            queue.push_back(pos);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        let next_distance = distances[pos.y][pos.x].unwrap_or(0) + 1;
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let Some(neighbor) = pos.checked_add(dx, dy) else {
+                continue;
+            };
+            let (nx, ny) = (neighbor.x, neighbor.y);
+            if !grid.is_valid(neighbor) || distances[ny][nx].is_some() {
+                continue;
+            }
+
+            distances[ny][nx] = Some(next_distance);
+            if grid.get(neighbor) == Some(CellState::Empty) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
 /// Clamp a value between min and max
 pub fn clamp<T: std::cmp::PartialOrd>(val: T, min: T, max: T) -> T {
     if val < min {
@@ -50,6 +139,28 @@ mod tests {
         assert_eq!(manhattan_distance(a, b), 7);
     }
 
+    #[test]
+    fn test_euclidean_distance() {
+        let a = Position::new(0, 0);
+        let b = Position::new(3, 4);
+        assert_eq!(euclidean_distance(a, b), 5.0);
+        assert_eq!(euclidean_distance(b, a), 5.0);
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let p = Position::new;
+        // A plus sign: the arm tips are the hull, the center and the
+        // cells between tips are not
+        let plus = [p(2, 0), p(0, 2), p(1, 2), p(2, 2), p(3, 2), p(4, 2), p(2, 4), p(2, 1), p(2, 3)];
+        assert_eq!(convex_hull(&plus), vec![p(0, 2), p(2, 0), p(4, 2), p(2, 4)]);
+
+        let line = [p(0, 0), p(1, 1), p(2, 2), p(1, 1)];
+        assert_eq!(convex_hull(&line), vec![p(0, 0), p(2, 2)]);
+        assert_eq!(convex_hull(&[p(3, 3), p(3, 3)]), vec![p(3, 3)]);
+        assert!(convex_hull(&[]).is_empty());
+    }
+
     #[test]
     fn test_chebyshev_distance() {
         let a = Position::new(0, 0);
@@ -81,4 +192,35 @@ mod tests {
         assert!(!are_adjacent_8(a, Position::new(4, 4))); // too far
         assert!(!are_adjacent_8(a, Position::new(2, 0))); // too far
     }
+
+    #[test]
+    fn test_bfs_distance_map() {
+        let raw = vec![
+            vec!['$', '.', '.', '.'],
+            vec!['.', '@', '@', '.'],
+            vec!['.', '@', '.', '.'],
+        ];
+        let grid = Grid::from_chars(4, 3, raw);
+        let map = bfs_distance_map(&grid, &[Position::new(0, 0)]);
+
+        assert_eq!(map[0][0], Some(0));
+        assert_eq!(map[0][3], Some(3));
+        // Occupied cells get a distance but block the search
+        assert_eq!(map[1][1], Some(2));
+        // (2, 2) must be reached around the @ wall
+        assert_eq!(map[2][2], Some(6));
+    }
+
+    #[test]
+    fn test_bfs_distance_map_unreachable() {
+        let raw = vec![
+            vec!['$', '@', '.'],
+            vec!['@', '@', '.'],
+        ];
+        let grid = Grid::from_chars(3, 2, raw);
+        let map = bfs_distance_map(&grid, &[Position::new(0, 0)]);
+
+        assert_eq!(map[0][2], None);
+        assert_eq!(map[1][2], None);
+    }
 }